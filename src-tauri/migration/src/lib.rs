@@ -14,6 +14,40 @@ mod m20260105_025937_seed_initial_data;
 mod m20260107_010000_permissions_table;
 mod m20260107_010001_profile_permissions_table;
 mod m20260107_010002_seed_permissions;
+mod m20260112_010000_sales_cancellation;
+mod m20260114_010000_seed_price_override_permission;
+mod m20260116_010000_sales_change_amount;
+mod m20260120_010000_products_sat_keys;
+mod m20260120_010100_invoices_table;
+mod m20260120_010200_seed_invoice_permissions;
+mod m20260122_010000_customers_table;
+mod m20260122_010100_seed_customer_permissions;
+mod m20260124_010000_sales_discounts;
+mod m20260124_010100_profiles_max_discount;
+mod m20260126_010000_promotions_table;
+mod m20260126_010100_seed_promotion_permissions;
+mod m20260128_010000_coupons_tables;
+mod m20260128_010100_seed_coupon_permissions;
+mod m20260130_010000_parked_sales_tables;
+mod m20260201_010000_quotes_tables;
+mod m20260201_010100_seed_quote_permissions;
+mod m20260203_010000_cash_sessions_tables;
+mod m20260203_010100_seed_cash_session_permissions;
+mod m20260205_010000_cash_movements_table;
+mod m20260205_010100_seed_cash_movement_permissions;
+mod m20260207_010000_sales_layaway;
+mod m20260207_010100_seed_layaway_permissions;
+mod m20260209_010000_customer_credit;
+mod m20260209_010100_seed_customer_credit_permissions;
+mod m20260211_010000_loyalty_points;
+mod m20260211_010100_seed_loyalty_permissions;
+mod m20260213_010000_gift_cards;
+mod m20260213_010100_seed_gift_card_permissions;
+mod m20260215_010000_decimal_quantities;
+mod m20260217_010000_product_taxes;
+mod m20260219_010000_sale_idempotency;
+mod m20260221_010000_payment_method_tender;
+mod m20260221_020000_invoices_unique_sale;
 pub struct Migrator;
 
 #[async_trait::async_trait]
@@ -34,6 +68,40 @@ impl MigratorTrait for Migrator {
             Box::new(m20260107_010000_permissions_table::Migration),
             Box::new(m20260107_010001_profile_permissions_table::Migration),
             Box::new(m20260107_010002_seed_permissions::Migration),
+            Box::new(m20260112_010000_sales_cancellation::Migration),
+            Box::new(m20260114_010000_seed_price_override_permission::Migration),
+            Box::new(m20260116_010000_sales_change_amount::Migration),
+            Box::new(m20260120_010000_products_sat_keys::Migration),
+            Box::new(m20260120_010100_invoices_table::Migration),
+            Box::new(m20260120_010200_seed_invoice_permissions::Migration),
+            Box::new(m20260122_010000_customers_table::Migration),
+            Box::new(m20260122_010100_seed_customer_permissions::Migration),
+            Box::new(m20260124_010000_sales_discounts::Migration),
+            Box::new(m20260124_010100_profiles_max_discount::Migration),
+            Box::new(m20260126_010000_promotions_table::Migration),
+            Box::new(m20260126_010100_seed_promotion_permissions::Migration),
+            Box::new(m20260128_010000_coupons_tables::Migration),
+            Box::new(m20260128_010100_seed_coupon_permissions::Migration),
+            Box::new(m20260130_010000_parked_sales_tables::Migration),
+            Box::new(m20260201_010000_quotes_tables::Migration),
+            Box::new(m20260201_010100_seed_quote_permissions::Migration),
+            Box::new(m20260203_010000_cash_sessions_tables::Migration),
+            Box::new(m20260203_010100_seed_cash_session_permissions::Migration),
+            Box::new(m20260205_010000_cash_movements_table::Migration),
+            Box::new(m20260205_010100_seed_cash_movement_permissions::Migration),
+            Box::new(m20260207_010000_sales_layaway::Migration),
+            Box::new(m20260207_010100_seed_layaway_permissions::Migration),
+            Box::new(m20260209_010000_customer_credit::Migration),
+            Box::new(m20260209_010100_seed_customer_credit_permissions::Migration),
+            Box::new(m20260211_010000_loyalty_points::Migration),
+            Box::new(m20260211_010100_seed_loyalty_permissions::Migration),
+            Box::new(m20260213_010000_gift_cards::Migration),
+            Box::new(m20260213_010100_seed_gift_card_permissions::Migration),
+            Box::new(m20260215_010000_decimal_quantities::Migration),
+            Box::new(m20260217_010000_product_taxes::Migration),
+            Box::new(m20260219_010000_sale_idempotency::Migration),
+            Box::new(m20260221_010000_payment_method_tender::Migration),
+            Box::new(m20260221_020000_invoices_unique_sale::Migration),
         ]
     }
 }
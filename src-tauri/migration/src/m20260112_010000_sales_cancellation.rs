@@ -0,0 +1,44 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table("sales")
+                    .add_column(string_len_null("cancelled_by", 36))
+                    .add_column(timestamp_with_time_zone_null("cancelled_at"))
+                    .add_column(text_null("cancellation_reason"))
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_sales_cancelled_by")
+                            .from_tbl("sales")
+                            .from_col("cancelled_by")
+                            .to_tbl("users")
+                            .to_col("id")
+                            .on_delete(ForeignKeyAction::Restrict)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table("sales")
+                    .drop_foreign_key("fk_sales_cancelled_by")
+                    .drop_column("cancellation_reason")
+                    .drop_column("cancelled_at")
+                    .drop_column("cancelled_by")
+                    .to_owned(),
+            )
+            .await
+    }
+}
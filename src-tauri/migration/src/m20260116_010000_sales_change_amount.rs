@@ -0,0 +1,30 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Cambio entregado al cliente, necesario para reimprimir tickets
+        manager
+            .alter_table(
+                Table::alter()
+                    .table("sales")
+                    .add_column(decimal_len("change_amount", 10, 2).not_null().default(0))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table("sales")
+                    .drop_column("change_amount")
+                    .to_owned(),
+            )
+            .await
+    }
+}
@@ -0,0 +1,38 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Claves del catálogo SAT para facturación (CFDI 4.0)
+        manager
+            .alter_table(
+                Table::alter()
+                    .table("products")
+                    .add_column(
+                        string_len("sat_product_key", 8)
+                            .not_null()
+                            .default("01010101"), // ClaveProdServ: "No existe en el catálogo"
+                    )
+                    .add_column(
+                        string_len("sat_unit_key", 3).not_null().default("H87"), // ClaveUnidad: Pieza
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table("products")
+                    .drop_column("sat_unit_key")
+                    .drop_column("sat_product_key")
+                    .to_owned(),
+            )
+            .await
+    }
+}
@@ -0,0 +1,71 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table("invoices")
+                    .if_not_exists()
+                    .col(integer("id").not_null().primary_key().auto_increment())
+                    .col(string_len("sale_id", 36).not_null())
+                    .col(string_len("receiver_rfc", 13).not_null())
+                    .col(string_len("receiver_name", 254).not_null())
+                    .col(string_len("receiver_postal_code", 5).not_null())
+                    .col(string_len("receiver_tax_regime", 3).not_null())
+                    .col(string_len("cfdi_use", 4).not_null())
+                    .col(string_len("payment_form", 2).not_null())
+                    .col(decimal_len("subtotal", 10, 2).not_null())
+                    .col(decimal_len("total", 10, 2).not_null())
+                    .col(string_len("status", 20).not_null().default("draft")) // draft, stamped
+                    .col(text("xml").not_null())
+                    .col(string_len_null("uuid", 36).unique_key()) // Folio fiscal asignado por el PAC
+                    .col(timestamp_with_time_zone_null("stamped_at"))
+                    .col(
+                        timestamp_with_time_zone("created_at")
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        timestamp_with_time_zone("updated_at")
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(string_len("created_by", 36))
+                    .col(string_len("updated_by", 36))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from("invoices", "sale_id")
+                            .to("sales", "id")
+                            .on_delete(ForeignKeyAction::Restrict)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from("invoices", "created_by")
+                            .to("users", "id")
+                            .on_delete(ForeignKeyAction::Restrict)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from("invoices", "updated_by")
+                            .to("users", "id")
+                            .on_delete(ForeignKeyAction::Restrict)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table("invoices").to_owned())
+            .await
+    }
+}
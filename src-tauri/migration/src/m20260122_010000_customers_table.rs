@@ -0,0 +1,88 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table("customers")
+                    .if_not_exists()
+                    .col(integer("id").not_null().primary_key().auto_increment())
+                    .col(string_len("name", 254).not_null())
+                    .col(string_len_null("rfc", 13).unique_key())
+                    .col(string_len_null("tax_regime", 3)) // RegimenFiscal del SAT
+                    .col(string_len_null("cfdi_use", 4)) // UsoCFDI del SAT
+                    .col(string_len_null("postal_code", 5))
+                    .col(string_len_null("email", 254))
+                    .col(string_len_null("phone", 20))
+                    .col(boolean("is_active").not_null().default(true))
+                    .col(
+                        timestamp_with_time_zone("created_at")
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        timestamp_with_time_zone("updated_at")
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(string_len("created_by", 36))
+                    .col(string_len("updated_by", 36))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from("customers", "created_by")
+                            .to("users", "id")
+                            .on_delete(ForeignKeyAction::Restrict)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from("customers", "updated_by")
+                            .to("users", "id")
+                            .on_delete(ForeignKeyAction::Restrict)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table("sales")
+                    .add_column(integer_null("customer_id"))
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_sales_customer_id")
+                            .from_tbl("sales")
+                            .from_col("customer_id")
+                            .to_tbl("customers")
+                            .to_col("id")
+                            .on_delete(ForeignKeyAction::Restrict)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table("sales")
+                    .drop_foreign_key("fk_sales_customer_id")
+                    .drop_column("customer_id")
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table("customers").to_owned())
+            .await
+    }
+}
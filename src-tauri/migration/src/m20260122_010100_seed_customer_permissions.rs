@@ -0,0 +1,101 @@
+use sea_orm_migration::prelude::*;
+
+const PERMISSION_CODES: [&str; 4] = [
+    "customer.view",
+    "customer.create",
+    "customer.update",
+    "customer.delete",
+];
+
+// El Cajero puede consultar y registrar clientes al momento de la venta
+const CASHIER_PERMISSION_CODES: [&str; 2] = ["customer.view", "customer.create"];
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .exec_stmt(
+                Query::insert()
+                    .into_table(Alias::new("permissions"))
+                    .columns([
+                        Alias::new("code"),
+                        Alias::new("name"),
+                        Alias::new("module"),
+                        Alias::new("description"),
+                    ])
+                    // === CLIENTES ===
+                    .values_panic([
+                        "customer.view".into(),
+                        "Ver clientes".into(),
+                        "customers".into(),
+                        "Permite consultar el catálogo de clientes".into(),
+                    ])
+                    .values_panic([
+                        "customer.create".into(),
+                        "Crear clientes".into(),
+                        "customers".into(),
+                        "Permite registrar nuevos clientes".into(),
+                    ])
+                    .values_panic([
+                        "customer.update".into(),
+                        "Editar clientes".into(),
+                        "customers".into(),
+                        "Permite modificar los datos de los clientes".into(),
+                    ])
+                    .values_panic([
+                        "customer.delete".into(),
+                        "Eliminar clientes".into(),
+                        "customers".into(),
+                        "Permite desactivar clientes".into(),
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        // Administrador (1) y Gerente (3) con todos los permisos, Cajero (2) parcial
+        for (profile_id, codes) in [
+            (1, &PERMISSION_CODES[..]),
+            (2, &CASHIER_PERMISSION_CODES[..]),
+            (3, &PERMISSION_CODES[..]),
+        ] {
+            manager
+                .exec_stmt(
+                    Query::insert()
+                        .into_table(Alias::new("profile_permissions"))
+                        .columns([Alias::new("profile_id"), Alias::new("permission_id")])
+                        .select_from(
+                            Query::select()
+                                .expr(Expr::val(profile_id))
+                                .column(Alias::new("id"))
+                                .from(Alias::new("permissions"))
+                                .and_where(
+                                    Expr::col(Alias::new("code")).is_in(codes.iter().copied()),
+                                )
+                                .to_owned(),
+                        )
+                        .map_err(|e| DbErr::Migration(e.to_string()))?
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Las asignaciones en profile_permissions se eliminan en cascada
+        manager
+            .exec_stmt(
+                Query::delete()
+                    .from_table(Alias::new("permissions"))
+                    .and_where(Expr::col(Alias::new("code")).is_in(PERMISSION_CODES))
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,63 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Descuento por línea (propio y parte prorrateada del descuento al ticket)
+        manager
+            .alter_table(
+                Table::alter()
+                    .table("sale_details")
+                    .add_column(string_len_null("discount_type", 10)) // percent, amount
+                    .add_column(decimal_len("discount_value", 10, 2).not_null().default(0))
+                    .add_column(decimal_len("discount_amount", 10, 2).not_null().default(0))
+                    .add_column(
+                        decimal_len("ticket_discount_amount", 10, 2)
+                            .not_null()
+                            .default(0),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Descuento al ticket
+        manager
+            .alter_table(
+                Table::alter()
+                    .table("sales")
+                    .add_column(string_len_null("discount_type", 10)) // percent, amount
+                    .add_column(decimal_len("discount_value", 10, 2).not_null().default(0))
+                    .add_column(decimal_len("discount_amount", 10, 2).not_null().default(0))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table("sales")
+                    .drop_column("discount_amount")
+                    .drop_column("discount_value")
+                    .drop_column("discount_type")
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table("sale_details")
+                    .drop_column("ticket_discount_amount")
+                    .drop_column("discount_amount")
+                    .drop_column("discount_value")
+                    .drop_column("discount_type")
+                    .to_owned(),
+            )
+            .await
+    }
+}
@@ -0,0 +1,97 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+const CASHIER_PROFILE_ID: i32 = 2;
+const CASHIER_MAX_DISCOUNT: i32 = 10;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Descuento máximo por perfil en porcentaje; NULL = sin límite
+        manager
+            .alter_table(
+                Table::alter()
+                    .table("profiles")
+                    .add_column(decimal_len_null("max_discount_percent", 5, 2))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .exec_stmt(
+                Query::update()
+                    .table(Alias::new("profiles"))
+                    .value(Alias::new("max_discount_percent"), CASHIER_MAX_DISCOUNT)
+                    .and_where(Expr::col(Alias::new("id")).eq(CASHIER_PROFILE_ID))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .exec_stmt(
+                Query::insert()
+                    .into_table(Alias::new("permissions"))
+                    .columns([
+                        Alias::new("code"),
+                        Alias::new("name"),
+                        Alias::new("module"),
+                        Alias::new("description"),
+                    ])
+                    .values_panic([
+                        "sales.discount".into(),
+                        "Aplicar descuentos".into(),
+                        "sales".into(),
+                        "Permite aplicar descuentos por línea o al ticket hasta el máximo del perfil"
+                            .into(),
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        // Asignar a Administrador (1), Cajero (2) y Gerente (3)
+        for profile_id in [1, 2, 3] {
+            manager
+                .exec_stmt(
+                    Query::insert()
+                        .into_table(Alias::new("profile_permissions"))
+                        .columns([Alias::new("profile_id"), Alias::new("permission_id")])
+                        .select_from(
+                            Query::select()
+                                .expr(Expr::val(profile_id))
+                                .column(Alias::new("id"))
+                                .from(Alias::new("permissions"))
+                                .and_where(Expr::col(Alias::new("code")).eq("sales.discount"))
+                                .to_owned(),
+                        )
+                        .map_err(|e| DbErr::Migration(e.to_string()))?
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Las asignaciones en profile_permissions se eliminan en cascada
+        manager
+            .exec_stmt(
+                Query::delete()
+                    .from_table(Alias::new("permissions"))
+                    .and_where(Expr::col(Alias::new("code")).eq("sales.discount"))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table("profiles")
+                    .drop_column("max_discount_percent")
+                    .to_owned(),
+            )
+            .await
+    }
+}
@@ -0,0 +1,116 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table("promotions")
+                    .if_not_exists()
+                    .col(integer("id").not_null().primary_key().auto_increment())
+                    .col(string_len("name", 100).not_null())
+                    .col(string_len("promotion_type", 10).not_null()) // nxm, percent
+                    .col(integer_null("buy_quantity")) // N en NxM
+                    .col(integer_null("pay_quantity")) // M en NxM
+                    .col(decimal_len_null("discount_percent", 5, 2))
+                    // La promoción aplica a un producto o a una categoría
+                    .col(integer_null("product_id"))
+                    .col(integer_null("category_id"))
+                    // Vigencia: fechas, días de la semana (1 = lunes ... 7 = domingo) y horario
+                    .col(date_null("starts_on"))
+                    .col(date_null("ends_on"))
+                    .col(string_len_null("weekdays", 20))
+                    .col(time_null("starts_at"))
+                    .col(time_null("ends_at"))
+                    .col(boolean("is_active").not_null().default(true))
+                    .col(
+                        timestamp_with_time_zone("created_at")
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        timestamp_with_time_zone("updated_at")
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(string_len("created_by", 36))
+                    .col(string_len("updated_by", 36))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from("promotions", "product_id")
+                            .to("products", "id")
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from("promotions", "category_id")
+                            .to("categories", "id")
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from("promotions", "created_by")
+                            .to("users", "id")
+                            .on_delete(ForeignKeyAction::Restrict)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from("promotions", "updated_by")
+                            .to("users", "id")
+                            .on_delete(ForeignKeyAction::Restrict)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Promoción aplicada a cada línea de venta
+        manager
+            .alter_table(
+                Table::alter()
+                    .table("sale_details")
+                    .add_column(integer_null("promotion_id"))
+                    .add_column(
+                        decimal_len("promotion_discount_amount", 10, 2)
+                            .not_null()
+                            .default(0),
+                    )
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_sale_details_promotion_id")
+                            .from_tbl("sale_details")
+                            .from_col("promotion_id")
+                            .to_tbl("promotions")
+                            .to_col("id")
+                            .on_delete(ForeignKeyAction::Restrict)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table("sale_details")
+                    .drop_foreign_key("fk_sale_details_promotion_id")
+                    .drop_column("promotion_discount_amount")
+                    .drop_column("promotion_id")
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table("promotions").to_owned())
+            .await
+    }
+}
@@ -0,0 +1,146 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Lote de cupones: define el valor y las condiciones de todos sus códigos
+        manager
+            .create_table(
+                Table::create()
+                    .table("coupon_batches")
+                    .if_not_exists()
+                    .col(integer("id").not_null().primary_key().auto_increment())
+                    .col(string_len("name", 100).not_null())
+                    .col(string_len("discount_type", 10).not_null()) // percent, amount
+                    .col(decimal_len("discount_value", 10, 2).not_null())
+                    .col(decimal_len("min_purchase", 10, 2).not_null().default(0))
+                    .col(date_null("expires_on"))
+                    .col(integer("usage_limit").not_null().default(1)) // Usos por código
+                    .col(boolean("is_active").not_null().default(true))
+                    .col(
+                        timestamp_with_time_zone("created_at")
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        timestamp_with_time_zone("updated_at")
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(string_len("created_by", 36))
+                    .col(string_len("updated_by", 36))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from("coupon_batches", "created_by")
+                            .to("users", "id")
+                            .on_delete(ForeignKeyAction::Restrict)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from("coupon_batches", "updated_by")
+                            .to("users", "id")
+                            .on_delete(ForeignKeyAction::Restrict)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table("coupons")
+                    .if_not_exists()
+                    .col(integer("id").not_null().primary_key().auto_increment())
+                    .col(integer("batch_id").not_null())
+                    .col(string_len("code", 32).not_null().unique_key())
+                    .col(integer("times_used").not_null().default(0))
+                    .col(
+                        timestamp_with_time_zone("created_at")
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from("coupons", "batch_id")
+                            .to("coupon_batches", "id")
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Cupón canjeado en la venta y su descuento prorrateado por línea
+        manager
+            .alter_table(
+                Table::alter()
+                    .table("sales")
+                    .add_column(integer_null("coupon_id"))
+                    .add_column(
+                        decimal_len("coupon_discount_amount", 10, 2)
+                            .not_null()
+                            .default(0),
+                    )
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_sales_coupon_id")
+                            .from_tbl("sales")
+                            .from_col("coupon_id")
+                            .to_tbl("coupons")
+                            .to_col("id")
+                            .on_delete(ForeignKeyAction::Restrict)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table("sale_details")
+                    .add_column(
+                        decimal_len("coupon_discount_amount", 10, 2)
+                            .not_null()
+                            .default(0),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table("sale_details")
+                    .drop_column("coupon_discount_amount")
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table("sales")
+                    .drop_foreign_key("fk_sales_coupon_id")
+                    .drop_column("coupon_discount_amount")
+                    .drop_column("coupon_id")
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table("coupons").to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table("coupon_batches").to_owned())
+            .await
+    }
+}
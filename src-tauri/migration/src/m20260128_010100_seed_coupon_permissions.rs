@@ -0,0 +1,75 @@
+use sea_orm_migration::prelude::*;
+
+const PERMISSION_CODES: [&str; 2] = ["coupons.view", "coupons.manage"];
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .exec_stmt(
+                Query::insert()
+                    .into_table(Alias::new("permissions"))
+                    .columns([
+                        Alias::new("code"),
+                        Alias::new("name"),
+                        Alias::new("module"),
+                        Alias::new("description"),
+                    ])
+                    // === CUPONES ===
+                    .values_panic([
+                        "coupons.view".into(),
+                        "Ver cupones".into(),
+                        "coupons".into(),
+                        "Permite consultar los lotes de cupones y sus códigos".into(),
+                    ])
+                    .values_panic([
+                        "coupons.manage".into(),
+                        "Gestionar cupones".into(),
+                        "coupons".into(),
+                        "Permite generar y desactivar lotes de cupones".into(),
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        // Asignar al Administrador (profile_id = 1) y al Gerente (profile_id = 3)
+        for profile_id in [1, 3] {
+            manager
+                .exec_stmt(
+                    Query::insert()
+                        .into_table(Alias::new("profile_permissions"))
+                        .columns([Alias::new("profile_id"), Alias::new("permission_id")])
+                        .select_from(
+                            Query::select()
+                                .expr(Expr::val(profile_id))
+                                .column(Alias::new("id"))
+                                .from(Alias::new("permissions"))
+                                .and_where(Expr::col(Alias::new("code")).is_in(PERMISSION_CODES))
+                                .to_owned(),
+                        )
+                        .map_err(|e| DbErr::Migration(e.to_string()))?
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Las asignaciones en profile_permissions se eliminan en cascada
+        manager
+            .exec_stmt(
+                Query::delete()
+                    .from_table(Alias::new("permissions"))
+                    .and_where(Expr::col(Alias::new("code")).is_in(PERMISSION_CODES))
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
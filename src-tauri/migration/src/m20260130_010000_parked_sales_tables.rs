@@ -0,0 +1,90 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Ticket en espera: carrito guardado para cobrarse después desde cualquier caja
+        manager
+            .create_table(
+                Table::create()
+                    .table("parked_sales")
+                    .if_not_exists()
+                    .col(integer("id").not_null().primary_key().auto_increment())
+                    .col(string_len("label", 100).not_null())
+                    .col(integer_null("customer_id"))
+                    .col(string_len_null("discount_type", 10)) // percent, amount
+                    .col(decimal_len("discount_value", 10, 2).not_null().default(0))
+                    .col(string_len_null("coupon_code", 32))
+                    .col(
+                        timestamp_with_time_zone("created_at")
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        timestamp_with_time_zone("updated_at")
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(string_len("created_by", 36))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from("parked_sales", "customer_id")
+                            .to("customers", "id")
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from("parked_sales", "created_by")
+                            .to("users", "id")
+                            .on_delete(ForeignKeyAction::Restrict)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table("parked_sale_items")
+                    .if_not_exists()
+                    .col(integer("id").not_null().primary_key().auto_increment())
+                    .col(integer("parked_sale_id").not_null())
+                    .col(integer("product_id").not_null())
+                    .col(integer("quantity").not_null())
+                    .col(decimal_len_null("price_override", 10, 2))
+                    .col(string_len_null("discount_type", 10))
+                    .col(decimal_len("discount_value", 10, 2).not_null().default(0))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from("parked_sale_items", "parked_sale_id")
+                            .to("parked_sales", "id")
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from("parked_sale_items", "product_id")
+                            .to("products", "id")
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table("parked_sale_items").to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table("parked_sales").to_owned())
+            .await
+    }
+}
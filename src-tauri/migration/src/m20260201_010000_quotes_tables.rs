@@ -0,0 +1,134 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Cotización con los importes vigentes al momento de emitirla
+        manager
+            .create_table(
+                Table::create()
+                    .table("quotes")
+                    .if_not_exists()
+                    .col(integer("id").not_null().primary_key().auto_increment())
+                    .col(integer("customer_id").not_null())
+                    .col(date("valid_until").not_null())
+                    .col(text_null("notes"))
+                    .col(string_len_null("discount_type", 10)) // percent, amount
+                    .col(decimal_len("discount_value", 10, 2).not_null().default(0))
+                    .col(decimal_len("discount_amount", 10, 2).not_null().default(0))
+                    .col(decimal_len("subtotal", 10, 2).not_null())
+                    .col(decimal_len("total", 10, 2).not_null())
+                    .col(string_len("status", 20).not_null().default("open")) // open, converted
+                    .col(string_len_null("sale_id", 36)) // Venta generada al convertirla
+                    .col(
+                        timestamp_with_time_zone("created_at")
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        timestamp_with_time_zone("updated_at")
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(string_len("created_by", 36))
+                    .col(string_len("updated_by", 36))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from("quotes", "customer_id")
+                            .to("customers", "id")
+                            .on_delete(ForeignKeyAction::Restrict)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from("quotes", "sale_id")
+                            .to("sales", "id")
+                            .on_delete(ForeignKeyAction::Restrict)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from("quotes", "created_by")
+                            .to("users", "id")
+                            .on_delete(ForeignKeyAction::Restrict)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from("quotes", "updated_by")
+                            .to("users", "id")
+                            .on_delete(ForeignKeyAction::Restrict)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table("quote_details")
+                    .if_not_exists()
+                    .col(integer("id").not_null().primary_key().auto_increment())
+                    .col(integer("quote_id").not_null())
+                    .col(integer("product_id").not_null())
+                    .col(integer("quantity").not_null())
+                    .col(decimal_len("unit_price", 10, 2).not_null())
+                    .col(decimal_len_null("price_override", 10, 2))
+                    .col(string_len_null("discount_type", 10))
+                    .col(decimal_len("discount_value", 10, 2).not_null().default(0))
+                    .col(decimal_len("discount_amount", 10, 2).not_null().default(0))
+                    .col(
+                        decimal_len("ticket_discount_amount", 10, 2)
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(integer_null("promotion_id"))
+                    .col(
+                        decimal_len("promotion_discount_amount", 10, 2)
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(decimal_len("subtotal", 10, 2).not_null())
+                    .col(decimal_len("tax_rate", 5, 4).not_null())
+                    .col(decimal_len("tax_amount", 10, 2).not_null())
+                    .col(decimal_len("total", 10, 2).not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from("quote_details", "quote_id")
+                            .to("quotes", "id")
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from("quote_details", "product_id")
+                            .to("products", "id")
+                            .on_delete(ForeignKeyAction::Restrict)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from("quote_details", "promotion_id")
+                            .to("promotions", "id")
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table("quote_details").to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table("quotes").to_owned())
+            .await
+    }
+}
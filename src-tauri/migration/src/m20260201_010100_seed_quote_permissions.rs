@@ -0,0 +1,75 @@
+use sea_orm_migration::prelude::*;
+
+const PERMISSION_CODES: [&str; 2] = ["quotes.view", "quotes.create"];
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .exec_stmt(
+                Query::insert()
+                    .into_table(Alias::new("permissions"))
+                    .columns([
+                        Alias::new("code"),
+                        Alias::new("name"),
+                        Alias::new("module"),
+                        Alias::new("description"),
+                    ])
+                    // === COTIZACIONES ===
+                    .values_panic([
+                        "quotes.view".into(),
+                        "Ver cotizaciones".into(),
+                        "quotes".into(),
+                        "Permite consultar e imprimir cotizaciones".into(),
+                    ])
+                    .values_panic([
+                        "quotes.create".into(),
+                        "Crear cotizaciones".into(),
+                        "quotes".into(),
+                        "Permite emitir cotizaciones y convertirlas en venta".into(),
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        // Asignar a Administrador (1), Cajero (2) y Gerente (3)
+        for profile_id in [1, 2, 3] {
+            manager
+                .exec_stmt(
+                    Query::insert()
+                        .into_table(Alias::new("profile_permissions"))
+                        .columns([Alias::new("profile_id"), Alias::new("permission_id")])
+                        .select_from(
+                            Query::select()
+                                .expr(Expr::val(profile_id))
+                                .column(Alias::new("id"))
+                                .from(Alias::new("permissions"))
+                                .and_where(Expr::col(Alias::new("code")).is_in(PERMISSION_CODES))
+                                .to_owned(),
+                        )
+                        .map_err(|e| DbErr::Migration(e.to_string()))?
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Las asignaciones en profile_permissions se eliminan en cascada
+        manager
+            .exec_stmt(
+                Query::delete()
+                    .from_table(Alias::new("permissions"))
+                    .and_where(Expr::col(Alias::new("code")).is_in(PERMISSION_CODES))
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
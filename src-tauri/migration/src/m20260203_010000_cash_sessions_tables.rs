@@ -0,0 +1,161 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Turno de caja: fondo inicial al abrir y conteo de efectivo al cerrar (corte)
+        manager
+            .create_table(
+                Table::create()
+                    .table("cash_sessions")
+                    .if_not_exists()
+                    .col(integer("id").not_null().primary_key().auto_increment())
+                    .col(string_len("user_id", 36).not_null()) // Cajero responsable
+                    .col(string_len("status", 20).not_null().default("open")) // open, closed
+                    .col(decimal_len("opening_float", 10, 2).not_null())
+                    .col(decimal_len_null("expected_cash", 10, 2)) // Calculado al cerrar
+                    .col(decimal_len_null("counted_cash", 10, 2))
+                    .col(decimal_len_null("variance", 10, 2)) // Contado - esperado
+                    .col(text_null("notes"))
+                    .col(
+                        timestamp_with_time_zone("opened_at")
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(timestamp_with_time_zone_null("closed_at"))
+                    .col(string_len_null("closed_by", 36))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from("cash_sessions", "user_id")
+                            .to("users", "id")
+                            .on_delete(ForeignKeyAction::Restrict)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from("cash_sessions", "closed_by")
+                            .to("users", "id")
+                            .on_delete(ForeignKeyAction::Restrict)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Un cajero solo puede tener un turno abierto a la vez
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE UNIQUE INDEX IF NOT EXISTS idx_cash_sessions_open_user \
+                 ON cash_sessions (user_id) WHERE status = 'open'",
+            )
+            .await?;
+
+        // Conteo de efectivo por denominación al cerrar el turno
+        manager
+            .create_table(
+                Table::create()
+                    .table("cash_session_counts")
+                    .if_not_exists()
+                    .col(integer("id").not_null().primary_key().auto_increment())
+                    .col(integer("cash_session_id").not_null())
+                    .col(decimal_len("denomination", 10, 2).not_null())
+                    .col(integer("quantity").not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from("cash_session_counts", "cash_session_id")
+                            .to("cash_sessions", "id")
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Turno en el que se registró cada venta
+        manager
+            .alter_table(
+                Table::alter()
+                    .table("sales")
+                    .add_column(integer_null("cash_session_id"))
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_sales_cash_session_id")
+                            .from_tbl("sales")
+                            .from_col("cash_session_id")
+                            .to_tbl("cash_sessions")
+                            .to_col("id")
+                            .on_delete(ForeignKeyAction::Restrict)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Forma en que se reembolsó cada devolución y turno que entregó el efectivo
+        manager
+            .alter_table(
+                Table::alter()
+                    .table("refunds")
+                    .add_column(integer_null("payment_method_id"))
+                    .add_column(integer_null("cash_session_id"))
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_refunds_payment_method_id")
+                            .from_tbl("refunds")
+                            .from_col("payment_method_id")
+                            .to_tbl("payment_methods")
+                            .to_col("id")
+                            .on_delete(ForeignKeyAction::Restrict)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_refunds_cash_session_id")
+                            .from_tbl("refunds")
+                            .from_col("cash_session_id")
+                            .to_tbl("cash_sessions")
+                            .to_col("id")
+                            .on_delete(ForeignKeyAction::Restrict)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table("refunds")
+                    .drop_foreign_key("fk_refunds_cash_session_id")
+                    .drop_foreign_key("fk_refunds_payment_method_id")
+                    .drop_column("cash_session_id")
+                    .drop_column("payment_method_id")
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table("sales")
+                    .drop_foreign_key("fk_sales_cash_session_id")
+                    .drop_column("cash_session_id")
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table("cash_session_counts").to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table("cash_sessions").to_owned())
+            .await
+    }
+}
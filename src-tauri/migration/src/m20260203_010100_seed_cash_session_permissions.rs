@@ -0,0 +1,90 @@
+use sea_orm_migration::prelude::*;
+
+const PERMISSION_CODES: [&str; 3] = ["cash.open", "cash.close", "cash.view"];
+// Permisos del turno propio; cash.view permite consultar y cerrar turnos de otros cajeros
+const CASHIER_CODES: [&str; 2] = ["cash.open", "cash.close"];
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .exec_stmt(
+                Query::insert()
+                    .into_table(Alias::new("permissions"))
+                    .columns([
+                        Alias::new("code"),
+                        Alias::new("name"),
+                        Alias::new("module"),
+                        Alias::new("description"),
+                    ])
+                    // === CAJA ===
+                    .values_panic([
+                        "cash.open".into(),
+                        "Abrir caja".into(),
+                        "cash".into(),
+                        "Permite abrir un turno de caja con fondo inicial".into(),
+                    ])
+                    .values_panic([
+                        "cash.close".into(),
+                        "Cerrar caja".into(),
+                        "cash".into(),
+                        "Permite hacer el corte de caja del turno propio".into(),
+                    ])
+                    .values_panic([
+                        "cash.view".into(),
+                        "Supervisar cajas".into(),
+                        "cash".into(),
+                        "Permite consultar y cerrar los turnos de todos los cajeros".into(),
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        // Asignar a Administrador (1), Cajero (2) y Gerente (3);
+        // la supervisión solo al Administrador y al Gerente
+        for (profile_id, codes) in [
+            (1, &PERMISSION_CODES[..]),
+            (2, &CASHIER_CODES[..]),
+            (3, &PERMISSION_CODES[..]),
+        ] {
+            manager
+                .exec_stmt(
+                    Query::insert()
+                        .into_table(Alias::new("profile_permissions"))
+                        .columns([Alias::new("profile_id"), Alias::new("permission_id")])
+                        .select_from(
+                            Query::select()
+                                .expr(Expr::val(profile_id))
+                                .column(Alias::new("id"))
+                                .from(Alias::new("permissions"))
+                                .and_where(
+                                    Expr::col(Alias::new("code")).is_in(codes.iter().copied()),
+                                )
+                                .to_owned(),
+                        )
+                        .map_err(|e| DbErr::Migration(e.to_string()))?
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Las asignaciones en profile_permissions se eliminan en cascada
+        manager
+            .exec_stmt(
+                Query::delete()
+                    .from_table(Alias::new("permissions"))
+                    .and_where(Expr::col(Alias::new("code")).is_in(PERMISSION_CODES))
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
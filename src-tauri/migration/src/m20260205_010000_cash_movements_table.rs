@@ -0,0 +1,54 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Entradas y salidas de efectivo durante un turno (retiros a caja fuerte, pagos, cambio)
+        manager
+            .create_table(
+                Table::create()
+                    .table("cash_movements")
+                    .if_not_exists()
+                    .col(integer("id").not_null().primary_key().auto_increment())
+                    .col(integer("cash_session_id").not_null())
+                    .col(string_len("movement_type", 10).not_null()) // in, out
+                    .col(decimal_len("amount", 10, 2).not_null())
+                    .col(text("reason").not_null())
+                    .col(string_len("authorized_by", 36).not_null())
+                    .col(
+                        timestamp_with_time_zone("created_at")
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from("cash_movements", "cash_session_id")
+                            .to("cash_sessions", "id")
+                            .on_delete(ForeignKeyAction::Restrict)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from("cash_movements", "authorized_by")
+                            .to("users", "id")
+                            .on_delete(ForeignKeyAction::Restrict)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table("cash_movements").to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,88 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Apartados: la venta queda pendiente hasta liquidarse; completed_at es la fecha
+        // en que la venta cuenta como ingreso en los reportes
+        manager
+            .alter_table(
+                Table::alter()
+                    .table("sales")
+                    .add_column(string_len_null("layaway_status", 20)) // pending, completed, cancelled
+                    .add_column(date_null("layaway_due_date"))
+                    .add_column(
+                        decimal_len("layaway_penalty_amount", 10, 2)
+                            .not_null()
+                            .default(0),
+                    )
+                    .add_column(timestamp_with_time_zone_null("completed_at"))
+                    .to_owned(),
+            )
+            .await?;
+
+        // Las ventas existentes se completaron al registrarse
+        manager
+            .get_connection()
+            .execute_unprepared("UPDATE sales SET completed_at = created_at")
+            .await?;
+
+        // Turno en el que se recibió cada pago; los abonos de un apartado
+        // pueden llegar en turnos distintos al de la venta
+        manager
+            .alter_table(
+                Table::alter()
+                    .table("sale_payments")
+                    .add_column(integer_null("cash_session_id"))
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_sale_payments_cash_session_id")
+                            .from_tbl("sale_payments")
+                            .from_col("cash_session_id")
+                            .to_tbl("cash_sessions")
+                            .to_col("id")
+                            .on_delete(ForeignKeyAction::Restrict)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "UPDATE sale_payments sp SET cash_session_id = s.cash_session_id \
+                 FROM sales s WHERE sp.sale_id = s.id",
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table("sale_payments")
+                    .drop_foreign_key("fk_sale_payments_cash_session_id")
+                    .drop_column("cash_session_id")
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table("sales")
+                    .drop_column("completed_at")
+                    .drop_column("layaway_penalty_amount")
+                    .drop_column("layaway_due_date")
+                    .drop_column("layaway_status")
+                    .to_owned(),
+            )
+            .await
+    }
+}
@@ -0,0 +1,122 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Cuenta de crédito (fiado): límite autorizado y saldo adeudado por el cliente
+        manager
+            .alter_table(
+                Table::alter()
+                    .table("customers")
+                    .add_column(decimal_len("credit_limit", 10, 2).not_null().default(0))
+                    .add_column(decimal_len("credit_balance", 10, 2).not_null().default(0))
+                    .to_owned(),
+            )
+            .await?;
+
+        // Movimientos de la cuenta; la suma de los importes siempre es igual al saldo
+        manager
+            .create_table(
+                Table::create()
+                    .table("customer_credit_entries")
+                    .if_not_exists()
+                    .col(integer("id").not_null().primary_key().auto_increment())
+                    .col(integer("customer_id").not_null())
+                    .col(string_len("entry_type", 20).not_null()) // charge, payment, refund, cancellation
+                    .col(decimal_len("amount", 10, 2).not_null()) // Positivo = cargo, negativo = abono
+                    .col(decimal_len("balance_after", 10, 2).not_null())
+                    .col(string_len_null("sale_id", 36))
+                    .col(integer_null("payment_method_id")) // Forma en que se recibió un abono
+                    .col(integer_null("cash_session_id"))
+                    .col(text_null("notes"))
+                    .col(string_len("created_by", 36).not_null())
+                    .col(
+                        timestamp_with_time_zone("created_at")
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from("customer_credit_entries", "customer_id")
+                            .to("customers", "id")
+                            .on_delete(ForeignKeyAction::Restrict)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from("customer_credit_entries", "sale_id")
+                            .to("sales", "id")
+                            .on_delete(ForeignKeyAction::Restrict)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from("customer_credit_entries", "payment_method_id")
+                            .to("payment_methods", "id")
+                            .on_delete(ForeignKeyAction::Restrict)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from("customer_credit_entries", "cash_session_id")
+                            .to("cash_sessions", "id")
+                            .on_delete(ForeignKeyAction::Restrict)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from("customer_credit_entries", "created_by")
+                            .to("users", "id")
+                            .on_delete(ForeignKeyAction::Restrict)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Forma de pago para cargar la venta a la cuenta del cliente (99 = Por definir)
+        manager
+            .exec_stmt(
+                Query::insert()
+                    .into_table(Alias::new("payment_methods"))
+                    .columns([
+                        Alias::new("name"),
+                        Alias::new("sat_key"),
+                        Alias::new("is_active"),
+                    ])
+                    .values_panic(["Crédito de cliente".into(), "99".into(), true.into()])
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .exec_stmt(
+                Query::delete()
+                    .from_table(Alias::new("payment_methods"))
+                    .and_where(Expr::col(Alias::new("sat_key")).eq("99"))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table("customer_credit_entries").to_owned())
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table("customers")
+                    .drop_column("credit_balance")
+                    .drop_column("credit_limit")
+                    .to_owned(),
+            )
+            .await
+    }
+}
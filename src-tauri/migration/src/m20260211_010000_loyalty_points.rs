@@ -0,0 +1,165 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Saldo de puntos del cliente
+        manager
+            .alter_table(
+                Table::alter()
+                    .table("customers")
+                    .add_column(integer("loyalty_points").not_null().default(0))
+                    .to_owned(),
+            )
+            .await?;
+
+        // Multiplicador de puntos por categoría (2 = doble de puntos)
+        manager
+            .alter_table(
+                Table::alter()
+                    .table("categories")
+                    .add_column(decimal_len("points_multiplier", 5, 2).not_null().default(1))
+                    .to_owned(),
+            )
+            .await?;
+
+        // Configuración del programa; una sola fila editable por el administrador
+        manager
+            .create_table(
+                Table::create()
+                    .table("loyalty_settings")
+                    .if_not_exists()
+                    .col(integer("id").not_null().primary_key().auto_increment())
+                    .col(decimal_len("earn_rate", 10, 4).not_null()) // Puntos por peso pagado
+                    .col(decimal_len("point_value", 10, 4).not_null()) // Pesos por punto al canjear
+                    .col(boolean("is_active").not_null().default(true))
+                    .col(string_len_null("updated_by", 36))
+                    .col(
+                        timestamp_with_time_zone("updated_at")
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from("loyalty_settings", "updated_by")
+                            .to("users", "id")
+                            .on_delete(ForeignKeyAction::Restrict)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Por defecto: 1 punto por cada $10 y cada punto vale $0.10
+        manager
+            .exec_stmt(
+                Query::insert()
+                    .into_table(Alias::new("loyalty_settings"))
+                    .columns([Alias::new("earn_rate"), Alias::new("point_value")])
+                    .values_panic(["0.1".into(), "0.1".into()])
+                    .to_owned(),
+            )
+            .await?;
+
+        // Movimientos de puntos; la suma siempre es igual al saldo del cliente
+        manager
+            .create_table(
+                Table::create()
+                    .table("loyalty_entries")
+                    .if_not_exists()
+                    .col(integer("id").not_null().primary_key().auto_increment())
+                    .col(integer("customer_id").not_null())
+                    .col(string_len("entry_type", 20).not_null()) // earn, redeem, refund, reversal
+                    .col(integer("points").not_null()) // Positivo = acumula, negativo = descuenta
+                    .col(integer("balance_after").not_null())
+                    .col(string_len_null("sale_id", 36))
+                    .col(text_null("notes"))
+                    .col(string_len("created_by", 36).not_null())
+                    .col(
+                        timestamp_with_time_zone("created_at")
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from("loyalty_entries", "customer_id")
+                            .to("customers", "id")
+                            .on_delete(ForeignKeyAction::Restrict)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from("loyalty_entries", "sale_id")
+                            .to("sales", "id")
+                            .on_delete(ForeignKeyAction::Restrict)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from("loyalty_entries", "created_by")
+                            .to("users", "id")
+                            .on_delete(ForeignKeyAction::Restrict)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Forma de pago para canjear puntos (05 = Monedero electrónico)
+        manager
+            .exec_stmt(
+                Query::insert()
+                    .into_table(Alias::new("payment_methods"))
+                    .columns([
+                        Alias::new("name"),
+                        Alias::new("sat_key"),
+                        Alias::new("is_active"),
+                    ])
+                    .values_panic(["Puntos de lealtad".into(), "05".into(), true.into()])
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .exec_stmt(
+                Query::delete()
+                    .from_table(Alias::new("payment_methods"))
+                    .and_where(Expr::col(Alias::new("sat_key")).eq("05"))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table("loyalty_entries").to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table("loyalty_settings").to_owned())
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table("categories")
+                    .drop_column("points_multiplier")
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table("customers")
+                    .drop_column("loyalty_points")
+                    .to_owned(),
+            )
+            .await
+    }
+}
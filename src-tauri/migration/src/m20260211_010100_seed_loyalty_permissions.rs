@@ -0,0 +1,67 @@
+use sea_orm_migration::prelude::*;
+
+const PERMISSION_CODES: [&str; 1] = ["loyalty.manage"];
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .exec_stmt(
+                Query::insert()
+                    .into_table(Alias::new("permissions"))
+                    .columns([
+                        Alias::new("code"),
+                        Alias::new("name"),
+                        Alias::new("module"),
+                        Alias::new("description"),
+                    ])
+                    // === LEALTAD ===
+                    .values_panic([
+                        "loyalty.manage".into(),
+                        "Configurar programa de puntos".into(),
+                        "loyalty".into(),
+                        "Permite configurar la acumulación y el canje de puntos".into(),
+                    ])
+                    .to_owned(),
+            )
+            .await?;
+
+        // Solo el Administrador (1) configura el programa
+        manager
+            .exec_stmt(
+                Query::insert()
+                    .into_table(Alias::new("profile_permissions"))
+                    .columns([Alias::new("profile_id"), Alias::new("permission_id")])
+                    .select_from(
+                        Query::select()
+                            .expr(Expr::val(1))
+                            .column(Alias::new("id"))
+                            .from(Alias::new("permissions"))
+                            .and_where(Expr::col(Alias::new("code")).is_in(PERMISSION_CODES))
+                            .to_owned(),
+                    )
+                    .map_err(|e| DbErr::Migration(e.to_string()))?
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Las asignaciones en profile_permissions se eliminan en cascada
+        manager
+            .exec_stmt(
+                Query::delete()
+                    .from_table(Alias::new("permissions"))
+                    .and_where(Expr::col(Alias::new("code")).is_in(PERMISSION_CODES))
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
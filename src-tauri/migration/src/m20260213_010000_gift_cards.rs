@@ -0,0 +1,195 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Productos que al venderse emiten una tarjeta de regalo por el importe cobrado
+        manager
+            .alter_table(
+                Table::alter()
+                    .table("products")
+                    .add_column(boolean("is_gift_card").not_null().default(false))
+                    .to_owned(),
+            )
+            .await?;
+
+        // Tarjetas de regalo y vales de saldo a favor
+        manager
+            .create_table(
+                Table::create()
+                    .table("gift_cards")
+                    .if_not_exists()
+                    .col(integer("id").not_null().primary_key().auto_increment())
+                    .col(string_len("code", 32).not_null().unique_key())
+                    .col(decimal_len("initial_amount", 10, 2).not_null())
+                    .col(decimal_len("balance", 10, 2).not_null())
+                    .col(boolean("is_active").not_null().default(true))
+                    .col(string_len_null("sale_id", 36)) // Venta en que se vendió la tarjeta
+                    .col(
+                        timestamp_with_time_zone("created_at")
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(
+                        timestamp_with_time_zone("updated_at")
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(string_len("created_by", 36).not_null())
+                    .col(string_len("updated_by", 36).not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from("gift_cards", "sale_id")
+                            .to("sales", "id")
+                            .on_delete(ForeignKeyAction::Restrict)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from("gift_cards", "created_by")
+                            .to("users", "id")
+                            .on_delete(ForeignKeyAction::Restrict)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from("gift_cards", "updated_by")
+                            .to("users", "id")
+                            .on_delete(ForeignKeyAction::Restrict)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Movimientos de saldo; la suma siempre es igual al saldo de la tarjeta
+        manager
+            .create_table(
+                Table::create()
+                    .table("gift_card_entries")
+                    .if_not_exists()
+                    .col(integer("id").not_null().primary_key().auto_increment())
+                    .col(integer("gift_card_id").not_null())
+                    .col(string_len("entry_type", 20).not_null()) // issue, redeem, refund, reversal, void
+                    .col(decimal_len("amount", 10, 2).not_null()) // Positivo = carga, negativo = consumo
+                    .col(decimal_len("balance_after", 10, 2).not_null())
+                    .col(string_len_null("sale_id", 36))
+                    .col(integer_null("refund_id"))
+                    .col(text_null("notes"))
+                    .col(string_len("created_by", 36).not_null())
+                    .col(
+                        timestamp_with_time_zone("created_at")
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from("gift_card_entries", "gift_card_id")
+                            .to("gift_cards", "id")
+                            .on_delete(ForeignKeyAction::Restrict)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from("gift_card_entries", "sale_id")
+                            .to("sales", "id")
+                            .on_delete(ForeignKeyAction::Restrict)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from("gift_card_entries", "refund_id")
+                            .to("refunds", "id")
+                            .on_delete(ForeignKeyAction::Restrict)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from("gift_card_entries", "created_by")
+                            .to("users", "id")
+                            .on_delete(ForeignKeyAction::Restrict)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Tarjeta con la que se cubrió cada pago
+        manager
+            .alter_table(
+                Table::alter()
+                    .table("sale_payments")
+                    .add_column(integer_null("gift_card_id"))
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_sale_payments_gift_card_id")
+                            .from_tbl("sale_payments")
+                            .from_col("gift_card_id")
+                            .to_tbl("gift_cards")
+                            .to_col("id")
+                            .on_delete(ForeignKeyAction::Restrict)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Forma de pago para canjear tarjetas de regalo y vales (08 = Vales de despensa)
+        manager
+            .exec_stmt(
+                Query::insert()
+                    .into_table(Alias::new("payment_methods"))
+                    .columns([
+                        Alias::new("name"),
+                        Alias::new("sat_key"),
+                        Alias::new("is_active"),
+                    ])
+                    .values_panic(["Tarjeta de regalo".into(), "08".into(), true.into()])
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .exec_stmt(
+                Query::delete()
+                    .from_table(Alias::new("payment_methods"))
+                    .and_where(Expr::col(Alias::new("sat_key")).eq("08"))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table("sale_payments")
+                    .drop_foreign_key("fk_sale_payments_gift_card_id")
+                    .drop_column("gift_card_id")
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table("gift_card_entries").to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table("gift_cards").to_owned())
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table("products")
+                    .drop_column("is_gift_card")
+                    .to_owned(),
+            )
+            .await
+    }
+}
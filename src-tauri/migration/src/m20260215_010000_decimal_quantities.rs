@@ -0,0 +1,72 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+// Tablas con cantidades de producto que pasan a admitir fracciones
+const QUANTITY_TABLES: [&str; 4] = [
+    "sale_details",
+    "refund_details",
+    "quote_details",
+    "parked_sale_items",
+];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Unidad de medida del producto y si se vende en fracciones (0.750 kg)
+        manager
+            .alter_table(
+                Table::alter()
+                    .table("products")
+                    .add_column(
+                        string_len("unit_of_measure", 10)
+                            .not_null()
+                            .default("piece"),
+                    ) // piece, kg, g, l, m
+                    .add_column(boolean("allow_fractions").not_null().default(false))
+                    .modify_column(decimal_len("stock", 12, 3).not_null().default(0))
+                    .to_owned(),
+            )
+            .await?;
+
+        for table in QUANTITY_TABLES {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(table)
+                        .modify_column(decimal_len("quantity", 12, 3).not_null())
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for table in QUANTITY_TABLES {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(table)
+                        .modify_column(integer("quantity").not_null())
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table("products")
+                    .modify_column(integer("stock").not_null().default(0))
+                    .drop_column("allow_fractions")
+                    .drop_column("unit_of_measure")
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
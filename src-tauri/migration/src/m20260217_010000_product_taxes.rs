@@ -0,0 +1,133 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Impuestos de cada producto; reemplazan la tasa única de products.tax
+        manager
+            .create_table(
+                Table::create()
+                    .table("product_taxes")
+                    .if_not_exists()
+                    .col(integer("id").not_null().primary_key().auto_increment())
+                    .col(integer("product_id").not_null())
+                    .col(string_len("tax_type", 10).not_null()) // iva, ieps
+                    .col(decimal_len("rate", 7, 6).not_null()) // 0.160000 = 16%
+                    .col(boolean("is_included").not_null().default(false)) // Incluido en el precio
+                    .col(boolean("is_exempt").not_null().default(false)) // Exento (distinto de tasa 0%)
+                    .index(
+                        Index::create()
+                            .name("idx_product_taxes_product_type")
+                            .unique()
+                            .col("product_id")
+                            .col("tax_type"),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from("product_taxes", "product_id")
+                            .to("products", "id")
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Desglose de impuestos de cada línea de venta
+        manager
+            .create_table(
+                Table::create()
+                    .table("sale_detail_taxes")
+                    .if_not_exists()
+                    .col(integer("id").not_null().primary_key().auto_increment())
+                    .col(integer("sale_detail_id").not_null())
+                    .col(string_len("tax_type", 10).not_null())
+                    .col(decimal_len("rate", 7, 6).not_null())
+                    .col(boolean("is_included").not_null().default(false))
+                    .col(boolean("is_exempt").not_null().default(false))
+                    .col(decimal_len("base", 10, 2).not_null())
+                    .col(decimal_len("amount", 10, 2).not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from("sale_detail_taxes", "sale_detail_id")
+                            .to("sale_details", "id")
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Desglose de impuestos de cada línea cotizada
+        manager
+            .create_table(
+                Table::create()
+                    .table("quote_detail_taxes")
+                    .if_not_exists()
+                    .col(integer("id").not_null().primary_key().auto_increment())
+                    .col(integer("quote_detail_id").not_null())
+                    .col(string_len("tax_type", 10).not_null())
+                    .col(decimal_len("rate", 7, 6).not_null())
+                    .col(boolean("is_included").not_null().default(false))
+                    .col(boolean("is_exempt").not_null().default(false))
+                    .col(decimal_len("base", 10, 2).not_null())
+                    .col(decimal_len("amount", 10, 2).not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from("quote_detail_taxes", "quote_detail_id")
+                            .to("quote_details", "id")
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .on_update(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // La tasa actual de cada producto pasa a ser su IVA
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "INSERT INTO product_taxes (product_id, tax_type, rate) \
+                 SELECT id, 'iva', tax FROM products",
+            )
+            .await?;
+
+        // Las líneas ya registradas conservan su IVA como desglose
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "INSERT INTO sale_detail_taxes (sale_detail_id, tax_type, rate, base, amount) \
+                 SELECT id, 'iva', tax_rate, subtotal, tax_amount FROM sale_details",
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "INSERT INTO quote_detail_taxes (quote_detail_id, tax_type, rate, base, amount) \
+                 SELECT id, 'iva', tax_rate, subtotal, tax_amount FROM quote_details",
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table("quote_detail_taxes").to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table("sale_detail_taxes").to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table("product_taxes").to_owned())
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,38 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Clave generada por la caja para que un reintento no registre la venta dos veces
+        manager
+            .alter_table(
+                Table::alter()
+                    .table("sales")
+                    .add_column(string_len_null("idempotency_key", 64).unique_key())
+                    .add_column(text_null("idempotency_payload")) // Request original
+                    .add_column(text_null("idempotency_response")) // Respuesta original
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table("sales")
+                    .drop_column("idempotency_response")
+                    .drop_column("idempotency_payload")
+                    .drop_column("idempotency_key")
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,94 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Tipo de cobro de la forma de pago; sat_key queda solo como clave fiscal
+        manager
+            .alter_table(
+                Table::alter()
+                    .table("payment_methods")
+                    .add_column(string_len("tender", 20).not_null().default("other"))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "UPDATE payment_methods SET tender = CASE sat_key \
+                 WHEN '01' THEN 'cash' \
+                 WHEN '04' THEN 'card' \
+                 WHEN '28' THEN 'card' \
+                 WHEN '03' THEN 'transfer' \
+                 WHEN '05' THEN 'points' \
+                 WHEN '08' THEN 'gift_card' \
+                 WHEN '99' THEN 'credit' \
+                 ELSE 'other' END",
+            )
+            .await?;
+
+        // Varias formas de pago pueden compartir clave SAT
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TABLE payment_methods DROP CONSTRAINT IF EXISTS payment_methods_sat_key_key",
+            )
+            .await?;
+
+        // La tarjeta de regalo se salda con saldo de la tienda (05 = Monedero electrónico)
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "UPDATE payment_methods SET sat_key = '05' WHERE tender = 'gift_card'",
+            )
+            .await?;
+
+        // Crédito, puntos y tarjeta de regalo tienen su propio libro; solo una forma de pago de cada uno
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "CREATE UNIQUE INDEX IF NOT EXISTS idx_payment_methods_ledger_tender \
+                 ON payment_methods (tender) WHERE tender IN ('credit', 'points', 'gift_card')",
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("DROP INDEX IF EXISTS idx_payment_methods_ledger_tender")
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "UPDATE payment_methods SET sat_key = '08' WHERE tender = 'gift_card'",
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TABLE payment_methods \
+                 ADD CONSTRAINT payment_methods_sat_key_key UNIQUE (sat_key)",
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table("payment_methods")
+                    .drop_column("tender")
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
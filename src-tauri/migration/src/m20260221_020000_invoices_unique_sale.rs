@@ -0,0 +1,32 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Una venta solo se factura una vez, aun con solicitudes simultáneas
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_invoices_sale_id")
+                    .table("invoices")
+                    .col("sale_id")
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_invoices_sale_id")
+                    .table("invoices")
+                    .to_owned(),
+            )
+            .await
+    }
+}
@@ -0,0 +1,707 @@
+use std::collections::HashMap;
+
+use chrono::{NaiveDate, Utc};
+use sea_orm::{
+    prelude::Decimal, ActiveModelTrait, ActiveValue::Set, ColumnTrait, ConnectionTrait,
+    DatabaseBackend, EntityTrait, FromQueryResult, PaginatorTrait, QueryFilter, QueryOrder,
+    QuerySelect, Statement, TransactionTrait,
+};
+
+use super::structs::{
+    is_valid_denomination, CashCountLine, CashMovement, CashMovementFilter,
+    CashMovementListResponse, CashSession, CashSessionFilter, CashSessionListResponse,
+    CashSessionPaymentTotal, CashSessionReport, CloseCashSessionRequest, CreateCashMovementRequest,
+    OpenCashSessionRequest, MOVEMENT_IN, MOVEMENT_OUT, STATUS_CLOSED, STATUS_OPEN, TENDER_CASH,
+};
+use crate::customer_credit::structs::ENTRY_PAYMENT;
+use crate::entities::{
+    cash_movements, cash_session_counts, cash_sessions, customer_credit_entries,
+    prelude::{
+        CashMovements, CashSessionCounts, CashSessions, CustomerCreditEntries, PaymentMethods,
+        Users,
+    },
+    users,
+};
+use crate::sessions::{require_permission, structs::Session};
+use crate::AppState;
+
+const DB_ERROR: &str = "Error en la base de datos";
+
+#[derive(FromQueryResult)]
+struct SalesSummaryRaw {
+    sales_count: Option<i64>,
+    sales_total: Option<Decimal>,
+    cancelled_count: Option<i64>,
+    cancelled_total: Option<Decimal>,
+}
+
+#[derive(FromQueryResult)]
+struct PaymentTotalRaw {
+    payment_method_id: i32,
+    payment_method_name: String,
+    tender: String,
+    sales_count: Option<i64>,
+    amount: Option<Decimal>,
+}
+
+#[derive(FromQueryResult)]
+struct RefundsSummaryRaw {
+    refunds_count: Option<i64>,
+    refunds_total: Option<Decimal>,
+    cash_refunds: Option<Decimal>,
+}
+
+/// Turno abierto del usuario, si tiene uno. Dentro de una transacción lo bloquea
+/// en modo compartido para que no se cierre mientras se registra una operación.
+pub async fn find_open_session<C: ConnectionTrait>(
+    db: &C,
+    user_id: &str,
+) -> Result<Option<cash_sessions::Model>, String> {
+    CashSessions::find()
+        .filter(cash_sessions::Column::UserId.eq(user_id))
+        .filter(cash_sessions::Column::Status.eq(STATUS_OPEN))
+        .lock_shared()
+        .one(db)
+        .await
+        .map_err(|_| DB_ERROR.to_string())
+}
+
+/// Turno abierto del usuario; las operaciones con efectivo lo requieren
+pub async fn require_open_session<C: ConnectionTrait>(
+    db: &C,
+    user_id: &str,
+) -> Result<cash_sessions::Model, String> {
+    find_open_session(db, user_id)
+        .await?
+        .ok_or_else(|| "No tiene un turno de caja abierto. Abra la caja para continuar".to_string())
+}
+
+/// Nombre completo del usuario
+async fn user_name<C: ConnectionTrait>(db: &C, user_id: &str) -> Result<Option<String>, String> {
+    Ok(Users::find_by_id(user_id.to_string())
+        .one(db)
+        .await
+        .map_err(|_| DB_ERROR)?
+        .map(|user| format!("{} {}", user.first_name, user.last_name)))
+}
+
+/// Nombres completos de varios usuarios
+async fn user_names<C: ConnectionTrait>(
+    db: &C,
+    user_ids: Vec<String>,
+) -> Result<HashMap<String, String>, String> {
+    Ok(Users::find()
+        .filter(users::Column::Id.is_in(user_ids))
+        .all(db)
+        .await
+        .map_err(|_| DB_ERROR)?
+        .into_iter()
+        .map(|user| (user.id, format!("{} {}", user.first_name, user.last_name)))
+        .collect())
+}
+
+/// Solo el cajero del turno o un supervisor (cash.view) puede consultarlo o cerrarlo
+fn ensure_session_access(
+    session: &Session,
+    cash_session: &cash_sessions::Model,
+) -> Result<(), String> {
+    if cash_session.user_id != session.user_id && !session.has_permission("cash.view") {
+        return Err("No tiene permisos para consultar turnos de otros cajeros".to_string());
+    }
+    Ok(())
+}
+
+/// Calcula el corte de un turno con sus ventas, pagos, devoluciones y conteo
+async fn build_report<C: ConnectionTrait>(
+    db: &C,
+    cash_session: cash_sessions::Model,
+) -> Result<CashSessionReport, String> {
+    // Ventas completadas y canceladas del turno; los apartados pendientes no cuentan
+    let sales_summary = SalesSummaryRaw::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        r#"
+        SELECT
+            COUNT(*) FILTER (WHERE status = true AND completed_at IS NOT NULL)::bigint as sales_count,
+            SUM(total) FILTER (WHERE status = true AND completed_at IS NOT NULL) as sales_total,
+            COUNT(*) FILTER (WHERE status = false)::bigint as cancelled_count,
+            SUM(total) FILTER (WHERE status = false) as cancelled_total
+        FROM sales
+        WHERE cash_session_id = $1
+        "#,
+        [cash_session.id.into()],
+    ))
+    .one(db)
+    .await
+    .map_err(|_| DB_ERROR)?;
+
+    // Pagos recibidos en el turno por forma de pago; el efectivo ya viene sin el cambio
+    // entregado. Incluye abonos de apartados. Los pagos de ventas y apartados cancelados
+    // se conservan: su reembolso se registra como devolución en el turno de quien cancela.
+    let payment_rows = PaymentTotalRaw::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        r#"
+        SELECT
+            pm.id as payment_method_id,
+            pm.name as payment_method_name,
+            pm.tender,
+            COUNT(DISTINCT s.id)::bigint as sales_count,
+            SUM(sp.amount) as amount
+        FROM sale_payments sp
+        INNER JOIN sales s ON sp.sale_id = s.id
+        INNER JOIN payment_methods pm ON sp.payment_method_id = pm.id
+        WHERE sp.cash_session_id = $1
+        GROUP BY pm.id, pm.name, pm.tender
+        ORDER BY pm.id
+        "#,
+        [cash_session.id.into()],
+    ))
+    .all(db)
+    .await
+    .map_err(|_| DB_ERROR)?;
+
+    // Devoluciones entregadas durante el turno
+    let refunds_summary = RefundsSummaryRaw::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        r#"
+        SELECT
+            COUNT(*)::bigint as refunds_count,
+            SUM(r.amount) as refunds_total,
+            SUM(r.amount) FILTER (WHERE pm.tender = $2) as cash_refunds
+        FROM refunds r
+        LEFT JOIN payment_methods pm ON r.payment_method_id = pm.id
+        WHERE r.cash_session_id = $1
+        "#,
+        [cash_session.id.into(), TENDER_CASH.into()],
+    ))
+    .one(db)
+    .await
+    .map_err(|_| DB_ERROR)?;
+
+    let counts: Vec<CashCountLine> = CashSessionCounts::find()
+        .filter(cash_session_counts::Column::CashSessionId.eq(cash_session.id))
+        .order_by_desc(cash_session_counts::Column::Denomination)
+        .all(db)
+        .await
+        .map_err(|_| DB_ERROR)?
+        .into_iter()
+        .map(|count| CashCountLine {
+            denomination: count.denomination,
+            quantity: count.quantity,
+            amount: count.denomination * Decimal::from(count.quantity),
+        })
+        .collect();
+
+    // Entradas y retiros de efectivo del turno
+    let movement_rows = CashMovements::find()
+        .filter(cash_movements::Column::CashSessionId.eq(cash_session.id))
+        .order_by_asc(cash_movements::Column::CreatedAt)
+        .all(db)
+        .await
+        .map_err(|_| DB_ERROR)?;
+
+    let cash_in: Decimal = movement_rows
+        .iter()
+        .filter(|movement| movement.movement_type == MOVEMENT_IN)
+        .map(|movement| movement.amount)
+        .sum();
+    let cash_out: Decimal = movement_rows
+        .iter()
+        .filter(|movement| movement.movement_type == MOVEMENT_OUT)
+        .map(|movement| movement.amount)
+        .sum();
+
+    // Abonos a cuentas de crédito recibidos en el turno (se registran con monto negativo)
+    let credit_rows = CustomerCreditEntries::find()
+        .find_also_related(PaymentMethods)
+        .filter(customer_credit_entries::Column::CashSessionId.eq(cash_session.id))
+        .filter(customer_credit_entries::Column::EntryType.eq(ENTRY_PAYMENT))
+        .all(db)
+        .await
+        .map_err(|_| DB_ERROR)?;
+
+    let credit_payments: Decimal = credit_rows.iter().map(|(entry, _)| -entry.amount).sum();
+    let cash_credit_payments: Decimal = credit_rows
+        .iter()
+        .filter(|(_, method)| {
+            method
+                .as_ref()
+                .is_some_and(|method| method.tender == TENDER_CASH)
+        })
+        .map(|(entry, _)| -entry.amount)
+        .sum();
+
+    let authorizer_names = user_names(
+        db,
+        movement_rows
+            .iter()
+            .map(|movement| movement.authorized_by.clone())
+            .collect(),
+    )
+    .await?;
+    let movements = movement_rows
+        .into_iter()
+        .map(|movement| {
+            let name = authorizer_names.get(&movement.authorized_by).cloned();
+            CashMovement::from_model(movement, name)
+        })
+        .collect();
+
+    let cash_sales: Decimal = payment_rows
+        .iter()
+        .filter(|row| row.tender == TENDER_CASH)
+        .filter_map(|row| row.amount)
+        .sum();
+    let payments = payment_rows
+        .into_iter()
+        .map(|row| CashSessionPaymentTotal {
+            payment_method_id: row.payment_method_id,
+            payment_method_name: row.payment_method_name,
+            sales_count: row.sales_count.unwrap_or(0),
+            amount: row.amount.unwrap_or(Decimal::ZERO),
+        })
+        .collect();
+
+    let (sales_count, sales_total, cancelled_count, cancelled_total) = sales_summary
+        .map(|raw| {
+            (
+                raw.sales_count.unwrap_or(0),
+                raw.sales_total.unwrap_or(Decimal::ZERO),
+                raw.cancelled_count.unwrap_or(0),
+                raw.cancelled_total.unwrap_or(Decimal::ZERO),
+            )
+        })
+        .unwrap_or((0, Decimal::ZERO, 0, Decimal::ZERO));
+
+    let (refunds_count, refunds_total, cash_refunds) = refunds_summary
+        .map(|raw| {
+            (
+                raw.refunds_count.unwrap_or(0),
+                raw.refunds_total.unwrap_or(Decimal::ZERO),
+                raw.cash_refunds.unwrap_or(Decimal::ZERO),
+            )
+        })
+        .unwrap_or((0, Decimal::ZERO, Decimal::ZERO));
+
+    // Un turno cerrado conserva el efectivo esperado con el que se calculó su diferencia
+    let expected_cash = match cash_session.expected_cash {
+        Some(expected_cash) if cash_session.status == STATUS_CLOSED => expected_cash,
+        _ => {
+            cash_session.opening_float + cash_sales + cash_credit_payments - cash_refunds + cash_in
+                - cash_out
+        }
+    };
+    let report_type = if cash_session.status == STATUS_CLOSED {
+        "Z"
+    } else {
+        "X"
+    };
+    let name = user_name(db, &cash_session.user_id).await?;
+
+    Ok(CashSessionReport {
+        report_type: report_type.to_string(),
+        counted_cash: cash_session.counted_cash,
+        variance: cash_session.variance,
+        session: CashSession::from_model(cash_session, name),
+        sales_count,
+        sales_total,
+        cancelled_count,
+        cancelled_total,
+        refunds_count,
+        refunds_total,
+        payments,
+        cash_sales,
+        cash_refunds,
+        cash_in,
+        cash_out,
+        credit_payments,
+        cash_credit_payments,
+        movements,
+        expected_cash,
+        counts,
+        generated_at: Utc::now().fixed_offset().to_string(),
+    })
+}
+
+/// Abre un turno de caja con el fondo inicial
+#[tauri::command]
+pub async fn open_cash_session(
+    state: tauri::State<'_, AppState>,
+    request: OpenCashSessionRequest,
+) -> Result<CashSession, String> {
+    let session = require_permission(&state, "cash.open")?;
+    let db = &state.database;
+
+    if request.opening_float < Decimal::ZERO {
+        return Err("El fondo de caja no puede ser negativo".to_string());
+    }
+
+    if find_open_session(db, &session.user_id).await?.is_some() {
+        return Err("Ya tiene un turno de caja abierto".to_string());
+    }
+
+    // El índice único de turnos abiertos evita duplicados entre cajas simultáneas
+    let opened = cash_sessions::ActiveModel {
+        user_id: Set(session.user_id.clone()),
+        status: Set(STATUS_OPEN.to_string()),
+        opening_float: Set(request.opening_float.round_dp(2)),
+        ..Default::default()
+    }
+    .insert(db)
+    .await
+    .map_err(|_| "No se pudo abrir el turno; verifique que no tenga otro abierto".to_string())?;
+
+    let name = user_name(db, &session.user_id).await?;
+    Ok(CashSession::from_model(opened, name))
+}
+
+/// Obtiene el turno abierto del usuario actual, si tiene uno
+#[tauri::command]
+pub async fn get_current_cash_session(
+    state: tauri::State<'_, AppState>,
+) -> Result<Option<CashSession>, String> {
+    let session = require_permission(&state, "cash.open")?;
+    let db = &state.database;
+
+    match find_open_session(db, &session.user_id).await? {
+        Some(cash_session) => {
+            let name = user_name(db, &session.user_id).await?;
+            Ok(Some(CashSession::from_model(cash_session, name)))
+        }
+        None => Ok(None),
+    }
+}
+
+/// Cierra un turno con el conteo de efectivo por denominación y genera el corte Z
+#[tauri::command]
+pub async fn close_cash_session(
+    state: tauri::State<'_, AppState>,
+    request: CloseCashSessionRequest,
+) -> Result<CashSessionReport, String> {
+    // 1. Validar permiso
+    let session = require_permission(&state, "cash.close")?;
+    let db = &state.database;
+
+    // 2. Validar el conteo
+    if request
+        .counts
+        .iter()
+        .any(|count| count.quantity < 0 || !is_valid_denomination(count.denomination))
+    {
+        return Err("El conteo contiene denominaciones o cantidades inválidas".to_string());
+    }
+
+    // 3. Iniciar transacción y bloquear el turno para que no se registren más ventas
+    let txn = db
+        .begin()
+        .await
+        .map_err(|_| "Error al iniciar transacción")?;
+
+    let cash_session = CashSessions::find_by_id(request.cash_session_id)
+        .lock_exclusive()
+        .one(&txn)
+        .await
+        .map_err(|_| DB_ERROR)?
+        .ok_or("Turno de caja no encontrado")?;
+
+    ensure_session_access(&session, &cash_session)?;
+
+    if cash_session.status != STATUS_OPEN {
+        return Err("El turno de caja ya está cerrado".to_string());
+    }
+
+    // 4. Registrar el conteo por denominación
+    let mut counted_cash = Decimal::ZERO;
+    for count in request.counts.iter().filter(|count| count.quantity > 0) {
+        counted_cash += count.denomination * Decimal::from(count.quantity);
+
+        cash_session_counts::ActiveModel {
+            cash_session_id: Set(cash_session.id),
+            denomination: Set(count.denomination),
+            quantity: Set(count.quantity),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await
+        .map_err(|e| format!("Error al registrar el conteo: {:?}", e))?;
+    }
+
+    // 5. Calcular el efectivo esperado y la diferencia
+    let expected_cash = build_report(&txn, cash_session.clone())
+        .await?
+        .expected_cash;
+
+    let mut active_model: cash_sessions::ActiveModel = cash_session.into();
+    active_model.status = Set(STATUS_CLOSED.to_string());
+    active_model.expected_cash = Set(Some(expected_cash));
+    active_model.counted_cash = Set(Some(counted_cash));
+    active_model.variance = Set(Some(counted_cash - expected_cash));
+    active_model.notes = Set(request
+        .notes
+        .map(|notes| notes.trim().to_string())
+        .filter(|notes| !notes.is_empty()));
+    active_model.closed_at = Set(Some(Utc::now().fixed_offset()));
+    active_model.closed_by = Set(Some(session.user_id.clone()));
+
+    let closed = active_model
+        .update(&txn)
+        .await
+        .map_err(|e| format!("Error al cerrar el turno: {:?}", e))?;
+
+    // 6. Generar el corte Z y confirmar
+    let report = build_report(&txn, closed).await?;
+
+    txn.commit()
+        .await
+        .map_err(|_| "Error al confirmar la transacción")?;
+
+    Ok(report)
+}
+
+/// Obtiene una página de turnos de caja
+#[tauri::command]
+pub async fn get_cash_sessions(
+    state: tauri::State<'_, AppState>,
+    filters: CashSessionFilter,
+) -> Result<CashSessionListResponse, String> {
+    let session = require_permission(&state, "cash.open")?;
+    let db = &state.database;
+
+    let mut query = CashSessions::find();
+
+    // Sin permiso de supervisión solo se consultan los turnos propios
+    let user_filter = if session.has_permission("cash.view") {
+        filters.user_id
+    } else {
+        Some(session.user_id.clone())
+    };
+    if let Some(user_id) = user_filter {
+        query = query.filter(cash_sessions::Column::UserId.eq(user_id));
+    }
+    if let Some(status) = filters.status {
+        query = query.filter(cash_sessions::Column::Status.eq(status));
+    }
+    // Filtrar por fecha de apertura
+    if let Some(ref date_from) = filters.date_from {
+        if let Ok(parsed_date) = NaiveDate::parse_from_str(date_from, "%Y-%m-%d") {
+            let datetime_from = parsed_date.and_hms_opt(0, 0, 0).unwrap();
+            query = query.filter(cash_sessions::Column::OpenedAt.gte(datetime_from));
+        }
+    }
+    if let Some(ref date_to) = filters.date_to {
+        if let Ok(parsed_date) = NaiveDate::parse_from_str(date_to, "%Y-%m-%d") {
+            let datetime_to = parsed_date.and_hms_opt(23, 59, 59).unwrap();
+            query = query.filter(cash_sessions::Column::OpenedAt.lte(datetime_to));
+        }
+    }
+
+    // Contar total de items para paginación
+    let total_items = query.clone().count(db).await.map_err(|_| DB_ERROR)?;
+
+    let total_pages = if filters.limit > 0 {
+        (total_items as f64 / filters.limit as f64).ceil() as u64
+    } else {
+        1
+    };
+
+    let offset = (filters.page.saturating_sub(1)) * filters.limit;
+    let sessions_list = query
+        .order_by_desc(cash_sessions::Column::OpenedAt)
+        .offset(offset)
+        .limit(filters.limit)
+        .all(db)
+        .await
+        .map_err(|_| DB_ERROR)?;
+
+    let user_ids: Vec<String> = sessions_list.iter().map(|s| s.user_id.clone()).collect();
+    let names = user_names(db, user_ids).await?;
+
+    Ok(CashSessionListResponse {
+        sessions: sessions_list
+            .into_iter()
+            .map(|cash_session| {
+                let name = names.get(&cash_session.user_id).cloned();
+                CashSession::from_model(cash_session, name)
+            })
+            .collect(),
+        total_pages,
+        total_items,
+    })
+}
+
+/// Genera el corte de un turno: X si sigue abierto, Z si ya se cerró
+#[tauri::command]
+pub async fn get_cash_session_report(
+    state: tauri::State<'_, AppState>,
+    id: i32,
+) -> Result<CashSessionReport, String> {
+    let session = require_permission(&state, "cash.open")?;
+    let db = &state.database;
+
+    let cash_session = CashSessions::find_by_id(id)
+        .one(db)
+        .await
+        .map_err(|_| DB_ERROR)?
+        .ok_or("Turno de caja no encontrado")?;
+
+    ensure_session_access(&session, &cash_session)?;
+
+    build_report(db, cash_session).await
+}
+
+/// Registra una entrada o retiro de efectivo en un turno abierto
+#[tauri::command]
+pub async fn create_cash_movement(
+    state: tauri::State<'_, AppState>,
+    request: CreateCashMovementRequest,
+) -> Result<CashMovement, String> {
+    // 1. Validar permiso
+    let session = require_permission(&state, "cash.movements")?;
+    let db = &state.database;
+
+    // 2. Validar datos del movimiento
+    if request.movement_type != MOVEMENT_IN && request.movement_type != MOVEMENT_OUT {
+        return Err("Tipo de movimiento inválido".to_string());
+    }
+    if request.amount <= Decimal::ZERO {
+        return Err("El monto debe ser mayor a cero".to_string());
+    }
+    let reason = request.reason.trim().to_string();
+    if reason.is_empty() {
+        return Err("El motivo del movimiento es requerido".to_string());
+    }
+    let amount = request.amount.round_dp(2);
+
+    // 3. Iniciar transacción y bloquear el turno para que no se cierre ni reciba
+    // otro movimiento mientras se valida el efectivo disponible
+    let txn = db
+        .begin()
+        .await
+        .map_err(|_| "Error al iniciar transacción")?;
+
+    let cash_session_id = match request.cash_session_id {
+        Some(id) => id,
+        None => require_open_session(&txn, &session.user_id).await?.id,
+    };
+
+    let cash_session = CashSessions::find_by_id(cash_session_id)
+        .lock_exclusive()
+        .one(&txn)
+        .await
+        .map_err(|_| DB_ERROR)?
+        .ok_or("Turno de caja no encontrado")?;
+
+    ensure_session_access(&session, &cash_session)?;
+
+    if cash_session.status != STATUS_OPEN {
+        return Err("El turno de caja ya está cerrado".to_string());
+    }
+
+    // 4. Un retiro no puede superar el efectivo que debería haber en caja
+    if request.movement_type == MOVEMENT_OUT {
+        let available = build_report(&txn, cash_session.clone())
+            .await?
+            .expected_cash;
+        if amount > available {
+            return Err(format!(
+                "El retiro excede el efectivo disponible en caja (${})",
+                available.round_dp(2)
+            ));
+        }
+    }
+
+    // 5. Registrar el movimiento y confirmar
+    let movement = cash_movements::ActiveModel {
+        cash_session_id: Set(cash_session.id),
+        movement_type: Set(request.movement_type),
+        amount: Set(amount),
+        reason: Set(reason),
+        authorized_by: Set(session.user_id.clone()),
+        ..Default::default()
+    }
+    .insert(&txn)
+    .await
+    .map_err(|e| format!("Error al registrar el movimiento: {:?}", e))?;
+
+    txn.commit()
+        .await
+        .map_err(|_| "Error al confirmar la transacción")?;
+
+    let name = user_name(db, &session.user_id).await?;
+    Ok(CashMovement::from_model(movement, name))
+}
+
+/// Obtiene una página de entradas y retiros de efectivo para auditoría
+#[tauri::command]
+pub async fn get_cash_movements(
+    state: tauri::State<'_, AppState>,
+    filters: CashMovementFilter,
+) -> Result<CashMovementListResponse, String> {
+    let session = require_permission(&state, "cash.open")?;
+    let db = &state.database;
+
+    let mut query = CashMovements::find().inner_join(CashSessions);
+
+    // Sin permiso de supervisión solo se consultan los movimientos de turnos propios
+    let user_filter = if session.has_permission("cash.view") {
+        filters.user_id
+    } else {
+        Some(session.user_id.clone())
+    };
+    if let Some(user_id) = user_filter {
+        query = query.filter(cash_sessions::Column::UserId.eq(user_id));
+    }
+    if let Some(cash_session_id) = filters.cash_session_id {
+        query = query.filter(cash_movements::Column::CashSessionId.eq(cash_session_id));
+    }
+    if let Some(movement_type) = filters.movement_type {
+        query = query.filter(cash_movements::Column::MovementType.eq(movement_type));
+    }
+    if let Some(ref date_from) = filters.date_from {
+        if let Ok(parsed_date) = NaiveDate::parse_from_str(date_from, "%Y-%m-%d") {
+            let datetime_from = parsed_date.and_hms_opt(0, 0, 0).unwrap();
+            query = query.filter(cash_movements::Column::CreatedAt.gte(datetime_from));
+        }
+    }
+    if let Some(ref date_to) = filters.date_to {
+        if let Ok(parsed_date) = NaiveDate::parse_from_str(date_to, "%Y-%m-%d") {
+            let datetime_to = parsed_date.and_hms_opt(23, 59, 59).unwrap();
+            query = query.filter(cash_movements::Column::CreatedAt.lte(datetime_to));
+        }
+    }
+
+    // Contar total de items para paginación
+    let total_items = query.clone().count(db).await.map_err(|_| DB_ERROR)?;
+
+    let total_pages = if filters.limit > 0 {
+        (total_items as f64 / filters.limit as f64).ceil() as u64
+    } else {
+        1
+    };
+
+    let offset = (filters.page.saturating_sub(1)) * filters.limit;
+    let movements_list = query
+        .order_by_desc(cash_movements::Column::CreatedAt)
+        .offset(offset)
+        .limit(filters.limit)
+        .all(db)
+        .await
+        .map_err(|_| DB_ERROR)?;
+
+    let user_ids: Vec<String> = movements_list
+        .iter()
+        .map(|movement| movement.authorized_by.clone())
+        .collect();
+    let names = user_names(db, user_ids).await?;
+
+    Ok(CashMovementListResponse {
+        movements: movements_list
+            .into_iter()
+            .map(|movement| {
+                let name = names.get(&movement.authorized_by).cloned();
+                CashMovement::from_model(movement, name)
+            })
+            .collect(),
+        total_pages,
+        total_items,
+    })
+}
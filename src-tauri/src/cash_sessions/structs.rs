@@ -0,0 +1,200 @@
+use crate::entities::{cash_movements, cash_sessions};
+use sea_orm::prelude::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Tipo de cobro de las formas de pago en efectivo; lo cobrado con ellas entra al cajón
+pub const TENDER_CASH: &str = "cash";
+
+pub const STATUS_OPEN: &str = "open";
+pub const STATUS_CLOSED: &str = "closed";
+
+pub const MOVEMENT_IN: &str = "in";
+pub const MOVEMENT_OUT: &str = "out";
+
+/// Denominaciones de billetes y monedas aceptadas en el conteo, en centavos
+pub const DENOMINATIONS_CENTS: [i64; 13] = [
+    100000, 50000, 20000, 10000, 5000, 2000, 1000, 500, 200, 100, 50, 20, 10,
+];
+
+/// Indica si el importe corresponde a un billete o moneda en circulación
+pub fn is_valid_denomination(denomination: Decimal) -> bool {
+    DENOMINATIONS_CENTS
+        .iter()
+        .any(|cents| Decimal::new(*cents, 2) == denomination)
+}
+
+/// Turno de caja (para lectura/respuesta)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CashSession {
+    pub id: i32,
+    pub user_id: String,
+    pub user_name: Option<String>,
+    pub status: String, // open, closed
+    pub opening_float: Decimal,
+    pub expected_cash: Option<Decimal>, // Solo en turnos cerrados
+    pub counted_cash: Option<Decimal>,
+    pub variance: Option<Decimal>, // Contado - esperado; negativo = faltante
+    pub notes: Option<String>,
+    pub opened_at: String,
+    pub closed_at: Option<String>,
+    pub closed_by: Option<String>,
+}
+
+impl CashSession {
+    pub fn from_model(value: cash_sessions::Model, user_name: Option<String>) -> Self {
+        Self {
+            id: value.id,
+            user_id: value.user_id,
+            user_name,
+            status: value.status,
+            opening_float: value.opening_float,
+            expected_cash: value.expected_cash,
+            counted_cash: value.counted_cash,
+            variance: value.variance,
+            notes: value.notes,
+            opened_at: value.opened_at.to_string(),
+            closed_at: value.closed_at.map(|date| date.to_string()),
+            closed_by: value.closed_by,
+        }
+    }
+}
+
+// Request para abrir un turno de caja
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct OpenCashSessionRequest {
+    pub opening_float: Decimal, // Fondo de caja inicial
+}
+
+// Cantidad contada de una denominación
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DenominationCount {
+    pub denomination: Decimal,
+    pub quantity: i32,
+}
+
+// Request para cerrar un turno con el conteo de efectivo (corte Z)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CloseCashSessionRequest {
+    pub cash_session_id: i32,
+    pub counts: Vec<DenominationCount>,
+    pub notes: Option<String>,
+}
+
+/// Entrada o retiro de efectivo durante un turno
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CashMovement {
+    pub id: i32,
+    pub cash_session_id: i32,
+    pub movement_type: String, // in, out
+    pub amount: Decimal,
+    pub reason: String,
+    pub authorized_by: String,
+    pub authorized_by_name: Option<String>,
+    pub created_at: String,
+}
+
+impl CashMovement {
+    pub fn from_model(value: cash_movements::Model, authorized_by_name: Option<String>) -> Self {
+        Self {
+            id: value.id,
+            cash_session_id: value.cash_session_id,
+            movement_type: value.movement_type,
+            amount: value.amount,
+            reason: value.reason,
+            authorized_by: value.authorized_by,
+            authorized_by_name,
+            created_at: value.created_at.to_string(),
+        }
+    }
+}
+
+// Request para registrar una entrada o retiro de efectivo
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CreateCashMovementRequest {
+    pub cash_session_id: Option<i32>, // None = turno abierto del usuario actual
+    pub movement_type: String,        // in, out
+    pub amount: Decimal,
+    pub reason: String,
+}
+
+/// Filtros para obtener movimientos de efectivo
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CashMovementFilter {
+    pub cash_session_id: Option<i32>,
+    pub user_id: Option<String>, // Cajero del turno; sin permiso cash.view solo los propios
+    pub movement_type: Option<String>,
+    pub date_from: Option<String>, // AAAA-MM-DD
+    pub date_to: Option<String>,
+    pub page: u64,
+    pub limit: u64,
+}
+
+/// Respuesta paginada de movimientos de efectivo
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CashMovementListResponse {
+    pub movements: Vec<CashMovement>,
+    pub total_pages: u64,
+    pub total_items: u64,
+}
+
+/// Filtros para obtener turnos de caja
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CashSessionFilter {
+    pub user_id: Option<String>, // Sin permiso cash.view solo se ven los propios
+    pub status: Option<String>,
+    pub date_from: Option<String>, // Fecha de apertura AAAA-MM-DD
+    pub date_to: Option<String>,
+    pub page: u64,
+    pub limit: u64,
+}
+
+/// Respuesta paginada de turnos de caja
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CashSessionListResponse {
+    pub sessions: Vec<CashSession>,
+    pub total_pages: u64,
+    pub total_items: u64,
+}
+
+/// Ventas del turno por forma de pago
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CashSessionPaymentTotal {
+    pub payment_method_id: i32,
+    pub payment_method_name: String,
+    pub sales_count: i64,
+    pub amount: Decimal,
+}
+
+/// Línea del conteo con su importe
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CashCountLine {
+    pub denomination: Decimal,
+    pub quantity: i32,
+    pub amount: Decimal,
+}
+
+/// Corte de caja: X a mitad del turno, Z al cerrarlo
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CashSessionReport {
+    pub report_type: String, // X, Z
+    pub session: CashSession,
+    pub sales_count: i64,
+    pub sales_total: Decimal,
+    pub cancelled_count: i64,
+    pub cancelled_total: Decimal,
+    pub refunds_count: i64,
+    pub refunds_total: Decimal,
+    pub payments: Vec<CashSessionPaymentTotal>,
+    pub cash_sales: Decimal,
+    pub cash_refunds: Decimal,
+    pub cash_in: Decimal,         // Entradas de efectivo (cambio, depósitos)
+    pub cash_out: Decimal,        // Retiros de efectivo (caja fuerte, pagos)
+    pub credit_payments: Decimal, // Abonos a cuentas de crédito recibidos en el turno
+    pub cash_credit_payments: Decimal,
+    pub movements: Vec<CashMovement>,
+    pub expected_cash: Decimal, // Fondo + ventas, abonos y entradas en efectivo - devoluciones y retiros
+    pub counted_cash: Option<Decimal>,
+    pub variance: Option<Decimal>,
+    pub counts: Vec<CashCountLine>,
+    pub generated_at: String,
+}
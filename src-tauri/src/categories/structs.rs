@@ -1,5 +1,5 @@
 use crate::entities::categories::{self, ActiveModel};
-use sea_orm::ActiveValue::Set;
+use sea_orm::{prelude::Decimal, ActiveValue::Set};
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -7,6 +7,7 @@ pub struct Category {
     pub id: i32,
     pub name: String,
     pub is_active: bool,
+    pub points_multiplier: Decimal, // Se configura en el programa de puntos
 }
 
 impl From<categories::Model> for Category {
@@ -15,6 +16,7 @@ impl From<categories::Model> for Category {
             id: value.id,
             name: value.name,
             is_active: value.is_active,
+            points_multiplier: value.points_multiplier,
         }
     }
 }
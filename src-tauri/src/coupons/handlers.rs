@@ -0,0 +1,391 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::{Local, NaiveDate};
+use sea_orm::{
+    prelude::Decimal, ActiveModelTrait, ActiveValue::Set, ColumnTrait, ConnectionTrait,
+    EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, TransactionTrait,
+};
+
+use super::structs::{
+    Coupon, CouponBatch, CouponBatchDetail, CouponBatchFilter, CouponValidation, NewCouponBatch,
+};
+use crate::entities::{
+    coupon_batches, coupons,
+    prelude::{CouponBatches, Coupons, Sales},
+    sales,
+};
+use crate::sales::structs::DiscountType;
+use crate::sessions::require_permission;
+use crate::AppState;
+
+const DB_ERROR: &str = "Error en la base de datos";
+const MAX_CODES_PER_BATCH: u32 = 1000;
+const CODE_LENGTH: usize = 8;
+// Sin 0/O ni 1/I para evitar confusiones al capturar el código
+const CODE_ALPHABET: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+
+/// Normaliza un código capturado: sin espacios y en mayúsculas
+pub fn normalize_code(code: &str) -> String {
+    code.trim().to_uppercase()
+}
+
+/// Genera un código aleatorio con el prefijo indicado
+pub fn generate_code(prefix: &str) -> String {
+    let random: String = uuid::Uuid::new_v4()
+        .as_bytes()
+        .iter()
+        .take(CODE_LENGTH)
+        .map(|byte| CODE_ALPHABET[*byte as usize % CODE_ALPHABET.len()] as char)
+        .collect();
+
+    if prefix.is_empty() {
+        random
+    } else {
+        format!("{}-{}", prefix, random)
+    }
+}
+
+/// Busca un código y valida que pueda canjearse hoy.
+/// Dentro de una transacción bloquea el cupón hasta el commit para que dos
+/// terminales no puedan consumir el mismo uso.
+pub async fn find_redeemable_coupon<C: ConnectionTrait>(
+    db: &C,
+    code: &str,
+) -> Result<(coupons::Model, coupon_batches::Model), String> {
+    let code = normalize_code(code);
+
+    let coupon = Coupons::find()
+        .filter(coupons::Column::Code.eq(&code))
+        .lock_exclusive()
+        .one(db)
+        .await
+        .map_err(|_| DB_ERROR)?
+        .ok_or(format!("El cupón {} no existe", code))?;
+
+    let batch = CouponBatches::find_by_id(coupon.batch_id)
+        .one(db)
+        .await
+        .map_err(|_| DB_ERROR)?
+        .ok_or("Lote de cupones no encontrado")?;
+
+    if !batch.is_active {
+        return Err(format!("El cupón {} no está activo", code));
+    }
+
+    if batch
+        .expires_on
+        .is_some_and(|expires_on| Local::now().date_naive() > expires_on)
+    {
+        return Err(format!("El cupón {} está vencido", code));
+    }
+
+    if coupon.times_used >= batch.usage_limit {
+        return Err(format!("El cupón {} ya fue utilizado", code));
+    }
+
+    Ok((coupon, batch))
+}
+
+/// Cuenta códigos generados y canjeados por lote
+async fn batch_counts<C: ConnectionTrait>(db: &C, batch_id: i32) -> Result<(u64, u64), String> {
+    let codes_count = Coupons::find()
+        .filter(coupons::Column::BatchId.eq(batch_id))
+        .count(db)
+        .await
+        .map_err(|_| DB_ERROR)?;
+
+    let redeemed_count = Coupons::find()
+        .filter(coupons::Column::BatchId.eq(batch_id))
+        .filter(coupons::Column::TimesUsed.gt(0))
+        .count(db)
+        .await
+        .map_err(|_| DB_ERROR)?;
+
+    Ok((codes_count, redeemed_count))
+}
+
+/// Obtiene los lotes de cupones con filtros opcionales
+#[tauri::command]
+pub async fn get_coupon_batches(
+    state: tauri::State<'_, AppState>,
+    filters: CouponBatchFilter,
+) -> Result<Vec<CouponBatch>, String> {
+    require_permission(&state, "coupons.view")?;
+    let db = &state.database;
+
+    let mut query = CouponBatches::find();
+
+    if let Some(status) = filters.status {
+        query = query.filter(coupon_batches::Column::IsActive.eq(status));
+    }
+
+    let batches = query
+        .order_by_desc(coupon_batches::Column::CreatedAt)
+        .all(db)
+        .await
+        .map_err(|_| DB_ERROR)?;
+
+    let mut result = Vec::with_capacity(batches.len());
+    for batch in batches {
+        let (codes_count, redeemed_count) = batch_counts(db, batch.id).await?;
+        result.push(CouponBatch::from_model(batch, codes_count, redeemed_count));
+    }
+
+    Ok(result)
+}
+
+/// Obtiene un lote con sus códigos y las ventas que los consumieron
+#[tauri::command]
+pub async fn get_coupon_batch(
+    state: tauri::State<'_, AppState>,
+    id: i32,
+) -> Result<CouponBatchDetail, String> {
+    require_permission(&state, "coupons.view")?;
+    let db = &state.database;
+
+    let batch = CouponBatches::find_by_id(id)
+        .one(db)
+        .await
+        .map_err(|_| DB_ERROR)?
+        .ok_or("Lote de cupones no encontrado")?;
+
+    let coupons_list = Coupons::find()
+        .filter(coupons::Column::BatchId.eq(batch.id))
+        .order_by_asc(coupons::Column::Code)
+        .all(db)
+        .await
+        .map_err(|_| DB_ERROR)?;
+
+    let coupon_ids: Vec<i32> = coupons_list.iter().map(|coupon| coupon.id).collect();
+    let mut sales_by_coupon: HashMap<i32, Vec<String>> = HashMap::new();
+    for sale in Sales::find()
+        .filter(sales::Column::CouponId.is_in(coupon_ids))
+        .filter(sales::Column::Status.eq(true))
+        .order_by_asc(sales::Column::CreatedAt)
+        .all(db)
+        .await
+        .map_err(|_| DB_ERROR)?
+    {
+        if let Some(coupon_id) = sale.coupon_id {
+            sales_by_coupon.entry(coupon_id).or_default().push(sale.id);
+        }
+    }
+
+    let redeemed_count = coupons_list
+        .iter()
+        .filter(|coupon| coupon.times_used > 0)
+        .count() as u64;
+    let codes_count = coupons_list.len() as u64;
+
+    let coupons = coupons_list
+        .into_iter()
+        .map(|coupon| {
+            let sale_ids = sales_by_coupon.remove(&coupon.id).unwrap_or_default();
+            Coupon::from_model(coupon, sale_ids)
+        })
+        .collect();
+
+    Ok(CouponBatchDetail {
+        batch: CouponBatch::from_model(batch, codes_count, redeemed_count),
+        coupons,
+    })
+}
+
+/// Genera un lote de cupones con códigos únicos
+#[tauri::command]
+pub async fn create_coupon_batch(
+    state: tauri::State<'_, AppState>,
+    batch_data: NewCouponBatch,
+) -> Result<CouponBatchDetail, String> {
+    // 1. Validar permiso
+    let session = require_permission(&state, "coupons.manage")?;
+    let db = &state.database;
+
+    // 2. Validar datos del lote
+    let name = batch_data.name.trim();
+    if name.is_empty() {
+        return Err("El nombre del lote es obligatorio".to_string());
+    }
+
+    match batch_data.discount_type {
+        DiscountType::Percent => {
+            if batch_data.discount_value <= Decimal::ZERO
+                || batch_data.discount_value > Decimal::ONE_HUNDRED
+            {
+                return Err("El porcentaje de descuento debe estar entre 0 y 100".to_string());
+            }
+        }
+        DiscountType::Amount => {
+            if batch_data.discount_value <= Decimal::ZERO {
+                return Err("El monto del cupón debe ser mayor a cero".to_string());
+            }
+        }
+    }
+
+    let min_purchase = batch_data.min_purchase.unwrap_or(Decimal::ZERO);
+    if min_purchase < Decimal::ZERO {
+        return Err("La compra mínima no puede ser negativa".to_string());
+    }
+
+    let usage_limit = batch_data.usage_limit.unwrap_or(1);
+    if usage_limit < 1 {
+        return Err("Cada código debe permitir al menos un uso".to_string());
+    }
+
+    if batch_data.quantity == 0 || batch_data.quantity > MAX_CODES_PER_BATCH {
+        return Err(format!(
+            "Se pueden generar entre 1 y {} códigos por lote",
+            MAX_CODES_PER_BATCH
+        ));
+    }
+
+    let expires_on = batch_data
+        .expires_on
+        .as_ref()
+        .map(|date| {
+            NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                .map_err(|_| format!("Fecha inválida: {}", date))
+        })
+        .transpose()?;
+    if expires_on.is_some_and(|date| date < Local::now().date_naive()) {
+        return Err("La fecha de vencimiento ya pasó".to_string());
+    }
+
+    let prefix = batch_data
+        .prefix
+        .as_deref()
+        .map(normalize_code)
+        .unwrap_or_default();
+    if prefix.len() > 10 || !prefix.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err("El prefijo debe tener hasta 10 letras o números".to_string());
+    }
+
+    // 3. Generar códigos únicos que no existan en otros lotes
+    let mut codes: HashSet<String> = HashSet::new();
+    while codes.len() < batch_data.quantity as usize {
+        let missing = batch_data.quantity as usize - codes.len();
+        let candidates: Vec<String> = (0..missing).map(|_| generate_code(&prefix)).collect();
+
+        let existing: HashSet<String> = Coupons::find()
+            .filter(coupons::Column::Code.is_in(candidates.clone()))
+            .all(db)
+            .await
+            .map_err(|_| DB_ERROR)?
+            .into_iter()
+            .map(|coupon| coupon.code)
+            .collect();
+
+        codes.extend(
+            candidates
+                .into_iter()
+                .filter(|code| !existing.contains(code)),
+        );
+    }
+
+    // 4. Registrar lote y códigos en una transacción
+    let txn = db
+        .begin()
+        .await
+        .map_err(|_| "Error al iniciar transacción")?;
+
+    let batch = coupon_batches::ActiveModel {
+        name: Set(name.to_string()),
+        discount_type: Set(batch_data.discount_type.as_str().to_string()),
+        discount_value: Set(batch_data.discount_value),
+        min_purchase: Set(min_purchase),
+        expires_on: Set(expires_on),
+        usage_limit: Set(usage_limit),
+        created_by: Set(session.user_id.clone()),
+        updated_by: Set(session.user_id),
+        ..Default::default()
+    }
+    .insert(&txn)
+    .await
+    .map_err(|e| format!("Error al registrar el lote de cupones: {:?}", e))?;
+
+    let mut codes: Vec<String> = codes.into_iter().collect();
+    codes.sort();
+
+    Coupons::insert_many(codes.into_iter().map(|code| coupons::ActiveModel {
+        batch_id: Set(batch.id),
+        code: Set(code),
+        ..Default::default()
+    }))
+    .exec(&txn)
+    .await
+    .map_err(|e| format!("Error al generar los códigos: {:?}", e))?;
+
+    let coupons_list = Coupons::find()
+        .filter(coupons::Column::BatchId.eq(batch.id))
+        .order_by_asc(coupons::Column::Code)
+        .all(&txn)
+        .await
+        .map_err(|_| DB_ERROR)?;
+
+    txn.commit()
+        .await
+        .map_err(|_| "Error al confirmar la transacción")?;
+
+    let codes_count = coupons_list.len() as u64;
+    Ok(CouponBatchDetail {
+        batch: CouponBatch::from_model(batch, codes_count, 0),
+        coupons: coupons_list
+            .into_iter()
+            .map(|coupon| Coupon::from_model(coupon, Vec::new()))
+            .collect(),
+    })
+}
+
+/// Desactiva un lote completo; sus códigos dejan de ser canjeables
+#[tauri::command]
+pub async fn deactivate_coupon_batch(
+    state: tauri::State<'_, AppState>,
+    id: i32,
+) -> Result<CouponBatch, String> {
+    let session = require_permission(&state, "coupons.manage")?;
+    let db = &state.database;
+
+    let batch = CouponBatches::find_by_id(id)
+        .one(db)
+        .await
+        .map_err(|_| DB_ERROR)?
+        .ok_or("No se encontró el lote de cupones seleccionado".to_string())?;
+
+    let mut active_model: coupon_batches::ActiveModel = batch.into();
+    active_model.is_active = Set(false);
+    active_model.updated_by = Set(session.user_id);
+
+    let updated = active_model
+        .update(db)
+        .await
+        .map_err(|_| "Ocurrió un error al desactivar el lote de cupones".to_string())?;
+
+    let (codes_count, redeemed_count) = batch_counts(db, updated.id).await?;
+    Ok(CouponBatch::from_model(
+        updated,
+        codes_count,
+        redeemed_count,
+    ))
+}
+
+/// Valida un código en caja antes de cobrar; no consume el cupón
+#[tauri::command]
+pub async fn validate_coupon(
+    state: tauri::State<'_, AppState>,
+    code: String,
+) -> Result<CouponValidation, String> {
+    require_permission(&state, "sales.create")?;
+    let db = &state.database;
+
+    let (coupon, batch) = find_redeemable_coupon(db, &code).await?;
+
+    Ok(CouponValidation {
+        code: coupon.code,
+        batch_name: batch.name,
+        discount_type: batch.discount_type,
+        discount_value: batch.discount_value,
+        min_purchase: batch.min_purchase,
+        expires_on: batch.expires_on.map(|date| date.to_string()),
+        remaining_uses: batch.usage_limit - coupon.times_used,
+    })
+}
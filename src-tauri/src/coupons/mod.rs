@@ -0,0 +1,4 @@
+pub mod handlers;
+pub mod structs;
+
+pub use handlers as CouponsHandler;
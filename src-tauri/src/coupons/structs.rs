@@ -0,0 +1,102 @@
+use crate::entities::{coupon_batches, coupons};
+use crate::sales::structs::DiscountType;
+use sea_orm::prelude::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Lote de cupones (para lectura/respuesta)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CouponBatch {
+    pub id: i32,
+    pub name: String,
+    pub discount_type: String,
+    pub discount_value: Decimal,
+    pub min_purchase: Decimal,      // Importe mínimo antes de impuestos
+    pub expires_on: Option<String>, // AAAA-MM-DD, válido hasta el final del día
+    pub usage_limit: i32,           // Usos permitidos por código
+    pub is_active: bool,
+    pub codes_count: u64,
+    pub redeemed_count: u64, // Códigos con al menos un uso
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl CouponBatch {
+    pub fn from_model(value: coupon_batches::Model, codes_count: u64, redeemed_count: u64) -> Self {
+        Self {
+            id: value.id,
+            name: value.name,
+            discount_type: value.discount_type,
+            discount_value: value.discount_value,
+            min_purchase: value.min_purchase,
+            expires_on: value.expires_on.map(|date| date.to_string()),
+            usage_limit: value.usage_limit,
+            is_active: value.is_active,
+            codes_count,
+            redeemed_count,
+            created_at: value.created_at.to_string(),
+            updated_at: value.updated_at.to_string(),
+        }
+    }
+}
+
+/// Código canjeable de un lote
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Coupon {
+    pub id: i32,
+    pub batch_id: i32,
+    pub code: String,
+    pub times_used: i32,
+    pub sale_ids: Vec<String>, // Ventas activas que consumieron el código
+    pub created_at: String,
+}
+
+impl Coupon {
+    pub fn from_model(value: coupons::Model, sale_ids: Vec<String>) -> Self {
+        Self {
+            id: value.id,
+            batch_id: value.batch_id,
+            code: value.code,
+            times_used: value.times_used,
+            sale_ids,
+            created_at: value.created_at.to_string(),
+        }
+    }
+}
+
+/// Lote con todos sus códigos
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CouponBatchDetail {
+    pub batch: CouponBatch,
+    pub coupons: Vec<Coupon>,
+}
+
+/// Datos para generar un lote de cupones
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct NewCouponBatch {
+    pub name: String,
+    pub discount_type: DiscountType,
+    pub discount_value: Decimal,
+    pub min_purchase: Option<Decimal>,
+    pub expires_on: Option<String>,
+    pub usage_limit: Option<i32>, // Por defecto un solo uso por código
+    pub quantity: u32,            // Cantidad de códigos a generar
+    pub prefix: Option<String>,   // Prefijo opcional de los códigos (ej: "VERANO")
+}
+
+/// Filtros para obtener lotes de cupones
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CouponBatchFilter {
+    pub status: Option<bool>,
+}
+
+/// Resultado de validar un código antes de cobrar
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CouponValidation {
+    pub code: String,
+    pub batch_name: String,
+    pub discount_type: String,
+    pub discount_value: Decimal,
+    pub min_purchase: Decimal,
+    pub expires_on: Option<String>,
+    pub remaining_uses: i32,
+}
@@ -0,0 +1,496 @@
+use std::collections::{HashMap, VecDeque};
+
+use chrono::{Local, NaiveDate, Utc};
+use sea_orm::{
+    prelude::Decimal, ActiveModelTrait, ActiveValue::Set, ColumnTrait, ConnectionTrait,
+    EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, TransactionTrait,
+};
+
+use super::structs::{
+    CreditAccount, CreditAging, CreditEntry, CreditLedgerFilter, CreditLedgerResponse,
+    CreditPaymentRequest, CreditPaymentResponse, CreditStatement, CreditStatementParams,
+    SetCreditLimitRequest, ENTRY_CHARGE, ENTRY_PAYMENT, ENTRY_REFUND, TENDER_CREDIT,
+};
+use crate::cash_sessions::structs::TENDER_CASH;
+use crate::cash_sessions::CashSessionsHandler::{find_open_session, require_open_session};
+use crate::entities::{
+    customer_credit_entries, customers, payment_methods,
+    prelude::{CustomerCreditEntries, Customers, PaymentMethods, Users},
+    sales, users,
+};
+use crate::sessions::require_permission;
+use crate::AppState;
+
+const DB_ERROR: &str = "Error en la base de datos";
+
+/// Registra un movimiento en la cuenta del cliente y actualiza su saldo.
+/// Bloquea al cliente para que los movimientos simultáneos no desfasen el saldo;
+/// los cargos no pueden exceder el límite de crédito.
+#[allow(clippy::too_many_arguments)]
+pub async fn post_credit_entry<C: ConnectionTrait>(
+    db: &C,
+    customer_id: i32,
+    entry_type: &str,
+    amount: Decimal,
+    sale_id: Option<&str>,
+    payment_method_id: Option<i32>,
+    cash_session_id: Option<i32>,
+    notes: Option<String>,
+    user_id: &str,
+) -> Result<customer_credit_entries::Model, String> {
+    let customer = Customers::find_by_id(customer_id)
+        .lock_exclusive()
+        .one(db)
+        .await
+        .map_err(|_| DB_ERROR)?
+        .ok_or(format!("Cliente {} no encontrado", customer_id))?;
+
+    let balance_after = customer.credit_balance + amount;
+    if entry_type == ENTRY_CHARGE {
+        if !customer.is_active {
+            return Err(format!("El cliente {} no está activo", customer.name));
+        }
+        if balance_after > customer.credit_limit {
+            return Err(format!(
+                "Crédito insuficiente para {}. Disponible: {}",
+                customer.name,
+                (customer.credit_limit - customer.credit_balance).max(Decimal::ZERO)
+            ));
+        }
+    }
+
+    let entry = customer_credit_entries::ActiveModel {
+        customer_id: Set(customer.id),
+        entry_type: Set(entry_type.to_string()),
+        amount: Set(amount),
+        balance_after: Set(balance_after),
+        sale_id: Set(sale_id.map(|sale_id| sale_id.to_string())),
+        payment_method_id: Set(payment_method_id),
+        cash_session_id: Set(cash_session_id),
+        notes: Set(notes),
+        created_by: Set(user_id.to_string()),
+        ..Default::default()
+    }
+    .insert(db)
+    .await
+    .map_err(|e| format!("Error al registrar el movimiento de crédito: {:?}", e))?;
+
+    let mut active_model: customers::ActiveModel = customer.into();
+    active_model.credit_balance = Set(balance_after);
+    active_model.updated_by = Set(user_id.to_string());
+    active_model
+        .update(db)
+        .await
+        .map_err(|e| format!("Error al actualizar el saldo del cliente: {:?}", e))?;
+
+    Ok(entry)
+}
+
+/// Abona a la cuenta del cliente un reembolso de la venta
+pub async fn credit_refund<C: ConnectionTrait>(
+    db: &C,
+    sale: &sales::Model,
+    amount: Decimal,
+    reason: &str,
+    user_id: &str,
+) -> Result<(), String> {
+    let customer_id = sale
+        .customer_id
+        .ok_or("La venta no tiene un cliente al cual abonar el reembolso")?;
+
+    post_credit_entry(
+        db,
+        customer_id,
+        ENTRY_REFUND,
+        -amount,
+        Some(&sale.id),
+        None,
+        None,
+        Some(reason.to_string()),
+        user_id,
+    )
+    .await?;
+    Ok(())
+}
+
+/// Antigüedad del saldo: los abonos se aplican primero a los cargos más antiguos
+fn credit_aging(entries: &[customer_credit_entries::Model]) -> CreditAging {
+    let mut charges: VecDeque<(NaiveDate, Decimal)> = VecDeque::new();
+    let mut credits = Decimal::ZERO;
+
+    for entry in entries {
+        if entry.amount > Decimal::ZERO {
+            charges.push_back((
+                entry.created_at.with_timezone(&Local).date_naive(),
+                entry.amount,
+            ));
+        } else {
+            credits -= entry.amount;
+        }
+    }
+
+    while credits > Decimal::ZERO {
+        let Some((_, remaining)) = charges.front_mut() else {
+            break;
+        };
+        let applied = credits.min(*remaining);
+        *remaining -= applied;
+        credits -= applied;
+        if *remaining == Decimal::ZERO {
+            charges.pop_front();
+        }
+    }
+
+    let today = Local::now().date_naive();
+    let mut aging = CreditAging::default();
+    for (date, remaining) in charges {
+        let bucket = match (today - date).num_days() {
+            ..=30 => &mut aging.current,
+            31..=60 => &mut aging.days_31_60,
+            61..=90 => &mut aging.days_61_90,
+            _ => &mut aging.over_90,
+        };
+        *bucket += remaining;
+    }
+    aging
+}
+
+/// Calcula el estado de la cuenta y lo concilia contra la suma de sus movimientos
+async fn load_account<C: ConnectionTrait>(
+    db: &C,
+    customer: customers::Model,
+) -> Result<CreditAccount, String> {
+    let entries = CustomerCreditEntries::find()
+        .filter(customer_credit_entries::Column::CustomerId.eq(customer.id))
+        .order_by_asc(customer_credit_entries::Column::Id)
+        .all(db)
+        .await
+        .map_err(|_| DB_ERROR)?;
+
+    let ledger_balance: Decimal = entries.iter().map(|entry| entry.amount).sum();
+    let last_payment_at = entries
+        .iter()
+        .rev()
+        .find(|entry| entry.entry_type == ENTRY_PAYMENT)
+        .map(|entry| entry.created_at.to_string());
+
+    Ok(CreditAccount {
+        customer_id: customer.id,
+        customer_name: customer.name,
+        credit_limit: customer.credit_limit,
+        balance: customer.credit_balance,
+        available_credit: (customer.credit_limit - customer.credit_balance).max(Decimal::ZERO),
+        ledger_balance,
+        is_reconciled: ledger_balance == customer.credit_balance,
+        aging: credit_aging(&entries),
+        last_payment_at,
+    })
+}
+
+/// Agrega el nombre de la forma de pago y del usuario a cada movimiento
+async fn to_credit_entries<C: ConnectionTrait>(
+    db: &C,
+    entries: Vec<customer_credit_entries::Model>,
+) -> Result<Vec<CreditEntry>, String> {
+    let method_ids: Vec<i32> = entries
+        .iter()
+        .filter_map(|entry| entry.payment_method_id)
+        .collect();
+    let method_names: HashMap<i32, String> = PaymentMethods::find()
+        .filter(payment_methods::Column::Id.is_in(method_ids))
+        .all(db)
+        .await
+        .map_err(|_| DB_ERROR)?
+        .into_iter()
+        .map(|method| (method.id, method.name))
+        .collect();
+
+    let user_ids: Vec<String> = entries
+        .iter()
+        .map(|entry| entry.created_by.clone())
+        .collect();
+    let user_names: HashMap<String, String> = Users::find()
+        .filter(users::Column::Id.is_in(user_ids))
+        .all(db)
+        .await
+        .map_err(|_| DB_ERROR)?
+        .into_iter()
+        .map(|user| (user.id, format!("{} {}", user.first_name, user.last_name)))
+        .collect();
+
+    Ok(entries
+        .into_iter()
+        .map(|entry| {
+            let method_name = entry
+                .payment_method_id
+                .and_then(|id| method_names.get(&id).cloned());
+            let user_name = user_names.get(&entry.created_by).cloned();
+            CreditEntry::from_model(entry, method_name, user_name)
+        })
+        .collect())
+}
+
+async fn find_customer<C: ConnectionTrait>(
+    db: &C,
+    customer_id: i32,
+) -> Result<customers::Model, String> {
+    Customers::find_by_id(customer_id)
+        .one(db)
+        .await
+        .map_err(|_| DB_ERROR)?
+        .ok_or(format!("Cliente {} no encontrado", customer_id))
+}
+
+/// Carga el estado de cuenta de un periodo; compartido con la impresión
+pub async fn load_credit_statement<C: ConnectionTrait>(
+    db: &C,
+    params: &CreditStatementParams,
+) -> Result<CreditStatement, String> {
+    let customer = find_customer(db, params.customer_id).await?;
+    let account = load_account(db, customer).await?;
+
+    let mut query = CustomerCreditEntries::find()
+        .filter(customer_credit_entries::Column::CustomerId.eq(params.customer_id));
+
+    // Saldo inicial: el del último movimiento antes del periodo
+    let mut opening_balance = Decimal::ZERO;
+    if let Some(ref date_from) = params.date_from {
+        let parsed_date = NaiveDate::parse_from_str(date_from, "%Y-%m-%d")
+            .map_err(|_| "Fecha inicial inválida")?;
+        let datetime_from = parsed_date.and_hms_opt(0, 0, 0).unwrap();
+
+        opening_balance = CustomerCreditEntries::find()
+            .filter(customer_credit_entries::Column::CustomerId.eq(params.customer_id))
+            .filter(customer_credit_entries::Column::CreatedAt.lt(datetime_from))
+            .order_by_desc(customer_credit_entries::Column::Id)
+            .one(db)
+            .await
+            .map_err(|_| DB_ERROR)?
+            .map_or(Decimal::ZERO, |entry| entry.balance_after);
+
+        query = query.filter(customer_credit_entries::Column::CreatedAt.gte(datetime_from));
+    }
+    if let Some(ref date_to) = params.date_to {
+        let parsed_date =
+            NaiveDate::parse_from_str(date_to, "%Y-%m-%d").map_err(|_| "Fecha final inválida")?;
+        let datetime_to = parsed_date.and_hms_opt(23, 59, 59).unwrap();
+        query = query.filter(customer_credit_entries::Column::CreatedAt.lte(datetime_to));
+    }
+
+    let entries = query
+        .order_by_asc(customer_credit_entries::Column::Id)
+        .all(db)
+        .await
+        .map_err(|_| DB_ERROR)?;
+
+    let charges_total: Decimal = entries
+        .iter()
+        .filter(|entry| entry.amount > Decimal::ZERO)
+        .map(|entry| entry.amount)
+        .sum();
+    let credits_total: Decimal = entries
+        .iter()
+        .filter(|entry| entry.amount < Decimal::ZERO)
+        .map(|entry| -entry.amount)
+        .sum();
+    let closing_balance = entries
+        .last()
+        .map_or(opening_balance, |entry| entry.balance_after);
+
+    Ok(CreditStatement {
+        account,
+        date_from: params.date_from.clone(),
+        date_to: params.date_to.clone(),
+        opening_balance,
+        charges_total,
+        credits_total,
+        closing_balance,
+        entries: to_credit_entries(db, entries).await?,
+        generated_at: Utc::now().fixed_offset().to_string(),
+    })
+}
+
+/// Obtiene el saldo, crédito disponible y antigüedad de la cuenta de un cliente
+#[tauri::command]
+pub async fn get_credit_account(
+    state: tauri::State<'_, AppState>,
+    customer_id: i32,
+) -> Result<CreditAccount, String> {
+    require_permission(&state, "customer.credit")?;
+    let db = &state.database;
+
+    let customer = find_customer(db, customer_id).await?;
+    load_account(db, customer).await
+}
+
+/// Obtiene una página de movimientos de la cuenta de un cliente
+#[tauri::command]
+pub async fn get_credit_ledger(
+    state: tauri::State<'_, AppState>,
+    filters: CreditLedgerFilter,
+) -> Result<CreditLedgerResponse, String> {
+    require_permission(&state, "customer.credit")?;
+    let db = &state.database;
+
+    let mut query = CustomerCreditEntries::find()
+        .filter(customer_credit_entries::Column::CustomerId.eq(filters.customer_id));
+
+    if let Some(entry_type) = filters.entry_type {
+        query = query.filter(customer_credit_entries::Column::EntryType.eq(entry_type));
+    }
+    if let Some(ref date_from) = filters.date_from {
+        if let Ok(parsed_date) = NaiveDate::parse_from_str(date_from, "%Y-%m-%d") {
+            let datetime_from = parsed_date.and_hms_opt(0, 0, 0).unwrap();
+            query = query.filter(customer_credit_entries::Column::CreatedAt.gte(datetime_from));
+        }
+    }
+    if let Some(ref date_to) = filters.date_to {
+        if let Ok(parsed_date) = NaiveDate::parse_from_str(date_to, "%Y-%m-%d") {
+            let datetime_to = parsed_date.and_hms_opt(23, 59, 59).unwrap();
+            query = query.filter(customer_credit_entries::Column::CreatedAt.lte(datetime_to));
+        }
+    }
+
+    // Contar total de items para paginación
+    let total_items = query.clone().count(db).await.map_err(|_| DB_ERROR)?;
+
+    let total_pages = if filters.limit > 0 {
+        (total_items as f64 / filters.limit as f64).ceil() as u64
+    } else {
+        1
+    };
+
+    let offset = (filters.page.saturating_sub(1)) * filters.limit;
+    let entries = query
+        .order_by_desc(customer_credit_entries::Column::Id)
+        .offset(offset)
+        .limit(filters.limit)
+        .all(db)
+        .await
+        .map_err(|_| DB_ERROR)?;
+
+    Ok(CreditLedgerResponse {
+        entries: to_credit_entries(db, entries).await?,
+        total_pages,
+        total_items,
+    })
+}
+
+/// Registra un abono del cliente a su cuenta de crédito
+#[tauri::command]
+pub async fn register_credit_payment(
+    state: tauri::State<'_, AppState>,
+    request: CreditPaymentRequest,
+) -> Result<CreditPaymentResponse, String> {
+    // 1. Validar permiso y monto
+    let session = require_permission(&state, "customer.credit")?;
+    let db = &state.database;
+
+    if request.amount <= Decimal::ZERO {
+        return Err("El monto del abono debe ser mayor a cero".to_string());
+    }
+    let amount = request.amount.round_dp(2);
+
+    // 2. Iniciar transacción
+    let txn = db
+        .begin()
+        .await
+        .map_err(|_| "Error al iniciar transacción")?;
+
+    // 3. Validar la forma de pago; el efectivo entra al turno abierto del cajero
+    let payment_method = PaymentMethods::find_by_id(request.payment_method_id)
+        .one(&txn)
+        .await
+        .map_err(|_| DB_ERROR)?
+        .filter(|method| method.is_active && method.tender != TENDER_CREDIT)
+        .ok_or("Método de pago no válido")?;
+
+    let cash_session = if payment_method.tender == TENDER_CASH {
+        Some(require_open_session(&txn, &session.user_id).await?)
+    } else {
+        find_open_session(&txn, &session.user_id).await?
+    };
+
+    // 4. El abono no puede exceder el saldo adeudado
+    let customer = Customers::find_by_id(request.customer_id)
+        .lock_exclusive()
+        .one(&txn)
+        .await
+        .map_err(|_| DB_ERROR)?
+        .ok_or(format!("Cliente {} no encontrado", request.customer_id))?;
+
+    if amount > customer.credit_balance {
+        return Err(format!(
+            "El abono excede el saldo de la cuenta ({})",
+            customer.credit_balance.max(Decimal::ZERO)
+        ));
+    }
+
+    // 5. Registrar el abono y confirmar
+    let entry = post_credit_entry(
+        &txn,
+        customer.id,
+        ENTRY_PAYMENT,
+        -amount,
+        None,
+        Some(payment_method.id),
+        cash_session.map(|cash_session| cash_session.id),
+        request
+            .notes
+            .map(|notes| notes.trim().to_string())
+            .filter(|notes| !notes.is_empty()),
+        &session.user_id,
+    )
+    .await?;
+
+    let customer = find_customer(&txn, customer.id).await?;
+    let account = load_account(&txn, customer).await?;
+
+    txn.commit()
+        .await
+        .map_err(|_| "Error al confirmar la transacción")?;
+
+    let entry = to_credit_entries(db, vec![entry])
+        .await?
+        .pop()
+        .ok_or(DB_ERROR)?;
+
+    Ok(CreditPaymentResponse { entry, account })
+}
+
+/// Asigna el límite de crédito de un cliente
+#[tauri::command]
+pub async fn set_credit_limit(
+    state: tauri::State<'_, AppState>,
+    request: SetCreditLimitRequest,
+) -> Result<CreditAccount, String> {
+    let session = require_permission(&state, "customer.credit_limit")?;
+    let db = &state.database;
+
+    if request.credit_limit < Decimal::ZERO {
+        return Err("El límite de crédito no puede ser negativo".to_string());
+    }
+
+    let customer = find_customer(db, request.customer_id).await?;
+
+    let mut active_model: customers::ActiveModel = customer.into();
+    active_model.credit_limit = Set(request.credit_limit.round_dp(2));
+    active_model.updated_by = Set(session.user_id.clone());
+
+    let updated = active_model
+        .update(db)
+        .await
+        .map_err(|e| format!("Error al actualizar el límite de crédito: {:?}", e))?;
+
+    load_account(db, updated).await
+}
+
+/// Obtiene el estado de cuenta de un cliente en un periodo
+#[tauri::command]
+pub async fn get_credit_statement(
+    state: tauri::State<'_, AppState>,
+    params: CreditStatementParams,
+) -> Result<CreditStatement, String> {
+    require_permission(&state, "customer.credit")?;
+    load_credit_statement(&state.database, &params).await
+}
@@ -0,0 +1,138 @@
+use crate::entities::customer_credit_entries;
+use sea_orm::prelude::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Tipo de cobro de la forma de pago "Crédito de cliente"
+pub const TENDER_CREDIT: &str = "credit";
+
+pub const ENTRY_CHARGE: &str = "charge"; // Venta cargada a la cuenta
+pub const ENTRY_PAYMENT: &str = "payment"; // Abono del cliente
+pub const ENTRY_REFUND: &str = "refund"; // Devolución abonada a la cuenta
+pub const ENTRY_CANCELLATION: &str = "cancellation"; // Cancelación de una venta a crédito
+
+/// Movimiento de la cuenta de crédito (para lectura/respuesta)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CreditEntry {
+    pub id: i32,
+    pub customer_id: i32,
+    pub entry_type: String,
+    pub amount: Decimal, // Positivo = cargo, negativo = abono
+    pub balance_after: Decimal,
+    pub sale_id: Option<String>,
+    pub payment_method_id: Option<i32>,
+    pub payment_method_name: Option<String>,
+    pub notes: Option<String>,
+    pub created_by: String,
+    pub created_by_name: Option<String>,
+    pub created_at: String,
+}
+
+impl CreditEntry {
+    pub fn from_model(
+        value: customer_credit_entries::Model,
+        payment_method_name: Option<String>,
+        created_by_name: Option<String>,
+    ) -> Self {
+        Self {
+            id: value.id,
+            customer_id: value.customer_id,
+            entry_type: value.entry_type,
+            amount: value.amount,
+            balance_after: value.balance_after,
+            sale_id: value.sale_id,
+            payment_method_id: value.payment_method_id,
+            payment_method_name,
+            notes: value.notes,
+            created_by: value.created_by,
+            created_by_name,
+            created_at: value.created_at.to_string(),
+        }
+    }
+}
+
+/// Antigüedad del saldo; los abonos se aplican a los cargos más antiguos
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct CreditAging {
+    pub current: Decimal, // 0 a 30 días
+    pub days_31_60: Decimal,
+    pub days_61_90: Decimal,
+    pub over_90: Decimal,
+}
+
+/// Estado de la cuenta de crédito de un cliente
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CreditAccount {
+    pub customer_id: i32,
+    pub customer_name: String,
+    pub credit_limit: Decimal,
+    pub balance: Decimal,
+    pub available_credit: Decimal,
+    pub ledger_balance: Decimal, // Suma de los movimientos; debe ser igual al saldo
+    pub is_reconciled: bool,
+    pub aging: CreditAging,
+    pub last_payment_at: Option<String>,
+}
+
+// Request para registrar un abono a la cuenta de un cliente
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CreditPaymentRequest {
+    pub customer_id: i32,
+    pub payment_method_id: i32,
+    pub amount: Decimal,
+    pub notes: Option<String>,
+}
+
+// Respuesta de abono registrado
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CreditPaymentResponse {
+    pub entry: CreditEntry,
+    pub account: CreditAccount,
+}
+
+// Request para asignar el límite de crédito de un cliente
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SetCreditLimitRequest {
+    pub customer_id: i32,
+    pub credit_limit: Decimal,
+}
+
+/// Filtros para obtener los movimientos de una cuenta
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CreditLedgerFilter {
+    pub customer_id: i32,
+    pub entry_type: Option<String>,
+    pub date_from: Option<String>, // AAAA-MM-DD
+    pub date_to: Option<String>,
+    pub page: u64,
+    pub limit: u64,
+}
+
+/// Respuesta paginada de movimientos de una cuenta
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CreditLedgerResponse {
+    pub entries: Vec<CreditEntry>,
+    pub total_pages: u64,
+    pub total_items: u64,
+}
+
+/// Parámetros del estado de cuenta; sin fechas incluye todos los movimientos
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CreditStatementParams {
+    pub customer_id: i32,
+    pub date_from: Option<String>, // AAAA-MM-DD
+    pub date_to: Option<String>,
+}
+
+/// Estado de cuenta de un periodo
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CreditStatement {
+    pub account: CreditAccount,
+    pub date_from: Option<String>,
+    pub date_to: Option<String>,
+    pub opening_balance: Decimal, // Saldo antes del periodo
+    pub charges_total: Decimal,
+    pub credits_total: Decimal, // Abonos, devoluciones y cancelaciones
+    pub closing_balance: Decimal,
+    pub entries: Vec<CreditEntry>,
+    pub generated_at: String,
+}
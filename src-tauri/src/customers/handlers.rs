@@ -0,0 +1,273 @@
+use sea_orm::{
+    sea_query::Condition, ActiveModelTrait, ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter,
+    QueryOrder, QuerySelect,
+};
+
+use super::structs::{Customer, CustomerFilter, CustomerListResponse, NewCustomer, UpdateCustomer};
+use crate::entities::{customers, prelude::Customers};
+use crate::invoices::cfdi::is_valid_rfc;
+use crate::sessions::require_permission;
+use crate::AppState;
+
+const DB_ERROR: &str = "Error en la base de datos";
+
+/// Normaliza un dato opcional: recorta espacios y descarta cadenas vacías
+fn normalize(value: Option<String>) -> Option<String> {
+    value
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+/// Valida los datos fiscales y de contacto de un cliente
+fn validate_customer_data(
+    rfc: Option<&String>,
+    postal_code: Option<&String>,
+    tax_regime: Option<&String>,
+    email: Option<&String>,
+) -> Result<(), String> {
+    if let Some(rfc) = rfc {
+        if !is_valid_rfc(rfc) {
+            return Err("El RFC del cliente no es válido".to_string());
+        }
+    }
+    if let Some(postal_code) = postal_code {
+        if postal_code.len() != 5 || !postal_code.chars().all(|c| c.is_ascii_digit()) {
+            return Err("El código postal debe tener 5 dígitos".to_string());
+        }
+    }
+    if let Some(tax_regime) = tax_regime {
+        if tax_regime.len() != 3 || !tax_regime.chars().all(|c| c.is_ascii_digit()) {
+            return Err("El régimen fiscal debe ser una clave de 3 dígitos".to_string());
+        }
+    }
+    if let Some(email) = email {
+        if !email.contains('@') {
+            return Err("El correo electrónico no es válido".to_string());
+        }
+    }
+
+    Ok(())
+}
+
+/// Verifica que el RFC no esté registrado en otro cliente
+async fn ensure_unique_rfc(
+    db: &sea_orm::DatabaseConnection,
+    rfc: &str,
+    exclude_id: Option<i32>,
+) -> Result<(), String> {
+    let mut query = Customers::find().filter(customers::Column::Rfc.eq(rfc));
+    if let Some(id) = exclude_id {
+        query = query.filter(customers::Column::Id.ne(id));
+    }
+
+    let existing = query.one(db).await.map_err(|_| DB_ERROR)?;
+    if existing.is_some() {
+        return Err("Ya existe un cliente con ese RFC".to_string());
+    }
+
+    Ok(())
+}
+
+/// Obtiene una página de clientes con búsqueda opcional
+#[tauri::command]
+pub async fn get_customers(
+    state: tauri::State<'_, AppState>,
+    filters: CustomerFilter,
+) -> Result<CustomerListResponse, String> {
+    require_permission(&state, "customer.view")?;
+    let db = &state.database;
+
+    let mut query = Customers::find();
+
+    // Filtrar por estado si se especifica
+    if let Some(status) = filters.status {
+        query = query.filter(customers::Column::IsActive.eq(status));
+    }
+
+    // Buscar en nombre, RFC, correo y teléfono
+    if let Some(search) = normalize(filters.search) {
+        query = query.filter(
+            Condition::any()
+                .add(customers::Column::Name.contains(&search))
+                .add(customers::Column::Rfc.contains(search.to_uppercase()))
+                .add(customers::Column::Email.contains(&search))
+                .add(customers::Column::Phone.contains(&search)),
+        );
+    }
+
+    // Contar total de items para paginación
+    let total_items = query.clone().count(db).await.map_err(|_| DB_ERROR)?;
+
+    let total_pages = if filters.limit > 0 {
+        (total_items as f64 / filters.limit as f64).ceil() as u64
+    } else {
+        1
+    };
+
+    let offset = (filters.page.saturating_sub(1)) * filters.limit;
+    let customers_list = query
+        .order_by_asc(customers::Column::Name)
+        .offset(offset)
+        .limit(filters.limit)
+        .all(db)
+        .await
+        .map_err(|_| DB_ERROR)?;
+
+    Ok(CustomerListResponse {
+        customers: customers_list.into_iter().map(Customer::from).collect(),
+        total_pages,
+        total_items,
+    })
+}
+
+/// Obtiene un cliente por ID
+#[tauri::command]
+pub async fn get_customer_by_id(
+    state: tauri::State<'_, AppState>,
+    id: i32,
+) -> Result<Customer, String> {
+    require_permission(&state, "customer.view")?;
+    let db = &state.database;
+
+    let customer = Customers::find_by_id(id)
+        .one(db)
+        .await
+        .map_err(|_| DB_ERROR)?
+        .ok_or("No se encontró el cliente".to_string())?;
+
+    Ok(Customer::from(customer))
+}
+
+/// Crea un nuevo cliente
+#[tauri::command]
+pub async fn create_customer(
+    state: tauri::State<'_, AppState>,
+    customer_data: NewCustomer,
+) -> Result<Customer, String> {
+    let session = require_permission(&state, "customer.create")?;
+    let db = &state.database;
+
+    // Normalizar datos
+    let customer_data = NewCustomer {
+        name: customer_data.name.trim().to_string(),
+        rfc: normalize(customer_data.rfc).map(|rfc| rfc.to_uppercase()),
+        tax_regime: normalize(customer_data.tax_regime),
+        cfdi_use: normalize(customer_data.cfdi_use).map(|cfdi_use| cfdi_use.to_uppercase()),
+        postal_code: normalize(customer_data.postal_code),
+        email: normalize(customer_data.email),
+        phone: normalize(customer_data.phone),
+    };
+
+    if customer_data.name.is_empty() {
+        return Err("El nombre del cliente es obligatorio".to_string());
+    }
+
+    validate_customer_data(
+        customer_data.rfc.as_ref(),
+        customer_data.postal_code.as_ref(),
+        customer_data.tax_regime.as_ref(),
+        customer_data.email.as_ref(),
+    )?;
+
+    if let Some(ref rfc) = customer_data.rfc {
+        ensure_unique_rfc(db, rfc, None).await?;
+    }
+
+    let inserted = customer_data
+        .into_active_model(session.user_id)
+        .insert(db)
+        .await
+        .map_err(|e| format!("Error al registrar el cliente: {:?}", e))?;
+
+    Ok(Customer::from(inserted))
+}
+
+/// Actualiza un cliente existente
+#[tauri::command]
+pub async fn update_customer(
+    state: tauri::State<'_, AppState>,
+    id: i32,
+    update_data: UpdateCustomer,
+) -> Result<Customer, String> {
+    let session = require_permission(&state, "customer.update")?;
+    let db = &state.database;
+
+    Customers::find_by_id(id)
+        .one(db)
+        .await
+        .map_err(|_| DB_ERROR)?
+        .ok_or("No se encontró el cliente a modificar".to_string())?;
+
+    // Normalizar datos (una cadena vacía limpia el campo)
+    let trim = |value: Option<String>| value.map(|v| v.trim().to_string());
+    let update_data = UpdateCustomer {
+        name: trim(update_data.name),
+        rfc: trim(update_data.rfc).map(|rfc| rfc.to_uppercase()),
+        tax_regime: trim(update_data.tax_regime),
+        cfdi_use: trim(update_data.cfdi_use).map(|cfdi_use| cfdi_use.to_uppercase()),
+        postal_code: trim(update_data.postal_code),
+        email: trim(update_data.email),
+        phone: trim(update_data.phone),
+        is_active: update_data.is_active,
+    };
+
+    if update_data.name.as_deref() == Some("") {
+        return Err("El nombre del cliente es obligatorio".to_string());
+    }
+
+    let non_empty = |value: &Option<String>| value.as_ref().filter(|v| !v.is_empty()).cloned();
+    let rfc = non_empty(&update_data.rfc);
+    validate_customer_data(
+        rfc.as_ref(),
+        non_empty(&update_data.postal_code).as_ref(),
+        non_empty(&update_data.tax_regime).as_ref(),
+        non_empty(&update_data.email).as_ref(),
+    )?;
+
+    if let Some(ref rfc) = rfc {
+        ensure_unique_rfc(db, rfc, Some(id)).await?;
+    }
+
+    let updated = update_data
+        .into_active_model(id, session.user_id)
+        .update(db)
+        .await
+        .map_err(|_| DB_ERROR)?;
+
+    Ok(Customer::from(updated))
+}
+
+/// Desactiva un cliente (soft delete). Sus ventas históricas se conservan.
+#[tauri::command]
+pub async fn delete_customer(
+    state: tauri::State<'_, AppState>,
+    id: i32,
+) -> Result<Customer, String> {
+    let session = require_permission(&state, "customer.delete")?;
+    let db = &state.database;
+
+    Customers::find_by_id(id)
+        .one(db)
+        .await
+        .map_err(|_| "Error al consultar el ID del cliente".to_string())?
+        .ok_or("No se encontró el cliente seleccionado".to_string())?;
+
+    let update = UpdateCustomer {
+        name: None,
+        rfc: None,
+        tax_regime: None,
+        cfdi_use: None,
+        postal_code: None,
+        email: None,
+        phone: None,
+        is_active: Some(false),
+    };
+
+    let updated = update
+        .into_active_model(id, session.user_id)
+        .update(db)
+        .await
+        .map_err(|_| "Ocurrió un error al eliminar el cliente".to_string())?;
+
+    Ok(Customer::from(updated))
+}
@@ -0,0 +1,140 @@
+use crate::entities::customers::{self, ActiveModel};
+use sea_orm::{prelude::Decimal, ActiveValue::Set};
+use serde::{Deserialize, Serialize};
+
+/// Cliente (para lectura/respuesta)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Customer {
+    pub id: i32,
+    pub name: String,
+    pub rfc: Option<String>,
+    pub tax_regime: Option<String>,
+    pub cfdi_use: Option<String>,
+    pub postal_code: Option<String>,
+    pub email: Option<String>,
+    pub phone: Option<String>,
+    pub is_active: bool,
+    pub credit_limit: Decimal, // Límite de crédito autorizado; cero = sin crédito
+    pub credit_balance: Decimal, // Saldo adeudado en su cuenta
+    pub loyalty_points: i32,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl From<customers::Model> for Customer {
+    fn from(value: customers::Model) -> Self {
+        Self {
+            id: value.id,
+            name: value.name,
+            rfc: value.rfc,
+            tax_regime: value.tax_regime,
+            cfdi_use: value.cfdi_use,
+            postal_code: value.postal_code,
+            email: value.email,
+            phone: value.phone,
+            is_active: value.is_active,
+            credit_limit: value.credit_limit,
+            credit_balance: value.credit_balance,
+            loyalty_points: value.loyalty_points,
+            created_at: value.created_at.to_string(),
+            updated_at: value.updated_at.to_string(),
+        }
+    }
+}
+
+/// Nuevo cliente (para crear)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct NewCustomer {
+    pub name: String,
+    pub rfc: Option<String>,
+    pub tax_regime: Option<String>,
+    pub cfdi_use: Option<String>,
+    pub postal_code: Option<String>,
+    pub email: Option<String>,
+    pub phone: Option<String>,
+}
+
+impl NewCustomer {
+    pub fn into_active_model(self, created_by: String) -> customers::ActiveModel {
+        ActiveModel {
+            name: Set(self.name),
+            rfc: Set(self.rfc),
+            tax_regime: Set(self.tax_regime),
+            cfdi_use: Set(self.cfdi_use),
+            postal_code: Set(self.postal_code),
+            email: Set(self.email),
+            phone: Set(self.phone),
+            created_by: Set(created_by.clone()),
+            updated_by: Set(created_by),
+            ..Default::default()
+        }
+    }
+}
+
+/// Actualizar cliente (para editar)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct UpdateCustomer {
+    pub name: Option<String>,
+    pub rfc: Option<String>,
+    pub tax_regime: Option<String>,
+    pub cfdi_use: Option<String>,
+    pub postal_code: Option<String>,
+    pub email: Option<String>,
+    pub phone: Option<String>,
+    pub is_active: Option<bool>,
+}
+
+impl UpdateCustomer {
+    pub fn into_active_model(self, id: i32, updated_by: String) -> customers::ActiveModel {
+        let mut active_model = ActiveModel {
+            id: Set(id),
+            ..Default::default()
+        };
+
+        if let Some(name) = self.name {
+            active_model.name = Set(name);
+        }
+        // Los datos opcionales se limpian enviando una cadena vacía
+        if let Some(rfc) = self.rfc {
+            active_model.rfc = Set(Some(rfc).filter(|v| !v.is_empty()));
+        }
+        if let Some(tax_regime) = self.tax_regime {
+            active_model.tax_regime = Set(Some(tax_regime).filter(|v| !v.is_empty()));
+        }
+        if let Some(cfdi_use) = self.cfdi_use {
+            active_model.cfdi_use = Set(Some(cfdi_use).filter(|v| !v.is_empty()));
+        }
+        if let Some(postal_code) = self.postal_code {
+            active_model.postal_code = Set(Some(postal_code).filter(|v| !v.is_empty()));
+        }
+        if let Some(email) = self.email {
+            active_model.email = Set(Some(email).filter(|v| !v.is_empty()));
+        }
+        if let Some(phone) = self.phone {
+            active_model.phone = Set(Some(phone).filter(|v| !v.is_empty()));
+        }
+        if let Some(is_active) = self.is_active {
+            active_model.is_active = Set(is_active);
+        }
+
+        active_model.updated_by = Set(updated_by);
+        active_model
+    }
+}
+
+/// Filtros para obtener clientes
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CustomerFilter {
+    pub status: Option<bool>,   // None = todos
+    pub search: Option<String>, // Búsqueda por nombre, RFC, correo o teléfono
+    pub page: u64,
+    pub limit: u64,
+}
+
+/// Respuesta paginada de clientes
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CustomerListResponse {
+    pub customers: Vec<Customer>,
+    pub total_pages: u64,
+    pub total_items: u64,
+}
@@ -10,12 +10,16 @@ pub struct Model {
     #[sea_orm(unique)]
     pub name: String,
     pub is_active: bool,
+    #[sea_orm(column_type = "Decimal(Some((5, 2)))")]
+    pub points_multiplier: Decimal,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
     #[sea_orm(has_many = "super::products::Entity")]
     Products,
+    #[sea_orm(has_many = "super::promotions::Entity")]
+    Promotions,
 }
 
 impl Related<super::products::Entity> for Entity {
@@ -24,4 +28,10 @@ impl Related<super::products::Entity> for Entity {
     }
 }
 
+impl Related<super::promotions::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Promotions.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
@@ -2,14 +2,34 @@
 
 pub mod prelude;
 
+pub mod cash_movements;
+pub mod cash_session_counts;
+pub mod cash_sessions;
 pub mod categories;
+pub mod coupon_batches;
+pub mod coupons;
+pub mod customer_credit_entries;
+pub mod customers;
+pub mod gift_card_entries;
+pub mod gift_cards;
+pub mod invoices;
+pub mod loyalty_entries;
+pub mod loyalty_settings;
+pub mod parked_sale_items;
+pub mod parked_sales;
 pub mod payment_methods;
 pub mod permissions;
+pub mod product_taxes;
 pub mod products;
 pub mod profile_permissions;
 pub mod profiles;
+pub mod promotions;
+pub mod quote_detail_taxes;
+pub mod quote_details;
+pub mod quotes;
 pub mod refund_details;
 pub mod refunds;
+pub mod sale_detail_taxes;
 pub mod sale_details;
 pub mod sale_payments;
 pub mod sales;
@@ -9,17 +9,33 @@ pub struct Model {
     pub id: i32,
     #[sea_orm(unique)]
     pub name: String,
-    #[sea_orm(unique)]
     pub sat_key: String,
+    pub tender: String,
     pub is_active: bool,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(has_many = "super::customer_credit_entries::Entity")]
+    CustomerCreditEntries,
+    #[sea_orm(has_many = "super::refunds::Entity")]
+    Refunds,
     #[sea_orm(has_many = "super::sale_payments::Entity")]
     SalePayments,
 }
 
+impl Related<super::customer_credit_entries::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::CustomerCreditEntries.def()
+    }
+}
+
+impl Related<super::refunds::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Refunds.def()
+    }
+}
+
 impl Related<super::sale_payments::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::SalePayments.def()
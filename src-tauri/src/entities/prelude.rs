@@ -1,13 +1,33 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 2.0
 
+pub use super::cash_movements::Entity as CashMovements;
+pub use super::cash_session_counts::Entity as CashSessionCounts;
+pub use super::cash_sessions::Entity as CashSessions;
 pub use super::categories::Entity as Categories;
+pub use super::coupon_batches::Entity as CouponBatches;
+pub use super::coupons::Entity as Coupons;
+pub use super::customer_credit_entries::Entity as CustomerCreditEntries;
+pub use super::customers::Entity as Customers;
+pub use super::gift_card_entries::Entity as GiftCardEntries;
+pub use super::gift_cards::Entity as GiftCards;
+pub use super::invoices::Entity as Invoices;
+pub use super::loyalty_entries::Entity as LoyaltyEntries;
+pub use super::loyalty_settings::Entity as LoyaltySettings;
+pub use super::parked_sale_items::Entity as ParkedSaleItems;
+pub use super::parked_sales::Entity as ParkedSales;
 pub use super::payment_methods::Entity as PaymentMethods;
 pub use super::permissions::Entity as Permissions;
+pub use super::product_taxes::Entity as ProductTaxes;
 pub use super::products::Entity as Products;
 pub use super::profile_permissions::Entity as ProfilePermissions;
 pub use super::profiles::Entity as Profiles;
+pub use super::promotions::Entity as Promotions;
+pub use super::quote_detail_taxes::Entity as QuoteDetailTaxes;
+pub use super::quote_details::Entity as QuoteDetails;
+pub use super::quotes::Entity as Quotes;
 pub use super::refund_details::Entity as RefundDetails;
 pub use super::refunds::Entity as Refunds;
+pub use super::sale_detail_taxes::Entity as SaleDetailTaxes;
 pub use super::sale_details::Entity as SaleDetails;
 pub use super::sale_payments::Entity as SalePayments;
 pub use super::sales::Entity as Sales;
@@ -12,7 +12,8 @@ pub struct Model {
     pub category_id: Option<i32>,
     #[sea_orm(unique)]
     pub code: String,
-    pub stock: i32,
+    #[sea_orm(column_type = "Decimal(Some((12, 3)))")]
+    pub stock: Decimal,
     pub is_active: bool,
     #[sea_orm(column_type = "Decimal(Some((10, 2)))")]
     pub price: Decimal,
@@ -24,6 +25,11 @@ pub struct Model {
     pub updated_at: DateTimeWithTimeZone,
     pub created_by: String,
     pub updated_by: String,
+    pub sat_product_key: String,
+    pub sat_unit_key: String,
+    pub is_gift_card: bool,
+    pub unit_of_measure: String,
+    pub allow_fractions: bool,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -36,6 +42,14 @@ pub enum Relation {
         on_delete = "SetNull"
     )]
     Categories,
+    #[sea_orm(has_many = "super::parked_sale_items::Entity")]
+    ParkedSaleItems,
+    #[sea_orm(has_many = "super::product_taxes::Entity")]
+    ProductTaxes,
+    #[sea_orm(has_many = "super::promotions::Entity")]
+    Promotions,
+    #[sea_orm(has_many = "super::quote_details::Entity")]
+    QuoteDetails,
     #[sea_orm(has_many = "super::refund_details::Entity")]
     RefundDetails,
     #[sea_orm(has_many = "super::sale_details::Entity")]
@@ -64,6 +78,24 @@ impl Related<super::categories::Entity> for Entity {
     }
 }
 
+impl Related<super::parked_sale_items::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ParkedSaleItems.def()
+    }
+}
+
+impl Related<super::product_taxes::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ProductTaxes.def()
+    }
+}
+
+impl Related<super::quote_details::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::QuoteDetails.def()
+    }
+}
+
 impl Related<super::refund_details::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::RefundDetails.def()
@@ -76,4 +108,10 @@ impl Related<super::sale_details::Entity> for Entity {
     }
 }
 
+impl Related<super::promotions::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Promotions.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
@@ -12,6 +12,8 @@ pub struct Model {
     #[sea_orm(column_type = "Text")]
     pub description: String,
     pub is_active: bool,
+    #[sea_orm(column_type = "Decimal(Some((5, 2)))", nullable)]
+    pub max_discount_percent: Option<Decimal>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -9,7 +9,8 @@ pub struct Model {
     pub id: i32,
     pub refund_id: i32,
     pub product_id: i32,
-    pub quantity: i32,
+    #[sea_orm(column_type = "Decimal(Some((12, 3)))")]
+    pub quantity: Decimal,
     #[sea_orm(column_type = "Decimal(Some((10, 2)))")]
     pub unit_price: Decimal,
 }
@@ -16,10 +16,30 @@ pub struct Model {
     pub updated_at: DateTimeWithTimeZone,
     pub created_by: String,
     pub updated_by: String,
+    pub payment_method_id: Option<i32>,
+    pub cash_session_id: Option<i32>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::cash_sessions::Entity",
+        from = "Column::CashSessionId",
+        to = "super::cash_sessions::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Restrict"
+    )]
+    CashSessions,
+    #[sea_orm(has_many = "super::gift_card_entries::Entity")]
+    GiftCardEntries,
+    #[sea_orm(
+        belongs_to = "super::payment_methods::Entity",
+        from = "Column::PaymentMethodId",
+        to = "super::payment_methods::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Restrict"
+    )]
+    PaymentMethods,
     #[sea_orm(has_many = "super::refund_details::Entity")]
     RefundDetails,
     #[sea_orm(
@@ -48,6 +68,24 @@ pub enum Relation {
     Users1,
 }
 
+impl Related<super::cash_sessions::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::CashSessions.def()
+    }
+}
+
+impl Related<super::gift_card_entries::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::GiftCardEntries.def()
+    }
+}
+
+impl Related<super::payment_methods::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PaymentMethods.def()
+    }
+}
+
 impl Related<super::refund_details::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::RefundDetails.def()
@@ -9,7 +9,8 @@ pub struct Model {
     pub id: i32,
     pub sale_id: String,
     pub product_id: i32,
-    pub quantity: i32,
+    #[sea_orm(column_type = "Decimal(Some((12, 3)))")]
+    pub quantity: Decimal,
     #[sea_orm(column_type = "Decimal(Some((10, 2)))")]
     pub unit_price: Decimal,
     #[sea_orm(column_type = "Decimal(Some((10, 2)))")]
@@ -20,6 +21,18 @@ pub struct Model {
     pub tax_amount: Decimal,
     #[sea_orm(column_type = "Decimal(Some((10, 2)))")]
     pub total: Decimal,
+    pub discount_type: Option<String>,
+    #[sea_orm(column_type = "Decimal(Some((10, 2)))")]
+    pub discount_value: Decimal,
+    #[sea_orm(column_type = "Decimal(Some((10, 2)))")]
+    pub discount_amount: Decimal,
+    #[sea_orm(column_type = "Decimal(Some((10, 2)))")]
+    pub ticket_discount_amount: Decimal,
+    pub promotion_id: Option<i32>,
+    #[sea_orm(column_type = "Decimal(Some((10, 2)))")]
+    pub promotion_discount_amount: Decimal,
+    #[sea_orm(column_type = "Decimal(Some((10, 2)))")]
+    pub coupon_discount_amount: Decimal,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -32,6 +45,16 @@ pub enum Relation {
         on_delete = "Restrict"
     )]
     Products,
+    #[sea_orm(
+        belongs_to = "super::promotions::Entity",
+        from = "Column::PromotionId",
+        to = "super::promotions::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Restrict"
+    )]
+    Promotions,
+    #[sea_orm(has_many = "super::sale_detail_taxes::Entity")]
+    SaleDetailTaxes,
     #[sea_orm(
         belongs_to = "super::sales::Entity",
         from = "Column::SaleId",
@@ -48,6 +71,18 @@ impl Related<super::products::Entity> for Entity {
     }
 }
 
+impl Related<super::promotions::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Promotions.def()
+    }
+}
+
+impl Related<super::sale_detail_taxes::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::SaleDetailTaxes.def()
+    }
+}
+
 impl Related<super::sales::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::Sales.def()
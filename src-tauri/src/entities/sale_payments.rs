@@ -12,10 +12,28 @@ pub struct Model {
     #[sea_orm(column_type = "Decimal(Some((10, 2)))")]
     pub amount: Decimal,
     pub created_at: DateTimeWithTimeZone,
+    pub cash_session_id: Option<i32>,
+    pub gift_card_id: Option<i32>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::cash_sessions::Entity",
+        from = "Column::CashSessionId",
+        to = "super::cash_sessions::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Restrict"
+    )]
+    CashSessions,
+    #[sea_orm(
+        belongs_to = "super::gift_cards::Entity",
+        from = "Column::GiftCardId",
+        to = "super::gift_cards::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Restrict"
+    )]
+    GiftCards,
     #[sea_orm(
         belongs_to = "super::payment_methods::Entity",
         from = "Column::PaymentMethodId",
@@ -34,6 +52,18 @@ pub enum Relation {
     Sales,
 }
 
+impl Related<super::cash_sessions::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::CashSessions.def()
+    }
+}
+
+impl Related<super::gift_cards::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::GiftCards.def()
+    }
+}
+
 impl Related<super::payment_methods::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::PaymentMethods.def()
@@ -16,10 +16,73 @@ pub struct Model {
     pub updated_at: DateTimeWithTimeZone,
     pub created_by: String,
     pub updated_by: String,
+    pub cancelled_by: Option<String>,
+    pub cancelled_at: Option<DateTimeWithTimeZone>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub cancellation_reason: Option<String>,
+    #[sea_orm(column_type = "Decimal(Some((10, 2)))")]
+    pub change_amount: Decimal,
+    pub customer_id: Option<i32>,
+    pub discount_type: Option<String>,
+    #[sea_orm(column_type = "Decimal(Some((10, 2)))")]
+    pub discount_value: Decimal,
+    #[sea_orm(column_type = "Decimal(Some((10, 2)))")]
+    pub discount_amount: Decimal,
+    pub coupon_id: Option<i32>,
+    #[sea_orm(column_type = "Decimal(Some((10, 2)))")]
+    pub coupon_discount_amount: Decimal,
+    pub cash_session_id: Option<i32>,
+    pub layaway_status: Option<String>,
+    pub layaway_due_date: Option<Date>,
+    #[sea_orm(column_type = "Decimal(Some((10, 2)))")]
+    pub layaway_penalty_amount: Decimal,
+    pub completed_at: Option<DateTimeWithTimeZone>,
+    #[sea_orm(unique)]
+    pub idempotency_key: Option<String>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub idempotency_payload: Option<String>,
+    #[sea_orm(column_type = "Text", nullable)]
+    pub idempotency_response: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::cash_sessions::Entity",
+        from = "Column::CashSessionId",
+        to = "super::cash_sessions::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Restrict"
+    )]
+    CashSessions,
+    #[sea_orm(
+        belongs_to = "super::coupons::Entity",
+        from = "Column::CouponId",
+        to = "super::coupons::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Restrict"
+    )]
+    Coupons,
+    #[sea_orm(has_many = "super::customer_credit_entries::Entity")]
+    CustomerCreditEntries,
+    #[sea_orm(
+        belongs_to = "super::customers::Entity",
+        from = "Column::CustomerId",
+        to = "super::customers::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Restrict"
+    )]
+    Customers,
+    #[sea_orm(has_many = "super::gift_card_entries::Entity")]
+    GiftCardEntries,
+    #[sea_orm(has_many = "super::gift_cards::Entity")]
+    GiftCards,
+    #[sea_orm(has_many = "super::invoices::Entity")]
+    Invoices,
+    #[sea_orm(has_many = "super::loyalty_entries::Entity")]
+    LoyaltyEntries,
+    #[sea_orm(has_many = "super::quotes::Entity")]
+    Quotes,
     #[sea_orm(has_many = "super::refunds::Entity")]
     Refunds,
     #[sea_orm(has_many = "super::sale_details::Entity")]
@@ -42,6 +105,68 @@ pub enum Relation {
         on_delete = "Restrict"
     )]
     Users1,
+    #[sea_orm(
+        belongs_to = "super::users::Entity",
+        from = "Column::CancelledBy",
+        to = "super::users::Column::Id",
+        on_update = "Cascade",
+        on_delete = "Restrict"
+    )]
+    Users3,
+}
+
+impl Related<super::cash_sessions::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::CashSessions.def()
+    }
+}
+
+impl Related<super::coupons::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Coupons.def()
+    }
+}
+
+impl Related<super::customer_credit_entries::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::CustomerCreditEntries.def()
+    }
+}
+
+impl Related<super::customers::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Customers.def()
+    }
+}
+
+impl Related<super::gift_card_entries::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::GiftCardEntries.def()
+    }
+}
+
+impl Related<super::gift_cards::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::GiftCards.def()
+    }
+}
+
+impl Related<super::invoices::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Invoices.def()
+    }
+}
+
+impl Related<super::loyalty_entries::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::LoyaltyEntries.def()
+    }
+}
+
+impl Related<super::quotes::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Quotes.def()
+    }
 }
 
 impl Related<super::refunds::Entity> for Entity {
@@ -0,0 +1,620 @@
+use std::collections::HashMap;
+
+use chrono::{NaiveDate, Utc};
+use sea_orm::{
+    prelude::Decimal, ActiveModelTrait, ActiveValue::Set, ColumnTrait, ConnectionTrait,
+    DatabaseBackend, EntityTrait, FromQueryResult, PaginatorTrait, QueryFilter, QueryOrder,
+    QuerySelect, Statement, TransactionTrait,
+};
+
+use super::structs::{
+    DeactivateGiftCardRequest, GiftCard, GiftCardDetail, GiftCardEntry, GiftCardFilter,
+    GiftCardLiabilityParams, GiftCardLiabilityReport, GiftCardListResponse, GiftCardMovementTotal,
+    NewGiftCard, ENTRY_ISSUE, ENTRY_REDEEM, ENTRY_REFUND, ENTRY_REVERSAL, ENTRY_VOID,
+};
+use crate::coupons::CouponsHandler::{generate_code, normalize_code};
+use crate::entities::{
+    gift_card_entries, gift_cards,
+    prelude::{GiftCardEntries, GiftCards, SalePayments, Users},
+    sale_payments, sales, users,
+};
+use crate::sessions::require_permission;
+use crate::AppState;
+
+const DB_ERROR: &str = "Error en la base de datos";
+const CODE_PREFIX: &str = "GC";
+
+#[derive(FromQueryResult)]
+struct BalanceSummaryRaw {
+    outstanding_balance: Option<Decimal>,
+    cards_with_balance: Option<i64>,
+    ledger_balance: Option<Decimal>,
+}
+
+#[derive(FromQueryResult)]
+struct MovementTotalRaw {
+    entry_type: String,
+    count: Option<i64>,
+    amount: Option<Decimal>,
+}
+
+/// Busca una tarjeta por código y la bloquea hasta el commit para que dos
+/// terminales no consuman el mismo saldo
+pub async fn find_gift_card_for_update<C: ConnectionTrait>(
+    db: &C,
+    code: &str,
+) -> Result<gift_cards::Model, String> {
+    let code = normalize_code(code);
+    let card = GiftCards::find()
+        .filter(gift_cards::Column::Code.eq(&code))
+        .lock_exclusive()
+        .one(db)
+        .await
+        .map_err(|_| DB_ERROR)?
+        .ok_or(format!("La tarjeta de regalo {} no existe", code))?;
+
+    if !card.is_active {
+        return Err(format!("La tarjeta de regalo {} no está activa", code));
+    }
+    Ok(card)
+}
+
+/// Registra un movimiento en la tarjeta y actualiza su saldo; el saldo nunca es negativo
+#[allow(clippy::too_many_arguments)]
+pub async fn post_gift_card_entry<C: ConnectionTrait>(
+    db: &C,
+    card: gift_cards::Model,
+    entry_type: &str,
+    amount: Decimal,
+    sale_id: Option<&str>,
+    refund_id: Option<i32>,
+    notes: Option<String>,
+    user_id: &str,
+) -> Result<gift_cards::Model, String> {
+    let balance_after = card.balance + amount;
+    if balance_after < Decimal::ZERO {
+        return Err(format!(
+            "Saldo insuficiente en la tarjeta {}. Disponible: {}",
+            card.code, card.balance
+        ));
+    }
+
+    gift_card_entries::ActiveModel {
+        gift_card_id: Set(card.id),
+        entry_type: Set(entry_type.to_string()),
+        amount: Set(amount),
+        balance_after: Set(balance_after),
+        sale_id: Set(sale_id.map(|sale_id| sale_id.to_string())),
+        refund_id: Set(refund_id),
+        notes: Set(notes),
+        created_by: Set(user_id.to_string()),
+        ..Default::default()
+    }
+    .insert(db)
+    .await
+    .map_err(|e| format!("Error al registrar el movimiento de la tarjeta: {:?}", e))?;
+
+    let mut active_model: gift_cards::ActiveModel = card.into();
+    active_model.balance = Set(balance_after);
+    active_model.updated_by = Set(user_id.to_string());
+    active_model.updated_at = Set(Utc::now().fixed_offset());
+    active_model
+        .update(db)
+        .await
+        .map_err(|e| format!("Error al actualizar el saldo de la tarjeta: {:?}", e))
+}
+
+/// Emite una tarjeta con saldo inicial. Sin código se genera uno que no exista.
+#[allow(clippy::too_many_arguments)]
+pub async fn issue_gift_card<C: ConnectionTrait>(
+    db: &C,
+    amount: Decimal,
+    code: Option<&str>,
+    entry_type: &str,
+    sale_id: Option<&str>,
+    refund_id: Option<i32>,
+    notes: Option<String>,
+    user_id: &str,
+) -> Result<gift_cards::Model, String> {
+    if amount <= Decimal::ZERO {
+        return Err("El importe de la tarjeta de regalo debe ser mayor a cero".to_string());
+    }
+
+    let code = match code.map(normalize_code).filter(|code| !code.is_empty()) {
+        Some(code) => code,
+        None => loop {
+            let candidate = generate_code(CODE_PREFIX);
+            let exists = GiftCards::find()
+                .filter(gift_cards::Column::Code.eq(&candidate))
+                .count(db)
+                .await
+                .map_err(|_| DB_ERROR)?;
+            if exists == 0 {
+                break candidate;
+            }
+        },
+    };
+
+    let card = gift_cards::ActiveModel {
+        code: Set(code.clone()),
+        initial_amount: Set(amount),
+        balance: Set(Decimal::ZERO),
+        // Solo una tarjeta vendida queda ligada a la venta; un vale de reembolso no
+        sale_id: Set(sale_id
+            .filter(|_| entry_type == ENTRY_ISSUE)
+            .map(|sale_id| sale_id.to_string())),
+        created_by: Set(user_id.to_string()),
+        updated_by: Set(user_id.to_string()),
+        ..Default::default()
+    }
+    .insert(db)
+    .await
+    .map_err(|e| {
+        if e.to_string().contains("duplicate key") {
+            format!("Ya existe una tarjeta de regalo con el código {}", code)
+        } else {
+            format!("Error al emitir la tarjeta de regalo: {:?}", e)
+        }
+    })?;
+
+    post_gift_card_entry(
+        db, card, entry_type, amount, sale_id, refund_id, notes, user_id,
+    )
+    .await
+}
+
+/// Consume saldo de una tarjeta como forma de pago de una venta
+pub async fn redeem_gift_card<C: ConnectionTrait>(
+    db: &C,
+    code: &str,
+    amount: Decimal,
+    sale_id: &str,
+    user_id: &str,
+) -> Result<gift_cards::Model, String> {
+    let card = find_gift_card_for_update(db, code).await?;
+    post_gift_card_entry(
+        db,
+        card,
+        ENTRY_REDEEM,
+        -amount,
+        Some(sale_id),
+        None,
+        None,
+        user_id,
+    )
+    .await
+}
+
+/// Abona un reembolso como saldo a favor: a la tarjeta indicada o a un vale nuevo
+pub async fn refund_to_gift_card<C: ConnectionTrait>(
+    db: &C,
+    code: Option<&str>,
+    amount: Decimal,
+    sale_id: &str,
+    refund_id: i32,
+    user_id: &str,
+) -> Result<gift_cards::Model, String> {
+    match code.filter(|code| !code.trim().is_empty()) {
+        Some(code) => {
+            let card = find_gift_card_for_update(db, code).await?;
+            post_gift_card_entry(
+                db,
+                card,
+                ENTRY_REFUND,
+                amount,
+                Some(sale_id),
+                Some(refund_id),
+                None,
+                user_id,
+            )
+            .await
+        }
+        None => {
+            issue_gift_card(
+                db,
+                amount,
+                None,
+                ENTRY_REFUND,
+                Some(sale_id),
+                Some(refund_id),
+                None,
+                user_id,
+            )
+            .await
+        }
+    }
+}
+
+/// Al cancelar una venta se devuelve el saldo consumido y se anulan las tarjetas
+/// vendidas en ella; una tarjeta vendida que ya tiene consumos impide la cancelación
+pub async fn reverse_sale_gift_cards<C: ConnectionTrait>(
+    db: &C,
+    sale: &sales::Model,
+    reason: &str,
+    user_id: &str,
+) -> Result<(), String> {
+    // Saldo consumido como forma de pago
+    let payments = SalePayments::find()
+        .filter(sale_payments::Column::SaleId.eq(&sale.id))
+        .filter(sale_payments::Column::GiftCardId.is_not_null())
+        .all(db)
+        .await
+        .map_err(|_| DB_ERROR)?;
+
+    for payment in payments {
+        let Some(gift_card_id) = payment.gift_card_id else {
+            continue;
+        };
+        let card = GiftCards::find_by_id(gift_card_id)
+            .lock_exclusive()
+            .one(db)
+            .await
+            .map_err(|_| DB_ERROR)?
+            .ok_or("Tarjeta de regalo no encontrada")?;
+
+        post_gift_card_entry(
+            db,
+            card,
+            ENTRY_REVERSAL,
+            payment.amount,
+            Some(&sale.id),
+            None,
+            Some(reason.to_string()),
+            user_id,
+        )
+        .await?;
+    }
+
+    // Tarjetas vendidas en la venta
+    let issued = GiftCards::find()
+        .filter(gift_cards::Column::SaleId.eq(&sale.id))
+        .lock_exclusive()
+        .all(db)
+        .await
+        .map_err(|_| DB_ERROR)?;
+
+    for card in issued {
+        if card.balance != card.initial_amount {
+            return Err(format!(
+                "La tarjeta de regalo {} ya tiene consumos; no se puede cancelar la venta",
+                card.code
+            ));
+        }
+
+        let balance = card.balance;
+        let voided = post_gift_card_entry(
+            db,
+            card,
+            ENTRY_VOID,
+            -balance,
+            Some(&sale.id),
+            None,
+            Some(reason.to_string()),
+            user_id,
+        )
+        .await?;
+
+        let mut active_model: gift_cards::ActiveModel = voided.into();
+        active_model.is_active = Set(false);
+        active_model
+            .update(db)
+            .await
+            .map_err(|e| format!("Error al desactivar la tarjeta de regalo: {:?}", e))?;
+    }
+
+    Ok(())
+}
+
+/// Tarjetas vendidas en una venta, para el ticket
+pub async fn sale_gift_cards<C: ConnectionTrait>(
+    db: &C,
+    sale_id: &str,
+) -> Result<Vec<GiftCard>, String> {
+    Ok(GiftCards::find()
+        .filter(gift_cards::Column::SaleId.eq(sale_id))
+        .order_by_asc(gift_cards::Column::Id)
+        .all(db)
+        .await
+        .map_err(|_| DB_ERROR)?
+        .into_iter()
+        .map(GiftCard::from)
+        .collect())
+}
+
+/// Consulta el saldo de una tarjeta por su código
+#[tauri::command]
+pub async fn get_gift_card(
+    state: tauri::State<'_, AppState>,
+    code: String,
+) -> Result<GiftCard, String> {
+    require_permission(&state, "sales.create")?;
+
+    let code = normalize_code(&code);
+    GiftCards::find()
+        .filter(gift_cards::Column::Code.eq(&code))
+        .one(&state.database)
+        .await
+        .map_err(|_| DB_ERROR)?
+        .map(GiftCard::from)
+        .ok_or(format!("La tarjeta de regalo {} no existe", code))
+}
+
+/// Obtiene una página de tarjetas de regalo con filtros opcionales
+#[tauri::command]
+pub async fn get_gift_cards(
+    state: tauri::State<'_, AppState>,
+    filters: GiftCardFilter,
+) -> Result<GiftCardListResponse, String> {
+    require_permission(&state, "gift_cards.view")?;
+    let db = &state.database;
+
+    let mut query = GiftCards::find();
+
+    if let Some(search) = filters.search {
+        let search = normalize_code(&search);
+        if !search.is_empty() {
+            query = query.filter(gift_cards::Column::Code.contains(&search));
+        }
+    }
+    if let Some(is_active) = filters.is_active {
+        query = query.filter(gift_cards::Column::IsActive.eq(is_active));
+    }
+    if filters.with_balance == Some(true) {
+        query = query.filter(gift_cards::Column::Balance.gt(Decimal::ZERO));
+    }
+
+    // Contar total de items para paginación
+    let total_items = query.clone().count(db).await.map_err(|_| DB_ERROR)?;
+
+    let total_pages = if filters.limit > 0 {
+        (total_items as f64 / filters.limit as f64).ceil() as u64
+    } else {
+        1
+    };
+
+    let offset = (filters.page.saturating_sub(1)) * filters.limit;
+    let gift_cards_list = query
+        .order_by_desc(gift_cards::Column::CreatedAt)
+        .offset(offset)
+        .limit(filters.limit)
+        .all(db)
+        .await
+        .map_err(|_| DB_ERROR)?;
+
+    Ok(GiftCardListResponse {
+        gift_cards: gift_cards_list.into_iter().map(GiftCard::from).collect(),
+        total_pages,
+        total_items,
+    })
+}
+
+/// Obtiene una tarjeta con todos sus movimientos
+#[tauri::command]
+pub async fn get_gift_card_detail(
+    state: tauri::State<'_, AppState>,
+    gift_card_id: i32,
+) -> Result<GiftCardDetail, String> {
+    require_permission(&state, "gift_cards.view")?;
+    let db = &state.database;
+
+    let card = GiftCards::find_by_id(gift_card_id)
+        .one(db)
+        .await
+        .map_err(|_| DB_ERROR)?
+        .ok_or("Tarjeta de regalo no encontrada")?;
+
+    let entries = GiftCardEntries::find()
+        .filter(gift_card_entries::Column::GiftCardId.eq(card.id))
+        .order_by_asc(gift_card_entries::Column::Id)
+        .all(db)
+        .await
+        .map_err(|_| DB_ERROR)?;
+
+    let user_ids: Vec<String> = entries
+        .iter()
+        .map(|entry| entry.created_by.clone())
+        .collect();
+    let user_names: HashMap<String, String> = Users::find()
+        .filter(users::Column::Id.is_in(user_ids))
+        .all(db)
+        .await
+        .map_err(|_| DB_ERROR)?
+        .into_iter()
+        .map(|user| (user.id, format!("{} {}", user.first_name, user.last_name)))
+        .collect();
+
+    Ok(GiftCardDetail {
+        card: GiftCard::from(card),
+        entries: entries
+            .into_iter()
+            .map(|entry| {
+                let name = user_names.get(&entry.created_by).cloned();
+                GiftCardEntry::from_model(entry, name)
+            })
+            .collect(),
+    })
+}
+
+/// Emite un vale con saldo fuera de una venta
+#[tauri::command]
+pub async fn create_gift_card(
+    state: tauri::State<'_, AppState>,
+    gift_card: NewGiftCard,
+) -> Result<GiftCard, String> {
+    let session = require_permission(&state, "gift_cards.manage")?;
+    let db = &state.database;
+
+    let txn = db
+        .begin()
+        .await
+        .map_err(|_| "Error al iniciar transacción")?;
+
+    let card = issue_gift_card(
+        &txn,
+        gift_card.amount.round_dp(2),
+        gift_card.code.as_deref(),
+        ENTRY_ISSUE,
+        None,
+        None,
+        gift_card
+            .notes
+            .map(|notes| notes.trim().to_string())
+            .filter(|notes| !notes.is_empty()),
+        &session.user_id,
+    )
+    .await?;
+
+    txn.commit()
+        .await
+        .map_err(|_| "Error al confirmar la transacción")?;
+
+    Ok(GiftCard::from(card))
+}
+
+/// Desactiva una tarjeta y anula su saldo (extravío, fraude)
+#[tauri::command]
+pub async fn deactivate_gift_card(
+    state: tauri::State<'_, AppState>,
+    request: DeactivateGiftCardRequest,
+) -> Result<GiftCard, String> {
+    let session = require_permission(&state, "gift_cards.manage")?;
+    let db = &state.database;
+
+    let reason = request.reason.trim();
+    if reason.is_empty() {
+        return Err("Debe indicar el motivo de la desactivación".to_string());
+    }
+
+    let txn = db
+        .begin()
+        .await
+        .map_err(|_| "Error al iniciar transacción")?;
+
+    let card = GiftCards::find_by_id(request.gift_card_id)
+        .lock_exclusive()
+        .one(&txn)
+        .await
+        .map_err(|_| DB_ERROR)?
+        .ok_or("Tarjeta de regalo no encontrada")?;
+
+    if !card.is_active {
+        return Err("La tarjeta de regalo ya está desactivada".to_string());
+    }
+
+    let card = if card.balance > Decimal::ZERO {
+        let balance = card.balance;
+        post_gift_card_entry(
+            &txn,
+            card,
+            ENTRY_VOID,
+            -balance,
+            None,
+            None,
+            Some(reason.to_string()),
+            &session.user_id,
+        )
+        .await?
+    } else {
+        card
+    };
+
+    let mut active_model: gift_cards::ActiveModel = card.into();
+    active_model.is_active = Set(false);
+    active_model.updated_by = Set(session.user_id.clone());
+    active_model.updated_at = Set(Utc::now().fixed_offset());
+
+    let updated = active_model
+        .update(&txn)
+        .await
+        .map_err(|e| format!("Error al desactivar la tarjeta de regalo: {:?}", e))?;
+
+    txn.commit()
+        .await
+        .map_err(|_| "Error al confirmar la transacción")?;
+
+    Ok(GiftCard::from(updated))
+}
+
+/// Reporte del pasivo por tarjetas de regalo: saldo por canjear y movimientos del periodo
+#[tauri::command]
+pub async fn get_gift_card_liability_report(
+    state: tauri::State<'_, AppState>,
+    params: GiftCardLiabilityParams,
+) -> Result<GiftCardLiabilityReport, String> {
+    require_permission(&state, "gift_cards.view")?;
+    let db = &state.database;
+
+    let date_from = match params.date_from {
+        Some(ref date) => Some(
+            NaiveDate::parse_from_str(date, "%Y-%m-%d").map_err(|_| "Fecha inicial inválida")?,
+        ),
+        None => None,
+    };
+    let date_to = match params.date_to {
+        Some(ref date) => {
+            Some(NaiveDate::parse_from_str(date, "%Y-%m-%d").map_err(|_| "Fecha final inválida")?)
+        }
+        None => None,
+    };
+
+    // Saldo vigente contra la suma del libro de movimientos
+    let summary = BalanceSummaryRaw::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        r#"
+        SELECT
+            (SELECT SUM(balance) FROM gift_cards) as outstanding_balance,
+            (SELECT COUNT(*) FROM gift_cards WHERE balance > 0)::bigint as cards_with_balance,
+            (SELECT SUM(amount) FROM gift_card_entries) as ledger_balance
+        "#,
+        [],
+    ))
+    .one(db)
+    .await
+    .map_err(|_| DB_ERROR)?;
+
+    // Emisiones, consumos, reembolsos y anulaciones del periodo
+    let movements = MovementTotalRaw::find_by_statement(Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        r#"
+        SELECT
+            entry_type,
+            COUNT(*)::bigint as count,
+            SUM(amount) as amount
+        FROM gift_card_entries
+        WHERE ($1::date IS NULL OR DATE(created_at) >= $1)
+        AND ($2::date IS NULL OR DATE(created_at) <= $2)
+        GROUP BY entry_type
+        ORDER BY entry_type
+        "#,
+        [date_from.into(), date_to.into()],
+    ))
+    .all(db)
+    .await
+    .map_err(|_| DB_ERROR)?
+    .into_iter()
+    .map(|row| GiftCardMovementTotal {
+        entry_type: row.entry_type,
+        count: row.count.unwrap_or(0),
+        amount: row.amount.unwrap_or(Decimal::ZERO),
+    })
+    .collect();
+
+    let (outstanding_balance, cards_with_balance, ledger_balance) = summary
+        .map(|raw| {
+            (
+                raw.outstanding_balance.unwrap_or(Decimal::ZERO),
+                raw.cards_with_balance.unwrap_or(0),
+                raw.ledger_balance.unwrap_or(Decimal::ZERO),
+            )
+        })
+        .unwrap_or((Decimal::ZERO, 0, Decimal::ZERO));
+
+    Ok(GiftCardLiabilityReport {
+        outstanding_balance,
+        cards_with_balance,
+        ledger_balance,
+        is_reconciled: outstanding_balance == ledger_balance,
+        movements,
+        generated_at: Utc::now().fixed_offset().to_string(),
+    })
+}
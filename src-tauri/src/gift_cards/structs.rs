@@ -0,0 +1,140 @@
+use crate::entities::{gift_card_entries, gift_cards};
+use sea_orm::prelude::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Tipo de cobro de la forma de pago "Tarjeta de regalo"
+pub const TENDER_GIFT_CARD: &str = "gift_card";
+
+pub const ENTRY_ISSUE: &str = "issue"; // Emisión con saldo inicial
+pub const ENTRY_REDEEM: &str = "redeem"; // Consumo como forma de pago
+pub const ENTRY_REFUND: &str = "refund"; // Reembolso abonado como saldo a favor
+pub const ENTRY_REVERSAL: &str = "reversal"; // Consumo devuelto al cancelar la venta
+pub const ENTRY_VOID: &str = "void"; // Saldo anulado al desactivar la tarjeta
+
+/// Tarjeta de regalo o vale (para lectura/respuesta)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct GiftCard {
+    pub id: i32,
+    pub code: String,
+    pub initial_amount: Decimal,
+    pub balance: Decimal,
+    pub is_active: bool,
+    pub sale_id: Option<String>, // Venta en que se vendió la tarjeta
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl From<gift_cards::Model> for GiftCard {
+    fn from(value: gift_cards::Model) -> Self {
+        Self {
+            id: value.id,
+            code: value.code,
+            initial_amount: value.initial_amount,
+            balance: value.balance,
+            is_active: value.is_active,
+            sale_id: value.sale_id,
+            created_at: value.created_at.to_string(),
+            updated_at: value.updated_at.to_string(),
+        }
+    }
+}
+
+/// Movimiento de saldo de una tarjeta
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct GiftCardEntry {
+    pub id: i32,
+    pub gift_card_id: i32,
+    pub entry_type: String,
+    pub amount: Decimal, // Positivo = carga, negativo = consumo
+    pub balance_after: Decimal,
+    pub sale_id: Option<String>,
+    pub refund_id: Option<i32>,
+    pub notes: Option<String>,
+    pub created_by: String,
+    pub created_by_name: Option<String>,
+    pub created_at: String,
+}
+
+impl GiftCardEntry {
+    pub fn from_model(value: gift_card_entries::Model, created_by_name: Option<String>) -> Self {
+        Self {
+            id: value.id,
+            gift_card_id: value.gift_card_id,
+            entry_type: value.entry_type,
+            amount: value.amount,
+            balance_after: value.balance_after,
+            sale_id: value.sale_id,
+            refund_id: value.refund_id,
+            notes: value.notes,
+            created_by: value.created_by,
+            created_by_name,
+            created_at: value.created_at.to_string(),
+        }
+    }
+}
+
+/// Tarjeta con su historial de movimientos
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct GiftCardDetail {
+    pub card: GiftCard,
+    pub entries: Vec<GiftCardEntry>,
+}
+
+/// Filtros para obtener tarjetas de regalo
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct GiftCardFilter {
+    pub search: Option<String>, // Código o parte del código
+    pub is_active: Option<bool>,
+    pub with_balance: Option<bool>, // Solo tarjetas con saldo disponible
+    pub page: u64,
+    pub limit: u64,
+}
+
+/// Respuesta paginada de tarjetas de regalo
+#[derive(Serialize, Deserialize, Debug)]
+pub struct GiftCardListResponse {
+    pub gift_cards: Vec<GiftCard>,
+    pub total_pages: u64,
+    pub total_items: u64,
+}
+
+// Request para emitir un vale con saldo fuera de una venta (cortesías, promociones)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct NewGiftCard {
+    pub amount: Decimal,
+    pub code: Option<String>, // Código impreso en una tarjeta física; se genera si es None
+    pub notes: Option<String>,
+}
+
+// Request para desactivar una tarjeta y anular su saldo
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DeactivateGiftCardRequest {
+    pub gift_card_id: i32,
+    pub reason: String,
+}
+
+/// Parámetros del reporte de saldos; sin fechas incluye todos los movimientos
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct GiftCardLiabilityParams {
+    pub date_from: Option<String>, // AAAA-MM-DD
+    pub date_to: Option<String>,
+}
+
+/// Importe y número de movimientos de un tipo en el periodo
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct GiftCardMovementTotal {
+    pub entry_type: String,
+    pub count: i64,
+    pub amount: Decimal,
+}
+
+/// Pasivo por tarjetas de regalo: saldo pendiente de canjear y movimientos del periodo
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct GiftCardLiabilityReport {
+    pub outstanding_balance: Decimal, // Saldo pendiente de canjear de todas las tarjetas
+    pub cards_with_balance: i64,
+    pub ledger_balance: Decimal, // Suma de todos los movimientos; debe ser igual al saldo
+    pub is_reconciled: bool,
+    pub movements: Vec<GiftCardMovementTotal>,
+    pub generated_at: String,
+}
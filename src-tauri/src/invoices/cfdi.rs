@@ -0,0 +1,497 @@
+//! Generación del XML de CFDI 4.0 (sin sello)
+
+use std::collections::BTreeMap;
+
+use sea_orm::prelude::Decimal;
+
+use super::structs::{InvoiceReceiver, IssuerInfo};
+use crate::products::structs::TAX_IEPS;
+use crate::sales::structs::SaleLineTax;
+
+/// Objeto de impuesto "01": no objeto de impuesto
+const NON_TAXABLE_OBJECT: &str = "01";
+/// Objeto de impuesto "02": sí objeto de impuesto
+const TAXABLE_OBJECT: &str = "02";
+/// Impuesto "002": IVA
+const TAX_IVA: &str = "002";
+/// Impuesto "003": IEPS
+const TAX_IEPS_CODE: &str = "003";
+/// Método de pago "PUE": pago en una sola exhibición
+pub const PAYMENT_METHOD_SINGLE: &str = "PUE";
+/// Método de pago "PPD": pago en parcialidades o diferido
+pub const PAYMENT_METHOD_DEFERRED: &str = "PPD";
+
+/// Concepto facturado (una línea de la venta)
+#[derive(Debug, Clone)]
+pub struct CfdiConcept {
+    pub sat_product_key: String,
+    pub code: String,
+    pub quantity: Decimal,
+    pub sat_unit_key: String,
+    pub description: String,
+    pub unit_price: Decimal,
+    pub amount: Decimal,   // Importe antes de descuentos
+    pub discount: Decimal, // Descuento antes de impuestos
+    pub taxes: Vec<SaleLineTax>,
+}
+
+/// Datos necesarios para construir el comprobante
+#[derive(Debug, Clone)]
+pub struct CfdiInput<'a> {
+    pub issuer: &'a IssuerInfo,
+    pub receiver: &'a InvoiceReceiver,
+    pub date: String, // Formato AAAA-MM-DDThh:mm:ss
+    pub payment_form: String,
+    pub payment_method: &'static str, // MetodoPago: PUE o PPD
+    pub concepts: Vec<CfdiConcept>,
+}
+
+/// Escapa caracteres especiales para atributos XML
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+fn amount(value: Decimal) -> String {
+    format!("{:.2}", value)
+}
+
+fn rate(value: Decimal) -> String {
+    format!("{:.6}", value)
+}
+
+/// ValorUnitario admite hasta seis decimales; con dos o menos se escribe a centavos
+fn unit_value(value: Decimal) -> String {
+    if value.round_dp(2) == value {
+        amount(value)
+    } else {
+        value.normalize().to_string()
+    }
+}
+
+/// Valor unitario, importe y descuento de un concepto a partir de la línea de venta.
+/// El importe es siempre el valor unitario por la cantidad y el descuento es lo que
+/// lo separa del subtotal, la base de los impuestos. Con impuestos incluidos el precio
+/// se expresa sin impuestos a seis decimales; si el subtotal absorbió un redondeo que
+/// lo deja arriba del importe, el valor unitario se toma del subtotal.
+pub fn concept_amounts(
+    unit_price: Decimal,
+    quantity: Decimal,
+    subtotal: Decimal,
+    taxes: &[SaleLineTax],
+) -> (Decimal, Decimal, Decimal) {
+    let mut unit_price = unit_price;
+    if taxes.iter().any(|tax| tax.is_included) {
+        let factor = taxes
+            .iter()
+            .filter(|tax| !tax.is_exempt)
+            .fold(Decimal::ONE, |factor, tax| {
+                factor * (Decimal::ONE + tax.rate)
+            });
+        unit_price = (unit_price / factor).round_dp(6);
+    }
+
+    let mut amount = (unit_price * quantity).round_dp(2);
+    if amount < subtotal && !quantity.is_zero() {
+        unit_price = (subtotal / quantity).round_dp(6);
+        amount = (unit_price * quantity).round_dp(2);
+    }
+
+    (unit_price, amount, (amount - subtotal).max(Decimal::ZERO))
+}
+
+/// Clave SAT del impuesto
+fn tax_code(tax_type: &str) -> &'static str {
+    if tax_type == TAX_IEPS {
+        TAX_IEPS_CODE
+    } else {
+        TAX_IVA
+    }
+}
+
+/// Nodo Traslado; los exentos solo llevan base e impuesto
+fn transfer(
+    indent: &str,
+    code: &str,
+    tax_rate: Decimal,
+    is_exempt: bool,
+    base: Decimal,
+    tax_amount: Decimal,
+) -> String {
+    if is_exempt {
+        format!(
+            r#"{}<cfdi:Traslado Base="{}" Impuesto="{}" TipoFactor="Exento"/>"#,
+            indent,
+            amount(base),
+            code,
+        )
+    } else {
+        format!(
+            r#"{}<cfdi:Traslado Base="{}" Impuesto="{}" TipoFactor="Tasa" TasaOCuota="{}" Importe="{}"/>"#,
+            indent,
+            amount(base),
+            code,
+            rate(tax_rate),
+            amount(tax_amount),
+        )
+    }
+}
+
+/// Valida la estructura de un RFC de persona moral (12) o física (13)
+pub fn is_valid_rfc(rfc: &str) -> bool {
+    let chars: Vec<char> = rfc.chars().collect();
+    let prefix = match chars.len() {
+        12 => 3,
+        13 => 4,
+        _ => return false,
+    };
+
+    let letters_ok = chars[..prefix]
+        .iter()
+        .all(|c| c.is_ascii_uppercase() || *c == 'Ñ' || *c == '&');
+    let date_ok = chars[prefix..prefix + 6].iter().all(|c| c.is_ascii_digit());
+    let homoclave_ok = chars[prefix + 6..]
+        .iter()
+        .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit());
+
+    letters_ok && date_ok && homoclave_ok
+}
+
+/// Construye el XML del comprobante de ingreso listo para sellar y timbrar
+pub fn build_cfdi_xml(input: &CfdiInput) -> String {
+    let subtotal: Decimal = input.concepts.iter().map(|c| c.amount).sum();
+    let discount: Decimal = input.concepts.iter().map(|c| c.discount).sum();
+    let total_taxes: Decimal = input
+        .concepts
+        .iter()
+        .flat_map(|c| &c.taxes)
+        .map(|tax| tax.amount)
+        .sum();
+    let total = subtotal - discount + total_taxes;
+
+    // El atributo Descuento solo se incluye cuando hay descuento
+    let discount_attr = |value: Decimal| {
+        if value > Decimal::ZERO {
+            format!(r#" Descuento="{}""#, amount(value))
+        } else {
+            String::new()
+        }
+    };
+
+    let mut xml = String::new();
+    xml.push_str(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    xml.push('\n');
+    xml.push_str(&format!(
+        concat!(
+            r#"<cfdi:Comprobante xmlns:cfdi="http://www.sat.gob.mx/cfd/4" "#,
+            r#"xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" "#,
+            r#"xsi:schemaLocation="http://www.sat.gob.mx/cfd/4 http://www.sat.gob.mx/sitio_internet/cfd/4/cfdv40.xsd" "#,
+            r#"Version="4.0" Fecha="{}" FormaPago="{}" SubTotal="{}"{} Moneda="MXN" Total="{}" "#,
+            r#"TipoDeComprobante="I" Exportacion="01" MetodoPago="{}" LugarExpedicion="{}">"#
+        ),
+        escape(&input.date),
+        escape(&input.payment_form),
+        amount(subtotal),
+        discount_attr(discount),
+        amount(total),
+        input.payment_method,
+        escape(&input.issuer.postal_code),
+    ));
+    xml.push('\n');
+
+    xml.push_str(&format!(
+        r#"  <cfdi:Emisor Rfc="{}" Nombre="{}" RegimenFiscal="{}"/>"#,
+        escape(&input.issuer.rfc),
+        escape(&input.issuer.name),
+        escape(&input.issuer.tax_regime),
+    ));
+    xml.push('\n');
+
+    xml.push_str(&format!(
+        concat!(
+            r#"  <cfdi:Receptor Rfc="{}" Nombre="{}" DomicilioFiscalReceptor="{}" "#,
+            r#"RegimenFiscalReceptor="{}" UsoCFDI="{}"/>"#
+        ),
+        escape(&input.receiver.rfc),
+        escape(&input.receiver.name),
+        escape(&input.receiver.postal_code),
+        escape(&input.receiver.tax_regime),
+        escape(&input.receiver.cfdi_use),
+    ));
+    xml.push('\n');
+
+    // Conceptos con sus traslados de IVA e IEPS
+    let mut transfers: BTreeMap<(&str, bool, Decimal), (Decimal, Decimal)> = BTreeMap::new();
+    xml.push_str("  <cfdi:Conceptos>\n");
+    for concept in &input.concepts {
+        let taxable_object = if concept.taxes.is_empty() {
+            NON_TAXABLE_OBJECT
+        } else {
+            TAXABLE_OBJECT
+        };
+        xml.push_str(&format!(
+            concat!(
+                r#"    <cfdi:Concepto ClaveProdServ="{}" NoIdentificacion="{}" Cantidad="{}" "#,
+                r#"ClaveUnidad="{}" Descripcion="{}" ValorUnitario="{}" Importe="{}"{} ObjetoImp="{}">"#
+            ),
+            escape(&concept.sat_product_key),
+            escape(&concept.code),
+            concept.quantity.normalize(),
+            escape(&concept.sat_unit_key),
+            escape(&concept.description),
+            unit_value(concept.unit_price),
+            amount(concept.amount),
+            discount_attr(concept.discount),
+            taxable_object,
+        ));
+        xml.push('\n');
+
+        if !concept.taxes.is_empty() {
+            xml.push_str("      <cfdi:Impuestos>\n        <cfdi:Traslados>\n");
+            for tax in &concept.taxes {
+                let code = tax_code(&tax.tax_type);
+                xml.push_str(&transfer(
+                    "          ",
+                    code,
+                    tax.rate,
+                    tax.is_exempt,
+                    tax.base,
+                    tax.amount,
+                ));
+                xml.push('\n');
+
+                let entry = transfers
+                    .entry((code, tax.is_exempt, tax.rate))
+                    .or_insert((Decimal::ZERO, Decimal::ZERO));
+                entry.0 += tax.base;
+                entry.1 += tax.amount;
+            }
+            xml.push_str("        </cfdi:Traslados>\n      </cfdi:Impuestos>\n");
+        }
+        xml.push_str("    </cfdi:Concepto>\n");
+    }
+    xml.push_str("  </cfdi:Conceptos>\n");
+
+    // Resumen de impuestos del comprobante agrupado por impuesto y tasa.
+    // TotalImpuestosTrasladados se omite si todos los traslados son exentos.
+    if !transfers.is_empty() {
+        let total_attr = if transfers.keys().any(|(_, is_exempt, _)| !is_exempt) {
+            format!(r#" TotalImpuestosTrasladados="{}""#, amount(total_taxes))
+        } else {
+            String::new()
+        };
+        xml.push_str(&format!(
+            "  <cfdi:Impuestos{}>\n    <cfdi:Traslados>\n",
+            total_attr
+        ));
+        for ((code, is_exempt, tax_rate), (base, tax_amount)) in &transfers {
+            xml.push_str(&transfer(
+                "      ",
+                code,
+                *tax_rate,
+                *is_exempt,
+                *base,
+                *tax_amount,
+            ));
+            xml.push('\n');
+        }
+        xml.push_str("    </cfdi:Traslados>\n  </cfdi:Impuestos>\n");
+    }
+    xml.push_str("</cfdi:Comprobante>\n");
+
+    xml
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::products::structs::TAX_IVA as IVA_TYPE;
+
+    fn line_tax(tax_type: &str, rate: Decimal, is_included: bool, base: Decimal) -> SaleLineTax {
+        SaleLineTax {
+            tax_type: tax_type.to_string(),
+            rate,
+            is_included,
+            is_exempt: false,
+            base,
+            amount: (base * rate).round_dp(2),
+        }
+    }
+
+    fn tax(tax_type: &str, rate: Decimal, is_exempt: bool, base: Decimal) -> SaleLineTax {
+        SaleLineTax {
+            is_exempt,
+            amount: if is_exempt {
+                Decimal::ZERO
+            } else {
+                (base * rate).round_dp(2)
+            },
+            ..line_tax(tax_type, rate, false, base)
+        }
+    }
+
+    fn concept(
+        unit_price: Decimal,
+        quantity: Decimal,
+        discount: Decimal,
+        taxes: Vec<SaleLineTax>,
+    ) -> CfdiConcept {
+        CfdiConcept {
+            sat_product_key: "50202306".to_string(),
+            code: "7501055300846".to_string(),
+            quantity,
+            sat_unit_key: "H87".to_string(),
+            description: "Refresco".to_string(),
+            unit_price,
+            amount: (unit_price * quantity).round_dp(2),
+            discount,
+            taxes,
+        }
+    }
+
+    /// Una línea con IEPS 8% e IVA 16% y $10 de descuento, y otra exenta de IVA
+    fn xml(payment_form: &str, payment_method: &'static str) -> String {
+        let issuer = IssuerInfo {
+            rfc: "EKU9003173C9".to_string(),
+            name: "ESCUELA KEMPER URGATE".to_string(),
+            tax_regime: "601".to_string(),
+            postal_code: "42501".to_string(),
+        };
+        let receiver = InvoiceReceiver {
+            rfc: "XAXX010101000".to_string(),
+            name: "PUBLICO EN GENERAL".to_string(),
+            postal_code: "42501".to_string(),
+            tax_regime: "616".to_string(),
+            cfdi_use: "S01".to_string(),
+        };
+        let subtotal = Decimal::from(90);
+        let ieps = tax(TAX_IEPS, Decimal::new(8, 2), false, subtotal);
+        let iva = tax(IVA_TYPE, Decimal::new(16, 2), false, subtotal + ieps.amount);
+
+        build_cfdi_xml(&CfdiInput {
+            issuer: &issuer,
+            receiver: &receiver,
+            date: "2026-03-02T12:00:00".to_string(),
+            payment_form: payment_form.to_string(),
+            payment_method,
+            concepts: vec![
+                concept(
+                    Decimal::from(100),
+                    Decimal::ONE,
+                    Decimal::from(10),
+                    vec![ieps, iva],
+                ),
+                concept(
+                    Decimal::from(50),
+                    Decimal::from(2),
+                    Decimal::ZERO,
+                    vec![tax(IVA_TYPE, Decimal::ZERO, true, Decimal::from(100))],
+                ),
+            ],
+        })
+    }
+
+    #[test]
+    fn xml_totals_include_discount_and_transfers() {
+        let xml = xml("01", PAYMENT_METHOD_SINGLE);
+
+        // 200 de importe - 10 de descuento + 7.20 de IEPS + 15.55 de IVA
+        assert!(xml.contains(r#"SubTotal="200.00" Descuento="10.00" Moneda="MXN" Total="212.75""#));
+        assert!(xml.contains(r#"<cfdi:Impuestos TotalImpuestosTrasladados="22.75">"#));
+        assert!(xml.contains(
+            r#"      <cfdi:Traslado Base="97.20" Impuesto="002" TipoFactor="Tasa" TasaOCuota="0.160000" Importe="15.55"/>"#
+        ));
+        assert!(xml.contains(
+            r#"      <cfdi:Traslado Base="90.00" Impuesto="003" TipoFactor="Tasa" TasaOCuota="0.080000" Importe="7.20"/>"#
+        ));
+        assert!(xml.contains(r#"FormaPago="01""#));
+        assert!(xml.contains(r#"MetodoPago="PUE""#));
+    }
+
+    #[test]
+    fn exempt_lines_have_no_rate_or_amount() {
+        let xml = xml("01", PAYMENT_METHOD_SINGLE);
+
+        assert!(xml.contains(
+            r#"          <cfdi:Traslado Base="100.00" Impuesto="002" TipoFactor="Exento"/>"#
+        ));
+        assert!(xml.contains(
+            r#"      <cfdi:Traslado Base="100.00" Impuesto="002" TipoFactor="Exento"/>"#
+        ));
+    }
+
+    #[test]
+    fn ieps_is_transferred_before_iva() {
+        let xml = xml("01", PAYMENT_METHOD_SINGLE);
+        let ieps = xml.find(r#"Impuesto="003""#).unwrap();
+        let iva = xml.find(r#"Impuesto="002""#).unwrap();
+
+        assert!(ieps < iva);
+        assert!(xml.contains(r#"Base="97.20" Impuesto="002""#));
+    }
+
+    #[test]
+    fn credit_sales_are_deferred_payments() {
+        let xml = xml("99", PAYMENT_METHOD_DEFERRED);
+
+        assert!(xml.contains(r#"FormaPago="99""#));
+        assert!(xml.contains(r#"MetodoPago="PPD""#));
+    }
+
+    #[test]
+    fn validates_rfc_structure() {
+        assert!(is_valid_rfc("EKU9003173C9")); // Persona moral, 12
+        assert!(is_valid_rfc("GODE561231GR8")); // Persona física, 13
+        assert!(is_valid_rfc("XAXX010101000")); // Público en general
+        assert!(is_valid_rfc("XEXX010101000")); // Extranjero
+        assert!(!is_valid_rfc("gode561231gr8")); // Se valida ya en mayúsculas
+        assert!(!is_valid_rfc("EKU900317C9")); // 11 caracteres
+        assert!(!is_valid_rfc("GODE561231GR8X")); // 14 caracteres
+        assert!(!is_valid_rfc("GOD3561231GR8")); // Dígito en las iniciales
+        assert!(!is_valid_rfc("GODE56A231GR8")); // Letra en la fecha
+    }
+
+    #[test]
+    fn concept_amount_matches_unit_value_times_quantity() {
+        // 100 piezas de $10.00 con IVA incluido: a dos decimales el valor unitario
+        // sería 8.62 y el importe quedaría 0.07 por debajo del subtotal
+        let subtotal = Decimal::new(86207, 2);
+        let taxes = [line_tax(IVA_TYPE, Decimal::new(16, 2), true, subtotal)];
+        let (unit_price, amount, discount) =
+            concept_amounts(Decimal::from(10), Decimal::from(100), subtotal, &taxes);
+
+        assert_eq!(unit_price, Decimal::new(8620690, 6));
+        assert_eq!(amount, (unit_price * Decimal::from(100)).round_dp(2));
+        assert_eq!(amount - discount, subtotal);
+        assert_eq!(discount, Decimal::ZERO);
+    }
+
+    #[test]
+    fn concept_discount_is_expressed_without_taxes() {
+        // $100 con IVA incluido y $10 de descuento: subtotal 77.59
+        let subtotal = Decimal::new(7759, 2);
+        let taxes = [line_tax(IVA_TYPE, Decimal::new(16, 2), true, subtotal)];
+        let (unit_price, amount, discount) =
+            concept_amounts(Decimal::from(100), Decimal::ONE, subtotal, &taxes);
+
+        assert_eq!(amount, unit_price.round_dp(2));
+        assert_eq!(amount, Decimal::new(8621, 2));
+        assert_eq!(discount, Decimal::new(862, 2));
+    }
+
+    #[test]
+    fn concept_absorbs_subtotal_rounding_in_unit_value() {
+        // $5.55 con IVA incluido: el subtotal absorbe un centavo y queda en 4.79
+        let subtotal = Decimal::new(479, 2);
+        let taxes = [line_tax(IVA_TYPE, Decimal::new(16, 2), true, subtotal)];
+        let (unit_price, amount, discount) =
+            concept_amounts(Decimal::new(555, 2), Decimal::ONE, subtotal, &taxes);
+
+        assert_eq!(unit_price, subtotal);
+        assert_eq!(amount, subtotal);
+        assert_eq!(discount, Decimal::ZERO);
+    }
+}
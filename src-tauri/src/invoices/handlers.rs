@@ -0,0 +1,298 @@
+use std::collections::HashMap;
+
+use chrono::Local;
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, ConnectionTrait, EntityTrait, PaginatorTrait,
+    QueryFilter, QueryOrder, QuerySelect, TransactionTrait,
+};
+
+use super::cfdi::{
+    build_cfdi_xml, concept_amounts, is_valid_rfc, CfdiConcept, CfdiInput, PAYMENT_METHOD_DEFERRED,
+    PAYMENT_METHOD_SINGLE,
+};
+use super::pac::{configured_pac, PacProvider};
+use super::structs::{
+    CreateInvoiceRequest, Invoice, InvoiceReceiver, IssuerInfo, STATUS_DRAFT, STATUS_STAMPED,
+};
+use crate::customer_credit::structs::TENDER_CREDIT;
+use crate::entities::{
+    invoices, payment_methods,
+    prelude::{Customers, Invoices, PaymentMethods, Products, Refunds, SalePayments, Sales},
+    products, refunds, sale_payments,
+};
+use crate::sales::SalesHandler::load_sale_detail;
+use crate::sessions::require_permission;
+use crate::AppState;
+
+const DB_ERROR: &str = "Error en la base de datos";
+const GENERIC_PAYMENT_FORM: &str = "99"; // Por definir
+
+/// Toma los datos fiscales del receptor del cliente asignado a la venta
+async fn customer_receiver<C: ConnectionTrait>(
+    db: &C,
+    customer_id: Option<i32>,
+) -> Result<InvoiceReceiver, String> {
+    let customer_id = customer_id.ok_or("La venta no tiene cliente; capture los datos fiscales")?;
+    let customer = Customers::find_by_id(customer_id)
+        .one(db)
+        .await
+        .map_err(|_| DB_ERROR)?
+        .ok_or("Cliente no encontrado")?;
+
+    match (
+        customer.rfc,
+        customer.postal_code,
+        customer.tax_regime,
+        customer.cfdi_use,
+    ) {
+        (Some(rfc), Some(postal_code), Some(tax_regime), Some(cfdi_use)) => Ok(InvoiceReceiver {
+            rfc,
+            name: customer.name,
+            postal_code,
+            tax_regime,
+            cfdi_use,
+        }),
+        _ => Err("El cliente no tiene datos fiscales completos".to_string()),
+    }
+}
+
+/// Genera la factura CFDI 4.0 (sin timbrar) de una venta
+#[tauri::command]
+pub async fn create_invoice(
+    state: tauri::State<'_, AppState>,
+    request: CreateInvoiceRequest,
+) -> Result<Invoice, String> {
+    let session = require_permission(&state, "invoices.create")?;
+    let db = &state.database;
+    let issuer = IssuerInfo::from_env()?;
+
+    let txn = db
+        .begin()
+        .await
+        .map_err(|_| "Error al iniciar transacción")?;
+
+    // 1. Bloquear la venta para que dos solicitudes no la facturen a la vez y
+    // validar que se pueda facturar
+    Sales::find_by_id(request.sale_id.clone())
+        .lock_exclusive()
+        .one(&txn)
+        .await
+        .map_err(|_| DB_ERROR)?
+        .ok_or("Venta no encontrada")?;
+
+    let detail = load_sale_detail(&txn, &request.sale_id).await?;
+    if !detail.sale.status {
+        return Err("No se puede facturar una venta cancelada".to_string());
+    }
+    if detail.sale.completed_at.is_none() {
+        return Err("No se puede facturar un apartado pendiente de liquidar".to_string());
+    }
+
+    // 2. Validar datos fiscales del receptor
+    let mut receiver = match request.receiver {
+        Some(receiver) => receiver,
+        None => customer_receiver(&txn, detail.sale.customer_id).await?,
+    };
+    receiver.rfc = receiver.rfc.trim().to_uppercase();
+    receiver.name = receiver.name.trim().to_uppercase();
+
+    if !is_valid_rfc(&receiver.rfc) {
+        return Err("El RFC del receptor no es válido".to_string());
+    }
+    if receiver.name.is_empty() {
+        return Err("El nombre o razón social del receptor es obligatorio".to_string());
+    }
+    if receiver.postal_code.len() != 5 || !receiver.postal_code.chars().all(|c| c.is_ascii_digit())
+    {
+        return Err("El código postal del receptor debe tener 5 dígitos".to_string());
+    }
+    if receiver.tax_regime.len() != 3 || receiver.cfdi_use.is_empty() {
+        return Err("El régimen fiscal y el uso de CFDI son obligatorios".to_string());
+    }
+
+    // 3. Validar que la venta no esté facturada ni tenga devoluciones
+    let existing = Invoices::find()
+        .filter(invoices::Column::SaleId.eq(&detail.sale.id))
+        .count(&txn)
+        .await
+        .map_err(|_| DB_ERROR)?;
+    if existing > 0 {
+        return Err("La venta ya tiene una factura registrada".to_string());
+    }
+
+    let refunds_count = Refunds::find()
+        .filter(refunds::Column::SaleId.eq(&detail.sale.id))
+        .count(&txn)
+        .await
+        .map_err(|_| DB_ERROR)?;
+    if refunds_count > 0 {
+        return Err("No se puede facturar una venta con devoluciones".to_string());
+    }
+
+    // 4. Forma y método de pago: si una parte se pagó a crédito la venta no está
+    // liquidada, se factura como PPD con forma "99"; si no, PUE con el pago de mayor monto
+    let methods: Vec<payment_methods::Model> = SalePayments::find()
+        .find_also_related(PaymentMethods)
+        .filter(sale_payments::Column::SaleId.eq(&detail.sale.id))
+        .order_by_desc(sale_payments::Column::Amount)
+        .all(&txn)
+        .await
+        .map_err(|_| DB_ERROR)?
+        .into_iter()
+        .filter_map(|(_, method)| method)
+        .collect();
+    let (payment_form, payment_method) =
+        if methods.iter().any(|method| method.tender == TENDER_CREDIT) {
+            (GENERIC_PAYMENT_FORM.to_string(), PAYMENT_METHOD_DEFERRED)
+        } else {
+            (
+                methods
+                    .into_iter()
+                    .next()
+                    .map(|method| method.sat_key)
+                    .unwrap_or_else(|| GENERIC_PAYMENT_FORM.to_string()),
+                PAYMENT_METHOD_SINGLE,
+            )
+        };
+
+    // 5. Claves SAT de los productos vendidos
+    let product_ids: Vec<i32> = detail.lines.iter().map(|line| line.product_id).collect();
+    let products_by_id: HashMap<i32, products::Model> = Products::find()
+        .filter(products::Column::Id.is_in(product_ids))
+        .all(&txn)
+        .await
+        .map_err(|_| DB_ERROR)?
+        .into_iter()
+        .map(|product| (product.id, product))
+        .collect();
+
+    let concepts = detail
+        .lines
+        .iter()
+        .map(|line| {
+            let product = products_by_id
+                .get(&line.product_id)
+                .ok_or(format!("Producto {} no encontrado", line.product_id))?;
+
+            // Con impuestos incluidos, el precio y los descuentos se expresan sin impuestos
+            let (unit_price, amount, discount) =
+                concept_amounts(line.unit_price, line.quantity, line.subtotal, &line.taxes);
+
+            Ok(CfdiConcept {
+                sat_product_key: product.sat_product_key.clone(),
+                code: product.code.clone(),
+                quantity: line.quantity,
+                sat_unit_key: product.sat_unit_key.clone(),
+                description: product.name.clone(),
+                unit_price,
+                amount,
+                discount,
+                taxes: line.taxes.clone(),
+            })
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    // 6. Construir el XML y guardarlo como borrador
+    let xml = build_cfdi_xml(&CfdiInput {
+        issuer: &issuer,
+        receiver: &receiver,
+        date: Local::now().format("%Y-%m-%dT%H:%M:%S").to_string(),
+        payment_form: payment_form.clone(),
+        payment_method,
+        concepts,
+    });
+
+    let invoice = invoices::ActiveModel {
+        sale_id: Set(detail.sale.id.clone()),
+        receiver_rfc: Set(receiver.rfc),
+        receiver_name: Set(receiver.name),
+        receiver_postal_code: Set(receiver.postal_code),
+        receiver_tax_regime: Set(receiver.tax_regime),
+        cfdi_use: Set(receiver.cfdi_use),
+        payment_form: Set(payment_form),
+        subtotal: Set(detail.sale.subtotal),
+        total: Set(detail.sale.total),
+        status: Set(STATUS_DRAFT.to_string()),
+        xml: Set(xml),
+        created_by: Set(session.user_id.clone()),
+        updated_by: Set(session.user_id.clone()),
+        ..Default::default()
+    };
+
+    let inserted = invoice
+        .insert(&txn)
+        .await
+        .map_err(|e| format!("Error al registrar la factura: {:?}", e))?;
+
+    txn.commit()
+        .await
+        .map_err(|_| "Error al confirmar la transacción")?;
+
+    Ok(Invoice::from(inserted))
+}
+
+/// Timbra una factura en borrador con el PAC configurado
+#[tauri::command]
+pub async fn stamp_invoice(
+    state: tauri::State<'_, AppState>,
+    invoice_id: i32,
+) -> Result<Invoice, String> {
+    let session = require_permission(&state, "invoices.create")?;
+    let db = &state.database;
+
+    let txn = db
+        .begin()
+        .await
+        .map_err(|_| "Error al iniciar transacción")?;
+
+    // El borrador queda bloqueado mientras el PAC timbra para no timbrarlo dos veces
+    let invoice = Invoices::find_by_id(invoice_id)
+        .lock_exclusive()
+        .one(&txn)
+        .await
+        .map_err(|_| DB_ERROR)?
+        .ok_or("Factura no encontrada")?;
+
+    if invoice.status != STATUS_DRAFT {
+        return Err("La factura ya fue timbrada".to_string());
+    }
+
+    let result = configured_pac()?.stamp(&invoice.xml).await?;
+
+    let mut active_model: invoices::ActiveModel = invoice.into();
+    active_model.status = Set(STATUS_STAMPED.to_string());
+    active_model.uuid = Set(Some(result.uuid));
+    active_model.stamped_at = Set(Some(result.stamped_at));
+    active_model.xml = Set(result.xml);
+    active_model.updated_by = Set(session.user_id);
+
+    let updated = active_model
+        .update(&txn)
+        .await
+        .map_err(|e| format!("Error al guardar el timbrado: {:?}", e))?;
+
+    txn.commit()
+        .await
+        .map_err(|_| "Error al confirmar la transacción")?;
+
+    Ok(Invoice::from(updated))
+}
+
+/// Obtiene las facturas registradas de una venta
+#[tauri::command]
+pub async fn get_sale_invoices(
+    state: tauri::State<'_, AppState>,
+    sale_id: String,
+) -> Result<Vec<Invoice>, String> {
+    require_permission(&state, "invoices.view")?;
+    let db = &state.database;
+
+    let invoices_list = Invoices::find()
+        .filter(invoices::Column::SaleId.eq(sale_id))
+        .order_by_desc(invoices::Column::CreatedAt)
+        .all(db)
+        .await
+        .map_err(|_| DB_ERROR)?;
+
+    Ok(invoices_list.into_iter().map(Invoice::from).collect())
+}
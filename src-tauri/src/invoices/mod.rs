@@ -0,0 +1,6 @@
+pub mod cfdi;
+pub mod handlers;
+pub mod pac;
+pub mod structs;
+
+pub use handlers as InvoicesHandler;
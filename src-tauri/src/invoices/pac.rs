@@ -0,0 +1,78 @@
+//! Proveedores Autorizados de Certificación (PAC) para timbrar CFDI
+
+use std::env;
+use std::future::Future;
+
+use chrono::{DateTime, FixedOffset, Local};
+
+/// Resultado del timbrado
+#[derive(Debug, Clone)]
+pub struct StampResult {
+    pub uuid: String,
+    pub stamped_at: DateTime<FixedOffset>,
+    pub xml: String,
+}
+
+/// Interfaz que debe implementar cada PAC
+pub trait PacProvider {
+    fn stamp(&self, xml: &str) -> impl Future<Output = Result<StampResult, String>> + Send;
+}
+
+/// PAC local para pruebas: agrega un TimbreFiscalDigital ficticio sin contactar al SAT
+#[derive(Debug, Default)]
+pub struct MockPac;
+
+impl PacProvider for MockPac {
+    async fn stamp(&self, xml: &str) -> Result<StampResult, String> {
+        let closing_tag = "</cfdi:Comprobante>";
+        let position = xml
+            .rfind(closing_tag)
+            .ok_or("El XML no es un comprobante válido")?;
+
+        let uuid = uuid::Uuid::new_v4().to_string().to_uppercase();
+        let stamped_at = Local::now().fixed_offset();
+
+        let complement = format!(
+            concat!(
+                "  <cfdi:Complemento>\n",
+                r#"    <tfd:TimbreFiscalDigital xmlns:tfd="http://www.sat.gob.mx/TimbreFiscalDigital" "#,
+                r#"Version="1.1" UUID="{}" FechaTimbrado="{}" RfcProvCertif="AAA010101AAA" "#,
+                r#"SelloCFD="" NoCertificadoSAT="00000000000000000000" SelloSAT=""/>"#,
+                "\n  </cfdi:Complemento>\n"
+            ),
+            uuid,
+            stamped_at.format("%Y-%m-%dT%H:%M:%S"),
+        );
+
+        let mut stamped_xml = xml.to_string();
+        stamped_xml.insert_str(position, &complement);
+
+        Ok(StampResult {
+            uuid,
+            stamped_at,
+            xml: stamped_xml,
+        })
+    }
+}
+
+/// PAC seleccionado en la configuración; cada proveedor real se agrega como variante
+#[derive(Debug)]
+pub enum ConfiguredPac {
+    Mock(MockPac),
+}
+
+impl PacProvider for ConfiguredPac {
+    async fn stamp(&self, xml: &str) -> Result<StampResult, String> {
+        match self {
+            Self::Mock(pac) => pac.stamp(xml).await,
+        }
+    }
+}
+
+/// Obtiene el PAC configurado en PAC_PROVIDER (por defecto "mock")
+pub fn configured_pac() -> Result<ConfiguredPac, String> {
+    match env::var("PAC_PROVIDER").as_deref() {
+        Ok("mock") | Err(_) => Ok(ConfiguredPac::Mock(MockPac)),
+        Ok(other) => Err(format!("El PAC '{}' no está soportado", other)),
+    }
+}
@@ -0,0 +1,92 @@
+use crate::entities::invoices;
+use sea_orm::prelude::Decimal;
+use serde::{Deserialize, Serialize};
+use std::env;
+
+/// Estados de una factura
+pub const STATUS_DRAFT: &str = "draft";
+pub const STATUS_STAMPED: &str = "stamped";
+
+/// Datos fiscales del receptor (cliente)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct InvoiceReceiver {
+    pub rfc: String,
+    pub name: String,
+    pub postal_code: String, // DomicilioFiscalReceptor
+    pub tax_regime: String,  // RegimenFiscalReceptor, ej: "616"
+    pub cfdi_use: String,    // UsoCFDI, ej: "G03"
+}
+
+/// Request para facturar una venta
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CreateInvoiceRequest {
+    pub sale_id: String,
+    pub receiver: Option<InvoiceReceiver>, // None = usar los datos fiscales del cliente de la venta
+}
+
+/// Factura (para lectura/respuesta)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Invoice {
+    pub id: i32,
+    pub sale_id: String,
+    pub receiver_rfc: String,
+    pub receiver_name: String,
+    pub cfdi_use: String,
+    pub payment_form: String,
+    pub subtotal: Decimal,
+    pub total: Decimal,
+    pub status: String,
+    pub xml: String,
+    pub uuid: Option<String>,
+    pub stamped_at: Option<String>,
+    pub created_at: String,
+    pub created_by: String,
+}
+
+impl From<invoices::Model> for Invoice {
+    fn from(value: invoices::Model) -> Self {
+        Self {
+            id: value.id,
+            sale_id: value.sale_id,
+            receiver_rfc: value.receiver_rfc,
+            receiver_name: value.receiver_name,
+            cfdi_use: value.cfdi_use,
+            payment_form: value.payment_form,
+            subtotal: value.subtotal,
+            total: value.total,
+            status: value.status,
+            xml: value.xml,
+            uuid: value.uuid,
+            stamped_at: value.stamped_at.map(|date| date.to_string()),
+            created_at: value.created_at.to_string(),
+            created_by: value.created_by,
+        }
+    }
+}
+
+/// Datos fiscales del emisor, configurables en el archivo .env
+#[derive(Debug, Clone)]
+pub struct IssuerInfo {
+    pub rfc: String,
+    pub name: String,
+    pub tax_regime: String,
+    pub postal_code: String, // LugarExpedicion
+}
+
+impl IssuerInfo {
+    pub fn from_env() -> Result<Self, String> {
+        let required = |key: &str| {
+            env::var(key)
+                .ok()
+                .filter(|value| !value.trim().is_empty())
+                .ok_or(format!("Falta configurar {} para facturar", key))
+        };
+
+        Ok(Self {
+            rfc: required("ISSUER_RFC")?,
+            name: required("ISSUER_NAME")?,
+            tax_regime: required("ISSUER_TAX_REGIME")?,
+            postal_code: required("ISSUER_POSTAL_CODE")?,
+        })
+    }
+}
@@ -0,0 +1,385 @@
+use std::collections::HashMap;
+
+use chrono::{Local, Utc};
+use sea_orm::{
+    prelude::Decimal, ActiveModelTrait, ActiveValue::Set, ColumnTrait, ConnectionTrait,
+    EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, TransactionTrait,
+};
+
+use super::structs::{
+    CancelLayawayRequest, CancelLayawayResponse, Layaway, LayawayConfig, LayawayFilter,
+    LayawayListResponse, LayawayPaymentRequest, LayawayPaymentResponse, LAYAWAY_CANCELLED,
+    LAYAWAY_COMPLETED, LAYAWAY_PENDING,
+};
+use crate::cash_sessions::CashSessionsHandler::require_open_session;
+use crate::customer_credit::structs::TENDER_CREDIT;
+use crate::entities::{
+    customers,
+    prelude::{Customers, SalePayments, Sales},
+    refunds, sale_payments, sales,
+};
+use crate::gift_cards::structs::TENDER_GIFT_CARD;
+use crate::gift_cards::GiftCardsHandler::refund_to_gift_card;
+use crate::loyalty::structs::TENDER_POINTS;
+use crate::loyalty::LoyaltyHandler::accrue_sale_points;
+use crate::sales::structs::{CreateSaleRequest, CreateSaleResponse, Sale};
+use crate::sales::SalesHandler::{
+    insert_payments, load_tenders, register_sale, resolve_refund_method, restore_sale_inventory,
+    tender_change,
+};
+use crate::sessions::require_permission;
+use crate::AppState;
+
+const DB_ERROR: &str = "Error en la base de datos";
+
+/// Suma de los abonos registrados por venta
+async fn paid_by_sale<C: ConnectionTrait>(
+    db: &C,
+    sale_ids: Vec<String>,
+) -> Result<HashMap<String, Decimal>, String> {
+    let mut paid: HashMap<String, Decimal> = HashMap::new();
+    for payment in SalePayments::find()
+        .filter(sale_payments::Column::SaleId.is_in(sale_ids))
+        .all(db)
+        .await
+        .map_err(|_| DB_ERROR)?
+    {
+        *paid.entry(payment.sale_id).or_insert(Decimal::ZERO) += payment.amount;
+    }
+    Ok(paid)
+}
+
+/// Bloquea un apartado pendiente para registrar abonos o cancelarlo
+async fn lock_pending_layaway<C: ConnectionTrait>(
+    db: &C,
+    sale_id: &str,
+) -> Result<sales::Model, String> {
+    let sale = Sales::find_by_id(sale_id)
+        .lock_exclusive()
+        .one(db)
+        .await
+        .map_err(|_| DB_ERROR)?
+        .ok_or("Apartado no encontrado")?;
+
+    match sale.layaway_status.as_deref() {
+        Some(LAYAWAY_PENDING) => Ok(sale),
+        Some(LAYAWAY_COMPLETED) => Err("El apartado ya fue liquidado".to_string()),
+        Some(_) => Err("El apartado está cancelado".to_string()),
+        None => Err("La venta no es un apartado".to_string()),
+    }
+}
+
+/// Registra un apartado: la venta queda pendiente con el anticipo y reserva el inventario
+#[tauri::command]
+pub async fn create_layaway(
+    state: tauri::State<'_, AppState>,
+    request: CreateSaleRequest,
+) -> Result<CreateSaleResponse, String> {
+    require_permission(&state, "sales.layaway")?;
+    register_sale(&state, request, None, true).await
+}
+
+/// Obtiene una página de apartados con lo abonado y el saldo pendiente
+#[tauri::command]
+pub async fn get_layaways(
+    state: tauri::State<'_, AppState>,
+    filters: LayawayFilter,
+) -> Result<LayawayListResponse, String> {
+    require_permission(&state, "sales.view")?;
+    let db = &state.database;
+
+    let today = Local::now().date_naive();
+    let mut query = Sales::find().filter(sales::Column::LayawayStatus.is_not_null());
+
+    if let Some(status) = filters.status {
+        query = query.filter(sales::Column::LayawayStatus.eq(status));
+    }
+    if let Some(customer_id) = filters.customer_id {
+        query = query.filter(sales::Column::CustomerId.eq(customer_id));
+    }
+    if filters.overdue == Some(true) {
+        query = query
+            .filter(sales::Column::LayawayStatus.eq(LAYAWAY_PENDING))
+            .filter(sales::Column::LayawayDueDate.lt(today));
+    }
+
+    // Contar total de items para paginación
+    let total_items = query.clone().count(db).await.map_err(|_| DB_ERROR)?;
+
+    let total_pages = if filters.limit > 0 {
+        (total_items as f64 / filters.limit as f64).ceil() as u64
+    } else {
+        1
+    };
+
+    let offset = (filters.page.saturating_sub(1)) * filters.limit;
+    let sales_list = query
+        .order_by_asc(sales::Column::LayawayDueDate)
+        .order_by_desc(sales::Column::CreatedAt)
+        .offset(offset)
+        .limit(filters.limit)
+        .all(db)
+        .await
+        .map_err(|_| DB_ERROR)?;
+
+    let paid = paid_by_sale(db, sales_list.iter().map(|sale| sale.id.clone()).collect()).await?;
+
+    let customer_ids: Vec<i32> = sales_list
+        .iter()
+        .filter_map(|sale| sale.customer_id)
+        .collect();
+    let customer_names: HashMap<i32, String> = Customers::find()
+        .filter(customers::Column::Id.is_in(customer_ids))
+        .all(db)
+        .await
+        .map_err(|_| DB_ERROR)?
+        .into_iter()
+        .map(|customer| (customer.id, customer.name))
+        .collect();
+
+    Ok(LayawayListResponse {
+        layaways: sales_list
+            .into_iter()
+            .map(|sale| {
+                let amount_paid = paid.get(&sale.id).copied().unwrap_or(Decimal::ZERO);
+                let pending = sale.layaway_status.as_deref() == Some(LAYAWAY_PENDING);
+                Layaway {
+                    customer_name: sale
+                        .customer_id
+                        .and_then(|id| customer_names.get(&id).cloned()),
+                    amount_paid,
+                    balance_due: if pending {
+                        sale.total - amount_paid
+                    } else {
+                        Decimal::ZERO
+                    },
+                    is_overdue: pending && sale.layaway_due_date.is_some_and(|due| due < today),
+                    sale: Sale::from(sale),
+                }
+            })
+            .collect(),
+        total_pages,
+        total_items,
+    })
+}
+
+/// Registra un abono a un apartado; al cubrir el saldo la venta se completa
+#[tauri::command]
+pub async fn add_layaway_payment(
+    state: tauri::State<'_, AppState>,
+    request: LayawayPaymentRequest,
+) -> Result<LayawayPaymentResponse, String> {
+    // 1. Validar permiso
+    let session = require_permission(&state, "sales.layaway")?;
+    let db = &state.database;
+
+    // 2. Iniciar transacción; el abono se recibe en el turno abierto del cajero
+    let txn = db
+        .begin()
+        .await
+        .map_err(|_| "Error al iniciar transacción")?;
+
+    let cash_session = require_open_session(&txn, &session.user_id).await?;
+
+    // 3. Bloquear el apartado para serializar abonos y cancelaciones
+    let sale = lock_pending_layaway(&txn, &request.sale_id).await?;
+
+    // 4. Validar formas de pago y calcular el saldo
+    let tenders = load_tenders(&txn, &request.payments).await?;
+    if tenders
+        .iter()
+        .any(|(method, _)| method.tender == TENDER_CREDIT)
+    {
+        return Err("Los abonos de un apartado no se pueden pagar a crédito".to_string());
+    }
+    if tenders
+        .iter()
+        .any(|(method, _)| method.tender == TENDER_POINTS)
+    {
+        return Err("Los abonos de un apartado no se pueden pagar con puntos".to_string());
+    }
+    let paid: Decimal = tenders.iter().map(|(_, amount)| *amount).sum();
+
+    let previously_paid = paid_by_sale(&txn, vec![sale.id.clone()])
+        .await?
+        .remove(&sale.id)
+        .unwrap_or(Decimal::ZERO);
+    let balance = sale.total - previously_paid;
+
+    let change = tender_change(&tenders, balance)?;
+    let applied = paid - change;
+    let balance_due = balance - applied;
+
+    // 5. Registrar los pagos
+    insert_payments(
+        &txn,
+        &sale.id,
+        &request.payments,
+        &tenders,
+        change,
+        cash_session.id,
+        &session.user_id,
+    )
+    .await?;
+
+    // 6. Completar la venta si el abono liquida el saldo
+    let completed = balance_due <= Decimal::ZERO;
+    let sale_id = sale.id.clone();
+    let mut sale_active: sales::ActiveModel = sale.clone().into();
+    sale_active.change_amount = Set(sale.change_amount + change);
+    sale_active.updated_by = Set(session.user_id.clone());
+    if completed {
+        sale_active.layaway_status = Set(Some(LAYAWAY_COMPLETED.to_string()));
+        sale_active.completed_at = Set(Some(Utc::now().fixed_offset()));
+    }
+
+    let updated_sale = sale_active
+        .update(&txn)
+        .await
+        .map_err(|e| format!("Error al actualizar el apartado: {:?}", e))?;
+
+    // El cliente acumula los puntos del apartado al liquidarlo
+    let points_earned = if completed {
+        accrue_sale_points(&txn, &updated_sale, &session.user_id).await?
+    } else {
+        0
+    };
+
+    // 7. Commit transacción
+    txn.commit()
+        .await
+        .map_err(|_| "Error al confirmar la transacción")?;
+
+    Ok(LayawayPaymentResponse {
+        sale_id,
+        amount_paid: paid,
+        change,
+        total_paid: previously_paid + applied,
+        balance_due: balance_due.max(Decimal::ZERO),
+        completed,
+        points_earned,
+    })
+}
+
+/// Cancela un apartado pendiente: regresa el inventario y reembolsa lo abonado,
+/// menos la penalización si ya venció el plazo
+#[tauri::command]
+pub async fn cancel_layaway(
+    state: tauri::State<'_, AppState>,
+    request: CancelLayawayRequest,
+) -> Result<CancelLayawayResponse, String> {
+    // 1. Validar permiso
+    let session = require_permission(&state, "sales.cancel")?;
+    let db = &state.database;
+
+    let reason = request.reason.trim();
+    if reason.is_empty() {
+        return Err("Debe indicar el motivo de la cancelación".to_string());
+    }
+
+    // 2. Iniciar transacción y bloquear el apartado
+    let txn = db
+        .begin()
+        .await
+        .map_err(|_| "Error al iniciar transacción")?;
+
+    let sale = lock_pending_layaway(&txn, &request.sale_id).await?;
+
+    // 3. Calcular la penalización; solo aplica después de la fecha límite
+    let paid = paid_by_sale(&txn, vec![sale.id.clone()])
+        .await?
+        .remove(&sale.id)
+        .unwrap_or(Decimal::ZERO);
+
+    let config = LayawayConfig::from_env();
+    let expired = sale
+        .layaway_due_date
+        .is_some_and(|due| due < Local::now().date_naive());
+    let penalty_amount = if expired {
+        (sale.total * config.penalty_percent / Decimal::from(100))
+            .round_dp(2)
+            .min(paid)
+    } else {
+        Decimal::ZERO
+    };
+    let refund_amount = paid - penalty_amount;
+
+    // 4. Reembolsar lo abonado menos la penalización
+    let (refund_id, gift_card_code) = if refund_amount > Decimal::ZERO {
+        let (refund_method, cash_session) =
+            resolve_refund_method(&txn, request.payment_method_id, &session.user_id).await?;
+
+        // Un apartado no se abona a crédito ni con puntos, así que tampoco se reembolsa con ellos
+        if refund_method.tender == TENDER_CREDIT {
+            return Err("El reembolso de un apartado no se puede abonar a crédito".to_string());
+        }
+        if refund_method.tender == TENDER_POINTS {
+            return Err("El reembolso de un apartado no se puede hacer en puntos".to_string());
+        }
+
+        let refund = refunds::ActiveModel {
+            sale_id: Set(sale.id.clone()),
+            amount: Set(refund_amount),
+            reason: Set(format!("Cancelación de apartado: {}", reason)),
+            payment_method_id: Set(Some(refund_method.id)),
+            cash_session_id: Set(cash_session.map(|cash_session| cash_session.id)),
+            created_by: Set(session.user_id.clone()),
+            updated_by: Set(session.user_id.clone()),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await
+        .map_err(|e| format!("Error al registrar el reembolso: {:?}", e))?;
+
+        // Un reembolso en tarjeta de regalo queda como saldo a favor en un vale
+        let gift_card_code = if refund_method.tender == TENDER_GIFT_CARD {
+            let card = refund_to_gift_card(
+                &txn,
+                request.gift_card_code.as_deref(),
+                refund_amount,
+                &sale.id,
+                refund.id,
+                &session.user_id,
+            )
+            .await?;
+            Some(card.code)
+        } else {
+            None
+        };
+
+        (Some(refund.id), gift_card_code)
+    } else {
+        (None, None)
+    };
+
+    // 5. Liberar el inventario reservado y el cupón
+    restore_sale_inventory(&txn, &sale, &session.user_id).await?;
+
+    // 6. Marcar el apartado como cancelado
+    let mut sale_active: sales::ActiveModel = sale.into();
+    sale_active.status = Set(false);
+    sale_active.layaway_status = Set(Some(LAYAWAY_CANCELLED.to_string()));
+    sale_active.layaway_penalty_amount = Set(penalty_amount);
+    sale_active.cancelled_by = Set(Some(session.user_id.clone()));
+    sale_active.cancelled_at = Set(Some(Utc::now().fixed_offset()));
+    sale_active.cancellation_reason = Set(Some(reason.to_string()));
+    sale_active.updated_by = Set(session.user_id.clone());
+
+    let cancelled_sale = sale_active
+        .update(&txn)
+        .await
+        .map_err(|e| format!("Error al cancelar el apartado: {:?}", e))?;
+
+    // 7. Commit transacción
+    txn.commit()
+        .await
+        .map_err(|_| "Error al confirmar la transacción")?;
+
+    Ok(CancelLayawayResponse {
+        sale: Sale::from(cancelled_sale),
+        penalty_amount,
+        refund_amount,
+        refund_id,
+        gift_card_code,
+    })
+}
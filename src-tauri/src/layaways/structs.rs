@@ -0,0 +1,111 @@
+use sea_orm::prelude::Decimal;
+use serde::{Deserialize, Serialize};
+use std::env;
+
+use crate::sales::structs::{Sale, SalePaymentRequest};
+
+pub const LAYAWAY_PENDING: &str = "pending";
+pub const LAYAWAY_COMPLETED: &str = "completed";
+pub const LAYAWAY_CANCELLED: &str = "cancelled";
+
+/// Condiciones de los apartados, tomadas del archivo .env
+///
+/// - `LAYAWAY_MIN_DOWN_PAYMENT_PERCENT`: anticipo mínimo sobre el total (por defecto 20)
+/// - `LAYAWAY_TERM_DAYS`: días para liquidar el apartado (por defecto 30)
+/// - `LAYAWAY_PENALTY_PERCENT`: penalización sobre el total al cancelar un apartado vencido (por defecto 10)
+#[derive(Debug, Clone)]
+pub struct LayawayConfig {
+    pub min_down_payment_percent: Decimal,
+    pub term_days: i64,
+    pub penalty_percent: Decimal,
+}
+
+impl LayawayConfig {
+    pub fn from_env() -> Self {
+        let percent = |key: &str, default: i64| {
+            env::var(key)
+                .ok()
+                .and_then(|value| value.trim().parse::<Decimal>().ok())
+                .filter(|value| *value >= Decimal::ZERO && *value <= Decimal::from(100))
+                .unwrap_or(Decimal::from(default))
+        };
+
+        let term_days = env::var("LAYAWAY_TERM_DAYS")
+            .ok()
+            .and_then(|value| value.trim().parse::<i64>().ok())
+            .filter(|days| *days > 0)
+            .unwrap_or(30);
+
+        Self {
+            min_down_payment_percent: percent("LAYAWAY_MIN_DOWN_PAYMENT_PERCENT", 20),
+            term_days,
+            penalty_percent: percent("LAYAWAY_PENALTY_PERCENT", 10),
+        }
+    }
+}
+
+/// Apartado con su saldo (para listados)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Layaway {
+    pub sale: Sale,
+    pub customer_name: Option<String>,
+    pub amount_paid: Decimal,
+    pub balance_due: Decimal,
+    pub is_overdue: bool, // Pendiente con fecha límite vencida
+}
+
+/// Filtros para obtener apartados
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct LayawayFilter {
+    pub status: Option<String>, // pending, completed, cancelled
+    pub customer_id: Option<i32>,
+    pub overdue: Option<bool>, // Solo pendientes vencidos
+    pub page: u64,
+    pub limit: u64,
+}
+
+/// Respuesta paginada de apartados
+#[derive(Serialize, Deserialize, Debug)]
+pub struct LayawayListResponse {
+    pub layaways: Vec<Layaway>,
+    pub total_pages: u64,
+    pub total_items: u64,
+}
+
+// Request para registrar un abono a un apartado
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct LayawayPaymentRequest {
+    pub sale_id: String,
+    pub payments: Vec<SalePaymentRequest>, // En efectivo puede exceder el saldo para calcular el cambio
+}
+
+// Respuesta de abono registrado
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct LayawayPaymentResponse {
+    pub sale_id: String,
+    pub amount_paid: Decimal, // Importe entregado en este abono
+    pub change: Decimal,
+    pub total_paid: Decimal, // Acumulado de todos los abonos
+    pub balance_due: Decimal,
+    pub completed: bool,    // El abono liquidó el apartado
+    pub points_earned: i32, // Puntos acumulados al liquidar
+}
+
+// Request para cancelar un apartado pendiente
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CancelLayawayRequest {
+    pub sale_id: String,
+    pub reason: String,
+    pub payment_method_id: Option<i32>, // Forma de reembolso; efectivo si es None
+    pub gift_card_code: Option<String>, // Tarjeta a abonar; sin código se emite un vale nuevo
+}
+
+// Respuesta de apartado cancelado
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CancelLayawayResponse {
+    pub sale: Sale,
+    pub penalty_amount: Decimal, // Retenido por cancelar después del vencimiento
+    pub refund_amount: Decimal,
+    pub refund_id: Option<i32>, // Sin devolución si la penalización absorbe lo abonado
+    pub gift_card_code: Option<String>, // Vale o tarjeta donde quedó el saldo a favor
+}
@@ -3,28 +3,89 @@ use std::{env, sync::Mutex};
 use tauri::Manager;
 mod db;
 use sea_orm::DatabaseConnection;
+mod cash_sessions;
 mod categories;
+mod coupons;
+mod customer_credit;
+mod customers;
 mod entities;
+mod gift_cards;
+mod invoices;
+mod layaways;
+mod loyalty;
+mod parked_sales;
+mod printer;
 mod products;
+mod promotions;
+mod quotes;
+mod receipts;
 mod reports;
 mod sales;
 mod sessions;
 mod users;
 mod utils;
 
+use cash_sessions::CashSessionsHandler::{
+    close_cash_session, create_cash_movement, get_cash_movements, get_cash_session_report,
+    get_cash_sessions, get_current_cash_session, open_cash_session,
+};
 use categories::handlers::{
     create_category, delete_category, get_all_categories, get_category_by_id, hard_delete_category,
     update_category,
 };
-use products::ProductHandlers::{create_product, delete_product, get_products, update_product};
+use coupons::CouponsHandler::{
+    create_coupon_batch, deactivate_coupon_batch, get_coupon_batch, get_coupon_batches,
+    validate_coupon,
+};
+use customer_credit::CustomerCreditHandler::{
+    get_credit_account, get_credit_ledger, get_credit_statement, register_credit_payment,
+    set_credit_limit,
+};
+use customers::CustomersHandler::{
+    create_customer, delete_customer, get_customer_by_id, get_customers, update_customer,
+};
+use gift_cards::GiftCardsHandler::{
+    create_gift_card, deactivate_gift_card, get_gift_card, get_gift_card_detail,
+    get_gift_card_liability_report, get_gift_cards,
+};
+use invoices::InvoicesHandler::{create_invoice, get_sale_invoices, stamp_invoice};
+use layaways::LayawaysHandler::{
+    add_layaway_payment, cancel_layaway, create_layaway, get_layaways,
+};
+use loyalty::LoyaltyHandler::{
+    get_loyalty_account, get_loyalty_config, get_loyalty_entries, update_loyalty_config,
+};
+use parked_sales::ParkedSalesHandler::{
+    discard_parked_sale, list_parked_sales, park_sale, resume_parked_sale,
+};
+use printer::PrinterHandler::{
+    open_cash_drawer, print_credit_statement, print_quote, print_sale_receipt, print_test_page,
+};
+use products::ProductHandlers::{
+    create_product, delete_product, get_product_by_code, get_products, update_product,
+};
+use promotions::PromotionsHandler::{
+    create_promotion, delete_promotion, get_promotions, update_promotion,
+};
+use quotes::QuotesHandler::{convert_quote_to_sale, create_quote, get_quote_detail, get_quotes};
+use receipts::ReceiptsHandler::{
+    get_credit_statement_receipt, get_quote_receipt, get_sale_receipt,
+};
 use reports::ReportsHandler::{
-    get_category_report, get_dashboard_report, get_payment_method_report, get_product_report,
-    get_refunds_report, get_sales_over_time_report,
+    get_category_report, get_dashboard_report, get_discounts_report, get_payment_method_report,
+    get_product_report, get_promotions_report, get_refunds_report, get_sales_over_time_report,
+    get_tax_summary_report,
+};
+use sales::SalesHandler::{
+    cancel_sale, create_refund, create_sale, get_payment_methods, get_sale_detail, get_sales,
+    preview_sale,
 };
-use sales::SalesHandler::{create_sale, get_payment_methods, get_sales};
 use sessions::SessionHandler::{get_session, login, logout};
 use sessions::SessionsStructs::Session;
-use users::UsersHandler::{create_user, get_profiles, get_users, toggle_user_status, update_user};
+use users::UsersHandler::{
+    create_user, get_profiles, get_users, toggle_user_status, update_profile_discount_limit,
+    update_user,
+};
 
 #[derive(Debug)]
 struct AppState {
@@ -57,6 +118,7 @@ pub async fn run() {
             create_product,
             delete_product,
             get_products,
+            get_product_by_code,
             update_product,
             get_all_categories,
             get_category_by_id,
@@ -65,8 +127,82 @@ pub async fn run() {
             delete_category,
             hard_delete_category,
             get_sales,
+            get_sale_detail,
             create_sale,
+            preview_sale,
             get_payment_methods,
+            create_refund,
+            get_sale_receipt,
+            print_sale_receipt,
+            open_cash_drawer,
+            print_test_page,
+            cancel_sale,
+            // Cash sessions
+            open_cash_session,
+            get_current_cash_session,
+            close_cash_session,
+            get_cash_sessions,
+            get_cash_session_report,
+            create_cash_movement,
+            get_cash_movements,
+            // Layaways
+            create_layaway,
+            get_layaways,
+            add_layaway_payment,
+            cancel_layaway,
+            // Loyalty
+            get_loyalty_config,
+            update_loyalty_config,
+            get_loyalty_account,
+            get_loyalty_entries,
+            // Parked sales
+            park_sale,
+            list_parked_sales,
+            resume_parked_sale,
+            discard_parked_sale,
+            // Promotions
+            get_promotions,
+            create_promotion,
+            update_promotion,
+            delete_promotion,
+            // Quotes
+            get_quotes,
+            get_quote_detail,
+            create_quote,
+            convert_quote_to_sale,
+            get_quote_receipt,
+            print_quote,
+            // Coupons
+            get_coupon_batches,
+            get_coupon_batch,
+            create_coupon_batch,
+            deactivate_coupon_batch,
+            validate_coupon,
+            // Customers
+            get_customers,
+            get_customer_by_id,
+            create_customer,
+            update_customer,
+            delete_customer,
+            // Customer credit
+            get_credit_account,
+            get_credit_ledger,
+            register_credit_payment,
+            set_credit_limit,
+            get_credit_statement,
+            get_credit_statement_receipt,
+            print_credit_statement,
+            // Gift cards
+            get_gift_card,
+            get_gift_cards,
+            get_gift_card_detail,
+            create_gift_card,
+            deactivate_gift_card,
+            get_gift_card_liability_report,
+            // Invoices
+            create_invoice,
+            stamp_invoice,
+            get_sale_invoices,
             // Reports
             get_dashboard_report,
             get_sales_over_time_report,
@@ -74,10 +210,14 @@ pub async fn run() {
             get_category_report,
             get_payment_method_report,
             get_refunds_report,
+            get_discounts_report,
+            get_promotions_report,
+            get_tax_summary_report,
             // Users
             get_users,
             toggle_user_status,
             get_profiles,
+            update_profile_discount_limit,
             create_user,
             update_user,
         ])
@@ -0,0 +1,551 @@
+use std::collections::HashMap;
+
+use chrono::{NaiveDate, Utc};
+use sea_orm::{
+    prelude::Decimal, ActiveModelTrait, ActiveValue::Set, ColumnTrait, ConnectionTrait,
+    EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, TransactionTrait,
+};
+
+use super::structs::{
+    CategoryMultiplier, LoyaltyAccount, LoyaltyConfig, LoyaltyEntry, LoyaltyEntryFilter,
+    LoyaltyEntryListResponse, UpdateLoyaltyConfigRequest, ENTRY_EARN, ENTRY_REDEEM, ENTRY_REFUND,
+    ENTRY_REVERSAL, TENDER_POINTS,
+};
+use crate::entities::{
+    categories, customers, loyalty_entries, loyalty_settings, payment_methods,
+    prelude::{
+        Categories, Customers, LoyaltyEntries, LoyaltySettings, PaymentMethods, Products,
+        SaleDetails, SalePayments, Users,
+    },
+    sale_details, sale_payments, sales, users,
+};
+use crate::sessions::require_permission;
+use crate::AppState;
+
+const DB_ERROR: &str = "Error en la base de datos";
+
+/// Convierte un importe de puntos a entero, truncando la fracción
+fn to_points(value: Decimal) -> Result<i32, String> {
+    i32::try_from(value.trunc()).map_err(|_| "Cantidad de puntos fuera de rango".to_string())
+}
+
+/// Configuración vigente del programa de puntos
+pub async fn load_settings<C: ConnectionTrait>(db: &C) -> Result<loyalty_settings::Model, String> {
+    LoyaltySettings::find()
+        .order_by_asc(loyalty_settings::Column::Id)
+        .one(db)
+        .await
+        .map_err(|_| DB_ERROR)?
+        .ok_or("El programa de puntos no está configurado".to_string())
+}
+
+/// Registra un movimiento de puntos y actualiza el saldo del cliente.
+/// Bloquea al cliente para que los movimientos simultáneos no desfasen el saldo;
+/// un canje no puede exceder los puntos disponibles.
+pub async fn post_loyalty_entry<C: ConnectionTrait>(
+    db: &C,
+    customer_id: i32,
+    entry_type: &str,
+    points: i32,
+    sale_id: Option<&str>,
+    notes: Option<String>,
+    user_id: &str,
+) -> Result<loyalty_entries::Model, String> {
+    let customer = Customers::find_by_id(customer_id)
+        .lock_exclusive()
+        .one(db)
+        .await
+        .map_err(|_| DB_ERROR)?
+        .ok_or(format!("Cliente {} no encontrado", customer_id))?;
+
+    let balance_after = customer.loyalty_points + points;
+    if entry_type == ENTRY_REDEEM && balance_after < 0 {
+        return Err(format!(
+            "Puntos insuficientes para {}. Disponibles: {}",
+            customer.name,
+            customer.loyalty_points.max(0)
+        ));
+    }
+
+    let entry = loyalty_entries::ActiveModel {
+        customer_id: Set(customer.id),
+        entry_type: Set(entry_type.to_string()),
+        points: Set(points),
+        balance_after: Set(balance_after),
+        sale_id: Set(sale_id.map(|sale_id| sale_id.to_string())),
+        notes: Set(notes),
+        created_by: Set(user_id.to_string()),
+        ..Default::default()
+    }
+    .insert(db)
+    .await
+    .map_err(|e| format!("Error al registrar el movimiento de puntos: {:?}", e))?;
+
+    let mut active_model: customers::ActiveModel = customer.into();
+    active_model.loyalty_points = Set(balance_after);
+    active_model.updated_by = Set(user_id.to_string());
+    active_model
+        .update(db)
+        .await
+        .map_err(|e| format!("Error al actualizar los puntos del cliente: {:?}", e))?;
+
+    Ok(entry)
+}
+
+/// Descuenta los puntos necesarios para cubrir un pago; la fracción se redondea hacia arriba
+pub async fn redeem_sale_points<C: ConnectionTrait>(
+    db: &C,
+    customer_id: i32,
+    amount: Decimal,
+    sale_id: &str,
+    user_id: &str,
+) -> Result<i32, String> {
+    let settings = load_settings(db).await?;
+    if !settings.is_active {
+        return Err("El programa de puntos no está activo".to_string());
+    }
+
+    let points = to_points((amount / settings.point_value).ceil())?;
+    post_loyalty_entry(
+        db,
+        customer_id,
+        ENTRY_REDEEM,
+        -points,
+        Some(sale_id),
+        None,
+        user_id,
+    )
+    .await?;
+    Ok(points)
+}
+
+/// Acumula los puntos de una venta completada: tasa del programa por el multiplicador
+/// de la categoría de cada línea. Lo pagado con puntos no genera puntos.
+pub async fn accrue_sale_points<C: ConnectionTrait>(
+    db: &C,
+    sale: &sales::Model,
+    user_id: &str,
+) -> Result<i32, String> {
+    let Some(customer_id) = sale.customer_id else {
+        return Ok(0);
+    };
+    let settings = load_settings(db).await?;
+    if !settings.is_active || sale.total <= Decimal::ZERO {
+        return Ok(0);
+    }
+
+    // Proporción de la venta pagada con otras formas de pago
+    let points_paid: Decimal = SalePayments::find()
+        .inner_join(PaymentMethods)
+        .filter(sale_payments::Column::SaleId.eq(&sale.id))
+        .filter(payment_methods::Column::Tender.eq(TENDER_POINTS))
+        .all(db)
+        .await
+        .map_err(|_| DB_ERROR)?
+        .iter()
+        .map(|payment| payment.amount)
+        .sum();
+    let eligible = (sale.total - points_paid).max(Decimal::ZERO) / sale.total;
+
+    // Multiplicador de la categoría de cada producto vendido; las tarjetas de regalo
+    // no acumulan, los puntos se ganan cuando se canjean
+    let details: Vec<_> = SaleDetails::find()
+        .find_also_related(Products)
+        .filter(sale_details::Column::SaleId.eq(&sale.id))
+        .all(db)
+        .await
+        .map_err(|_| DB_ERROR)?
+        .into_iter()
+        .filter(|(_, product)| !product.as_ref().is_some_and(|product| product.is_gift_card))
+        .collect();
+
+    let category_ids: Vec<i32> = details
+        .iter()
+        .filter_map(|(_, product)| product.as_ref().and_then(|product| product.category_id))
+        .collect();
+    let multipliers: HashMap<i32, Decimal> = Categories::find()
+        .filter(categories::Column::Id.is_in(category_ids))
+        .all(db)
+        .await
+        .map_err(|_| DB_ERROR)?
+        .into_iter()
+        .map(|category| (category.id, category.points_multiplier))
+        .collect();
+
+    let weighted: Decimal = details
+        .iter()
+        .map(|(detail, product)| {
+            let multiplier = product
+                .as_ref()
+                .and_then(|product| product.category_id)
+                .and_then(|category_id| multipliers.get(&category_id).copied())
+                .unwrap_or(Decimal::ONE);
+            detail.total * multiplier
+        })
+        .sum();
+
+    let points = to_points(weighted * eligible * settings.earn_rate)?;
+    if points > 0 {
+        post_loyalty_entry(
+            db,
+            customer_id,
+            ENTRY_EARN,
+            points,
+            Some(&sale.id),
+            None,
+            user_id,
+        )
+        .await?;
+    }
+    Ok(points)
+}
+
+/// Suma de puntos por tipo de movimiento de una venta
+async fn sale_points_by_type<C: ConnectionTrait>(
+    db: &C,
+    sale_id: &str,
+) -> Result<HashMap<String, i32>, String> {
+    let mut totals: HashMap<String, i32> = HashMap::new();
+    for entry in LoyaltyEntries::find()
+        .filter(loyalty_entries::Column::SaleId.eq(sale_id))
+        .all(db)
+        .await
+        .map_err(|_| DB_ERROR)?
+    {
+        *totals.entry(entry.entry_type).or_insert(0) += entry.points;
+    }
+    Ok(totals)
+}
+
+/// Al cancelar una venta se retiran los puntos ganados y se devuelven los canjeados
+pub async fn reverse_sale_points<C: ConnectionTrait>(
+    db: &C,
+    sale: &sales::Model,
+    reason: &str,
+    user_id: &str,
+) -> Result<(), String> {
+    let Some(customer_id) = sale.customer_id else {
+        return Ok(());
+    };
+
+    let net: i32 = sale_points_by_type(db, &sale.id).await?.values().sum();
+    if net != 0 {
+        post_loyalty_entry(
+            db,
+            customer_id,
+            ENTRY_REVERSAL,
+            -net,
+            Some(&sale.id),
+            Some(reason.to_string()),
+            user_id,
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+/// En una devolución se retiran los puntos ganados en proporción al importe devuelto.
+/// Si se reembolsa en puntos, se devuelven los canjeados en la misma proporción.
+pub async fn refund_sale_points<C: ConnectionTrait>(
+    db: &C,
+    sale: &sales::Model,
+    amount: Decimal,
+    refund_in_points: bool,
+    reason: &str,
+    user_id: &str,
+) -> Result<(), String> {
+    let Some(customer_id) = sale.customer_id else {
+        if refund_in_points {
+            return Err("Solo se reembolsa en puntos a ventas con cliente".to_string());
+        }
+        return Ok(());
+    };
+    if sale.total <= Decimal::ZERO {
+        return Ok(());
+    }
+
+    let totals = sale_points_by_type(db, &sale.id).await?;
+    let total_of = |entry_type: &str| totals.get(entry_type).copied().unwrap_or(0);
+    let ratio = (amount / sale.total).min(Decimal::ONE);
+
+    // Puntos ganados que aún no se han retirado
+    let earned = total_of(ENTRY_EARN);
+    let pending = earned + total_of(ENTRY_REVERSAL);
+    let reversed = to_points((Decimal::from(earned) * ratio).round())?.min(pending);
+    if reversed > 0 {
+        post_loyalty_entry(
+            db,
+            customer_id,
+            ENTRY_REVERSAL,
+            -reversed,
+            Some(&sale.id),
+            Some(reason.to_string()),
+            user_id,
+        )
+        .await?;
+    }
+
+    if refund_in_points {
+        let redeemed = -total_of(ENTRY_REDEEM);
+        if redeemed <= 0 {
+            return Err(
+                "La venta no se pagó con puntos; elija otra forma de reembolso".to_string(),
+            );
+        }
+
+        // El reembolso en puntos no puede exceder lo pagado con puntos
+        let points_paid: Decimal = SalePayments::find()
+            .inner_join(PaymentMethods)
+            .filter(sale_payments::Column::SaleId.eq(&sale.id))
+            .filter(payment_methods::Column::Tender.eq(TENDER_POINTS))
+            .all(db)
+            .await
+            .map_err(|_| DB_ERROR)?
+            .iter()
+            .map(|payment| payment.amount)
+            .sum();
+        if points_paid <= Decimal::ZERO || amount > points_paid {
+            return Err(format!(
+                "El reembolso en puntos excede lo pagado con puntos ({})",
+                points_paid
+            ));
+        }
+
+        let available = redeemed - total_of(ENTRY_REFUND);
+        let returned =
+            to_points((Decimal::from(redeemed) * amount / points_paid).round())?.min(available);
+        if returned <= 0 {
+            return Err("Los puntos de la venta ya fueron reembolsados".to_string());
+        }
+
+        post_loyalty_entry(
+            db,
+            customer_id,
+            ENTRY_REFUND,
+            returned,
+            Some(&sale.id),
+            Some(reason.to_string()),
+            user_id,
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Configuración con los multiplicadores de categoría distintos de 1
+async fn load_config<C: ConnectionTrait>(db: &C) -> Result<LoyaltyConfig, String> {
+    let settings = load_settings(db).await?;
+
+    let category_multipliers = Categories::find()
+        .filter(categories::Column::PointsMultiplier.ne(Decimal::ONE))
+        .order_by_asc(categories::Column::Name)
+        .all(db)
+        .await
+        .map_err(|_| DB_ERROR)?
+        .into_iter()
+        .map(|category| CategoryMultiplier {
+            category_id: category.id,
+            category_name: category.name,
+            multiplier: category.points_multiplier,
+        })
+        .collect();
+
+    Ok(LoyaltyConfig::from_model(settings, category_multipliers))
+}
+
+/// Obtiene la configuración del programa de puntos
+#[tauri::command]
+pub async fn get_loyalty_config(
+    state: tauri::State<'_, AppState>,
+) -> Result<LoyaltyConfig, String> {
+    require_permission(&state, "sales.create")?;
+    load_config(&state.database).await
+}
+
+/// Actualiza las tasas de acumulación y canje y los multiplicadores por categoría
+#[tauri::command]
+pub async fn update_loyalty_config(
+    state: tauri::State<'_, AppState>,
+    request: UpdateLoyaltyConfigRequest,
+) -> Result<LoyaltyConfig, String> {
+    // 1. Validar permiso y valores
+    let session = require_permission(&state, "loyalty.manage")?;
+    let db = &state.database;
+
+    if request.earn_rate.is_some_and(|rate| rate < Decimal::ZERO) {
+        return Err("La tasa de acumulación no puede ser negativa".to_string());
+    }
+    if request
+        .point_value
+        .is_some_and(|value| value <= Decimal::ZERO)
+    {
+        return Err("El valor del punto debe ser mayor a cero".to_string());
+    }
+    if let Some(ref multipliers) = request.category_multipliers {
+        if multipliers
+            .iter()
+            .any(|category| category.multiplier < Decimal::ZERO)
+        {
+            return Err("Los multiplicadores no pueden ser negativos".to_string());
+        }
+    }
+
+    // 2. Iniciar transacción
+    let txn = db
+        .begin()
+        .await
+        .map_err(|_| "Error al iniciar transacción")?;
+
+    // 3. Actualizar tasas
+    let settings = load_settings(&txn).await?;
+    let mut active_model: loyalty_settings::ActiveModel = settings.into();
+    if let Some(earn_rate) = request.earn_rate {
+        active_model.earn_rate = Set(earn_rate);
+    }
+    if let Some(point_value) = request.point_value {
+        active_model.point_value = Set(point_value);
+    }
+    if let Some(is_active) = request.is_active {
+        active_model.is_active = Set(is_active);
+    }
+    active_model.updated_by = Set(Some(session.user_id.clone()));
+    active_model.updated_at = Set(Utc::now().fixed_offset());
+
+    active_model
+        .update(&txn)
+        .await
+        .map_err(|e| format!("Error al actualizar la configuración de puntos: {:?}", e))?;
+
+    // 4. Actualizar multiplicadores por categoría
+    for category in request.category_multipliers.unwrap_or_default() {
+        let updated = Categories::update_many()
+            .col_expr(
+                categories::Column::PointsMultiplier,
+                category.multiplier.round_dp(2).into(),
+            )
+            .filter(categories::Column::Id.eq(category.category_id))
+            .exec(&txn)
+            .await
+            .map_err(|e| format!("Error al actualizar el multiplicador: {:?}", e))?;
+
+        if updated.rows_affected == 0 {
+            return Err(format!("Categoría {} no encontrada", category.category_id));
+        }
+    }
+
+    // 5. Commit transacción
+    txn.commit()
+        .await
+        .map_err(|_| "Error al confirmar la transacción")?;
+
+    load_config(db).await
+}
+
+/// Obtiene el saldo de puntos de un cliente conciliado contra sus movimientos
+#[tauri::command]
+pub async fn get_loyalty_account(
+    state: tauri::State<'_, AppState>,
+    customer_id: i32,
+) -> Result<LoyaltyAccount, String> {
+    require_permission(&state, "customer.view")?;
+    let db = &state.database;
+
+    let customer = Customers::find_by_id(customer_id)
+        .one(db)
+        .await
+        .map_err(|_| DB_ERROR)?
+        .ok_or(format!("Cliente {} no encontrado", customer_id))?;
+    let settings = load_settings(db).await?;
+
+    let ledger_points: i32 = LoyaltyEntries::find()
+        .filter(loyalty_entries::Column::CustomerId.eq(customer.id))
+        .all(db)
+        .await
+        .map_err(|_| DB_ERROR)?
+        .iter()
+        .map(|entry| entry.points)
+        .sum();
+
+    Ok(LoyaltyAccount {
+        customer_id: customer.id,
+        customer_name: customer.name,
+        points: customer.loyalty_points,
+        points_value: (Decimal::from(customer.loyalty_points.max(0)) * settings.point_value)
+            .round_dp(2),
+        ledger_points,
+        is_reconciled: ledger_points == customer.loyalty_points,
+    })
+}
+
+/// Obtiene una página de movimientos de puntos de un cliente
+#[tauri::command]
+pub async fn get_loyalty_entries(
+    state: tauri::State<'_, AppState>,
+    filters: LoyaltyEntryFilter,
+) -> Result<LoyaltyEntryListResponse, String> {
+    require_permission(&state, "customer.view")?;
+    let db = &state.database;
+
+    let mut query =
+        LoyaltyEntries::find().filter(loyalty_entries::Column::CustomerId.eq(filters.customer_id));
+
+    if let Some(entry_type) = filters.entry_type {
+        query = query.filter(loyalty_entries::Column::EntryType.eq(entry_type));
+    }
+    if let Some(ref date_from) = filters.date_from {
+        if let Ok(parsed_date) = NaiveDate::parse_from_str(date_from, "%Y-%m-%d") {
+            let datetime_from = parsed_date.and_hms_opt(0, 0, 0).unwrap();
+            query = query.filter(loyalty_entries::Column::CreatedAt.gte(datetime_from));
+        }
+    }
+    if let Some(ref date_to) = filters.date_to {
+        if let Ok(parsed_date) = NaiveDate::parse_from_str(date_to, "%Y-%m-%d") {
+            let datetime_to = parsed_date.and_hms_opt(23, 59, 59).unwrap();
+            query = query.filter(loyalty_entries::Column::CreatedAt.lte(datetime_to));
+        }
+    }
+
+    // Contar total de items para paginación
+    let total_items = query.clone().count(db).await.map_err(|_| DB_ERROR)?;
+
+    let total_pages = if filters.limit > 0 {
+        (total_items as f64 / filters.limit as f64).ceil() as u64
+    } else {
+        1
+    };
+
+    let offset = (filters.page.saturating_sub(1)) * filters.limit;
+    let entries = query
+        .order_by_desc(loyalty_entries::Column::Id)
+        .offset(offset)
+        .limit(filters.limit)
+        .all(db)
+        .await
+        .map_err(|_| DB_ERROR)?;
+
+    let user_ids: Vec<String> = entries
+        .iter()
+        .map(|entry| entry.created_by.clone())
+        .collect();
+    let user_names: HashMap<String, String> = Users::find()
+        .filter(users::Column::Id.is_in(user_ids))
+        .all(db)
+        .await
+        .map_err(|_| DB_ERROR)?
+        .into_iter()
+        .map(|user| (user.id, format!("{} {}", user.first_name, user.last_name)))
+        .collect();
+
+    Ok(LoyaltyEntryListResponse {
+        entries: entries
+            .into_iter()
+            .map(|entry| {
+                let name = user_names.get(&entry.created_by).cloned();
+                LoyaltyEntry::from_model(entry, name)
+            })
+            .collect(),
+        total_pages,
+        total_items,
+    })
+}
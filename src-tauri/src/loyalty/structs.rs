@@ -0,0 +1,124 @@
+use crate::entities::{loyalty_entries, loyalty_settings};
+use sea_orm::prelude::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Tipo de cobro de la forma de pago "Puntos de lealtad"
+pub const TENDER_POINTS: &str = "points";
+
+pub const ENTRY_EARN: &str = "earn"; // Puntos ganados en una venta
+pub const ENTRY_REDEEM: &str = "redeem"; // Puntos canjeados como forma de pago
+pub const ENTRY_REFUND: &str = "refund"; // Puntos devueltos al reembolsar lo pagado con puntos
+pub const ENTRY_REVERSAL: &str = "reversal"; // Reverso por devolución o cancelación
+
+/// Multiplicador de puntos de una categoría
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CategoryMultiplier {
+    pub category_id: i32,
+    pub category_name: String,
+    pub multiplier: Decimal,
+}
+
+/// Configuración del programa de puntos
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct LoyaltyConfig {
+    pub earn_rate: Decimal,   // Puntos por peso pagado
+    pub point_value: Decimal, // Pesos por punto al canjear
+    pub is_active: bool,
+    pub category_multipliers: Vec<CategoryMultiplier>, // Solo las distintas de 1
+    pub updated_by: Option<String>,
+    pub updated_at: String,
+}
+
+impl LoyaltyConfig {
+    pub fn from_model(
+        value: loyalty_settings::Model,
+        category_multipliers: Vec<CategoryMultiplier>,
+    ) -> Self {
+        Self {
+            earn_rate: value.earn_rate,
+            point_value: value.point_value,
+            is_active: value.is_active,
+            category_multipliers,
+            updated_by: value.updated_by,
+            updated_at: value.updated_at.to_string(),
+        }
+    }
+}
+
+// Multiplicador a asignar a una categoría
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CategoryMultiplierRequest {
+    pub category_id: i32,
+    pub multiplier: Decimal,
+}
+
+// Request para actualizar la configuración del programa de puntos
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct UpdateLoyaltyConfigRequest {
+    pub earn_rate: Option<Decimal>,
+    pub point_value: Option<Decimal>,
+    pub is_active: Option<bool>,
+    pub category_multipliers: Option<Vec<CategoryMultiplierRequest>>,
+}
+
+/// Movimiento de puntos (para lectura/respuesta)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct LoyaltyEntry {
+    pub id: i32,
+    pub customer_id: i32,
+    pub entry_type: String,
+    pub points: i32, // Positivo = acumula, negativo = descuenta
+    pub balance_after: i32,
+    pub sale_id: Option<String>,
+    pub notes: Option<String>,
+    pub created_by: String,
+    pub created_by_name: Option<String>,
+    pub created_at: String,
+}
+
+impl LoyaltyEntry {
+    pub fn from_model(value: loyalty_entries::Model, created_by_name: Option<String>) -> Self {
+        Self {
+            id: value.id,
+            customer_id: value.customer_id,
+            entry_type: value.entry_type,
+            points: value.points,
+            balance_after: value.balance_after,
+            sale_id: value.sale_id,
+            notes: value.notes,
+            created_by: value.created_by,
+            created_by_name,
+            created_at: value.created_at.to_string(),
+        }
+    }
+}
+
+/// Saldo de puntos de un cliente
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct LoyaltyAccount {
+    pub customer_id: i32,
+    pub customer_name: String,
+    pub points: i32,
+    pub points_value: Decimal, // Valor en pesos al canjear
+    pub ledger_points: i32,    // Suma de los movimientos; debe ser igual al saldo
+    pub is_reconciled: bool,
+}
+
+/// Filtros para obtener los movimientos de puntos de un cliente
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct LoyaltyEntryFilter {
+    pub customer_id: i32,
+    pub entry_type: Option<String>,
+    pub date_from: Option<String>, // AAAA-MM-DD
+    pub date_to: Option<String>,
+    pub page: u64,
+    pub limit: u64,
+}
+
+/// Respuesta paginada de movimientos de puntos
+#[derive(Serialize, Deserialize, Debug)]
+pub struct LoyaltyEntryListResponse {
+    pub entries: Vec<LoyaltyEntry>,
+    pub total_pages: u64,
+    pub total_items: u64,
+}
@@ -0,0 +1,282 @@
+use std::collections::HashMap;
+
+use sea_orm::{
+    prelude::Decimal, ActiveModelTrait, ActiveValue::Set, ColumnTrait, ConnectionTrait,
+    EntityTrait, QueryFilter, QueryOrder, TransactionTrait,
+};
+
+use super::structs::{ParkSaleRequest, ParkedSale, ParkedSaleItem, ResumedParkedSale};
+use crate::entities::{
+    customers, parked_sale_items, parked_sales,
+    prelude::{Customers, ParkedSaleItems, ParkedSales, Products, Users},
+    users,
+};
+use crate::sales::structs::{DiscountRequest, SalePreviewRequest};
+use crate::sales::SalesHandler::build_sale_preview;
+use crate::sessions::require_permission;
+use crate::AppState;
+
+const DB_ERROR: &str = "Error en la base de datos";
+
+/// Carga los tickets en espera indicados con sus líneas, cliente y usuario
+async fn load_parked_sales<C: ConnectionTrait>(
+    db: &C,
+    parked: Vec<parked_sales::Model>,
+) -> Result<Vec<ParkedSale>, String> {
+    let parked_ids: Vec<i32> = parked.iter().map(|p| p.id).collect();
+    let items = ParkedSaleItems::find()
+        .find_also_related(Products)
+        .filter(parked_sale_items::Column::ParkedSaleId.is_in(parked_ids))
+        .order_by_asc(parked_sale_items::Column::Id)
+        .all(db)
+        .await
+        .map_err(|_| DB_ERROR)?;
+
+    let customer_ids: Vec<i32> = parked.iter().filter_map(|p| p.customer_id).collect();
+    let customer_names: HashMap<i32, String> = Customers::find()
+        .filter(customers::Column::Id.is_in(customer_ids))
+        .all(db)
+        .await
+        .map_err(|_| DB_ERROR)?
+        .into_iter()
+        .map(|customer| (customer.id, customer.name))
+        .collect();
+
+    let user_ids: Vec<String> = parked.iter().map(|p| p.created_by.clone()).collect();
+    let user_names: HashMap<String, String> = Users::find()
+        .filter(users::Column::Id.is_in(user_ids))
+        .all(db)
+        .await
+        .map_err(|_| DB_ERROR)?
+        .into_iter()
+        .map(|user| (user.id, format!("{} {}", user.first_name, user.last_name)))
+        .collect();
+
+    Ok(parked
+        .into_iter()
+        .map(|parked_sale| ParkedSale {
+            items: items
+                .iter()
+                .filter(|(item, _)| item.parked_sale_id == parked_sale.id)
+                .map(|(item, product)| ParkedSaleItem {
+                    id: item.id,
+                    product_id: item.product_id,
+                    product_name: product.as_ref().map(|p| p.name.clone()),
+                    product_code: product.as_ref().map(|p| p.code.clone()),
+                    unit_price: product.as_ref().map(|p| p.price),
+                    quantity: item.quantity,
+                    price_override: item.price_override,
+                    discount: DiscountRequest::from_stored(
+                        item.discount_type.as_deref(),
+                        item.discount_value,
+                    ),
+                })
+                .collect(),
+            id: parked_sale.id,
+            customer_name: parked_sale
+                .customer_id
+                .and_then(|id| customer_names.get(&id).cloned()),
+            customer_id: parked_sale.customer_id,
+            discount: DiscountRequest::from_stored(
+                parked_sale.discount_type.as_deref(),
+                parked_sale.discount_value,
+            ),
+            coupon_code: parked_sale.coupon_code,
+            created_by_name: user_names.get(&parked_sale.created_by).cloned(),
+            created_by: parked_sale.created_by,
+            created_at: parked_sale.created_at.to_string(),
+            label: parked_sale.label,
+        })
+        .collect())
+}
+
+/// Carga un ticket en espera por ID
+async fn find_parked_sale<C: ConnectionTrait>(db: &C, id: i32) -> Result<ParkedSale, String> {
+    let parked_sale = ParkedSales::find_by_id(id)
+        .one(db)
+        .await
+        .map_err(|_| DB_ERROR)?
+        .ok_or("El ticket en espera ya fue cobrado o descartado")?;
+
+    load_parked_sales(db, vec![parked_sale])
+        .await?
+        .pop()
+        .ok_or_else(|| DB_ERROR.to_string())
+}
+
+/// Guarda el carrito actual como ticket en espera
+#[tauri::command]
+pub async fn park_sale(
+    state: tauri::State<'_, AppState>,
+    request: ParkSaleRequest,
+) -> Result<ParkedSale, String> {
+    // 1. Validar permiso
+    let session = require_permission(&state, "sales.create")?;
+    let db = &state.database;
+
+    // 2. Validar datos del carrito. Precios, stock y descuentos se validan al cobrar.
+    let label = request.label.trim();
+    if label.is_empty() {
+        return Err("Indique una referencia para el ticket en espera".to_string());
+    }
+
+    if request.items.is_empty() {
+        return Err("El ticket en espera debe tener al menos un producto".to_string());
+    }
+
+    if request
+        .items
+        .iter()
+        .any(|item| item.quantity <= Decimal::ZERO)
+    {
+        return Err("Las cantidades deben ser positivas".to_string());
+    }
+
+    for item in &request.items {
+        Products::find_by_id(item.product_id)
+            .one(db)
+            .await
+            .map_err(|_| format!("Error al consultar producto {}", item.product_id))?
+            .ok_or(format!("Producto {} no encontrado", item.product_id))?;
+    }
+
+    if let Some(customer_id) = request.customer_id {
+        Customers::find_by_id(customer_id)
+            .one(db)
+            .await
+            .map_err(|_| DB_ERROR)?
+            .ok_or(format!("Cliente {} no encontrado", customer_id))?;
+    }
+
+    // 3. Guardar el ticket y sus líneas en una transacción
+    let txn = db
+        .begin()
+        .await
+        .map_err(|_| "Error al iniciar transacción")?;
+
+    let parked_sale = parked_sales::ActiveModel {
+        label: Set(label.to_string()),
+        customer_id: Set(request.customer_id),
+        discount_type: Set(request
+            .discount
+            .as_ref()
+            .map(|discount| discount.discount_type.as_str().to_string())),
+        discount_value: Set(request
+            .discount
+            .as_ref()
+            .map_or(Decimal::ZERO, |discount| discount.value)),
+        coupon_code: Set(request
+            .coupon_code
+            .as_deref()
+            .map(str::trim)
+            .filter(|code| !code.is_empty())
+            .map(str::to_uppercase)),
+        created_by: Set(session.user_id.clone()),
+        ..Default::default()
+    }
+    .insert(&txn)
+    .await
+    .map_err(|e| format!("Error al guardar el ticket en espera: {:?}", e))?;
+
+    ParkedSaleItems::insert_many(request.items.iter().map(|item| {
+        parked_sale_items::ActiveModel {
+            parked_sale_id: Set(parked_sale.id),
+            product_id: Set(item.product_id),
+            quantity: Set(item.quantity),
+            price_override: Set(item.price_override),
+            discount_type: Set(item
+                .discount
+                .as_ref()
+                .map(|discount| discount.discount_type.as_str().to_string())),
+            discount_value: Set(item
+                .discount
+                .as_ref()
+                .map_or(Decimal::ZERO, |discount| discount.value)),
+            ..Default::default()
+        }
+    }))
+    .exec(&txn)
+    .await
+    .map_err(|e| format!("Error al guardar las líneas del ticket: {:?}", e))?;
+
+    txn.commit()
+        .await
+        .map_err(|_| "Error al confirmar la transacción")?;
+
+    // 4. Retornar el ticket guardado
+    find_parked_sale(db, parked_sale.id).await
+}
+
+/// Lista los tickets en espera de todas las cajas, del más antiguo al más reciente
+#[tauri::command]
+pub async fn list_parked_sales(
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<ParkedSale>, String> {
+    require_permission(&state, "sales.create")?;
+    let db = &state.database;
+
+    let parked = ParkedSales::find()
+        .order_by_asc(parked_sales::Column::CreatedAt)
+        .all(db)
+        .await
+        .map_err(|_| DB_ERROR)?;
+
+    load_parked_sales(db, parked).await
+}
+
+/// Retoma un ticket en espera: devuelve su carrito con los importes recalculados
+/// como lo hará create_sale. El ticket se elimina al cobrarse con parked_sale_id.
+#[tauri::command]
+pub async fn resume_parked_sale(
+    state: tauri::State<'_, AppState>,
+    id: i32,
+) -> Result<ResumedParkedSale, String> {
+    let session = require_permission(&state, "sales.create")?;
+    let db = &state.database;
+
+    let parked_sale = find_parked_sale(db, id).await?;
+
+    // El ticket se retoma aunque ya no se pueda cobrar tal cual; create_sale hace la
+    // validación final
+    let preview = build_sale_preview(
+        db,
+        &session,
+        &SalePreviewRequest {
+            items: parked_sale.sale_items(),
+            discount: parked_sale.discount.clone(),
+            coupon_code: parked_sale.coupon_code.clone(),
+        },
+    )
+    .await;
+
+    Ok(ResumedParkedSale {
+        parked_sale,
+        preview_error: preview.as_ref().err().cloned(),
+        preview: preview.ok(),
+    })
+}
+
+/// Descarta un ticket en espera. Solo quien lo creó o un usuario con permiso
+/// de cancelar ventas puede descartar tickets ajenos.
+#[tauri::command]
+pub async fn discard_parked_sale(state: tauri::State<'_, AppState>, id: i32) -> Result<(), String> {
+    let session = require_permission(&state, "sales.create")?;
+    let db = &state.database;
+
+    let parked_sale = ParkedSales::find_by_id(id)
+        .one(db)
+        .await
+        .map_err(|_| DB_ERROR)?
+        .ok_or("El ticket en espera ya fue cobrado o descartado")?;
+
+    if parked_sale.created_by != session.user_id && !session.has_permission("sales.cancel") {
+        return Err("No tiene permisos para descartar tickets de otros usuarios".to_string());
+    }
+
+    ParkedSales::delete_by_id(parked_sale.id)
+        .exec(db)
+        .await
+        .map_err(|_| "Ocurrió un error al descartar el ticket en espera".to_string())?;
+
+    Ok(())
+}
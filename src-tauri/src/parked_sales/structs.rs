@@ -0,0 +1,65 @@
+use crate::sales::structs::{DiscountRequest, SaleItemRequest, SalePreviewResponse};
+use sea_orm::prelude::Decimal;
+use serde::{Deserialize, Serialize};
+
+// Request para dejar un carrito en espera
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ParkSaleRequest {
+    pub label: String, // Referencia para identificar el ticket (ej: "Cliente de camisa azul")
+    pub items: Vec<SaleItemRequest>,
+    pub customer_id: Option<i32>,
+    pub discount: Option<DiscountRequest>,
+    pub coupon_code: Option<String>,
+}
+
+// Línea guardada del carrito con el precio vigente del catálogo
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ParkedSaleItem {
+    pub id: i32,
+    pub product_id: i32,
+    pub product_name: Option<String>,
+    pub product_code: Option<String>,
+    pub unit_price: Option<Decimal>,
+    pub quantity: Decimal,
+    pub price_override: Option<Decimal>,
+    pub discount: Option<DiscountRequest>,
+}
+
+// Ticket en espera con su carrito
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ParkedSale {
+    pub id: i32,
+    pub label: String,
+    pub customer_id: Option<i32>,
+    pub customer_name: Option<String>,
+    pub discount: Option<DiscountRequest>,
+    pub coupon_code: Option<String>,
+    pub created_by: String,
+    pub created_by_name: Option<String>,
+    pub created_at: String,
+    pub items: Vec<ParkedSaleItem>,
+}
+
+impl ParkedSale {
+    /// Líneas en el formato que recibe create_sale
+    pub fn sale_items(&self) -> Vec<SaleItemRequest> {
+        self.items
+            .iter()
+            .map(|item| SaleItemRequest {
+                product_id: item.product_id,
+                quantity: item.quantity,
+                price_override: item.price_override,
+                discount: item.discount.clone(),
+            })
+            .collect()
+    }
+}
+
+// Ticket retomado con los importes recalculados. Para cobrarlo se envía
+// create_sale con parked_sale_id, que elimina el ticket en la misma transacción.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ResumedParkedSale {
+    pub parked_sale: ParkedSale,
+    pub preview: Option<SalePreviewResponse>,
+    pub preview_error: Option<String>, // Producto agotado, precio cambiado, etc.; se corrige antes de cobrar
+}
@@ -0,0 +1,181 @@
+//! Codificador de comandos ESC/POS para impresoras térmicas
+
+use super::structs::DrawerPin;
+use crate::receipts::structs::{Align, ReceiptLine};
+
+const ESC: u8 = 0x1B;
+const GS: u8 = 0x1D;
+const LF: u8 = 0x0A;
+
+/// Página de códigos PC850 (multilingüe), cubre los acentos del español
+const CODE_PAGE_PC850: u8 = 2;
+
+/// Convierte un carácter a la página de códigos PC850
+fn encode_char(c: char) -> u8 {
+    match c {
+        ' '..='~' => c as u8,
+        'ü' => 0x81,
+        'é' => 0x82,
+        'á' => 0xA0,
+        'í' => 0xA1,
+        'ó' => 0xA2,
+        'ú' => 0xA3,
+        'ñ' => 0xA4,
+        'Ñ' => 0xA5,
+        '¿' => 0xA8,
+        '¡' => 0xAD,
+        'É' => 0x90,
+        'Ü' => 0x9A,
+        'Á' => 0xB5,
+        'Í' => 0xD6,
+        'Ó' => 0xE0,
+        'Ú' => 0xE9,
+        _ => b'?',
+    }
+}
+
+/// Acumula comandos ESC/POS en un buffer listo para enviarse a la impresora
+#[derive(Debug, Default)]
+pub struct EscPos {
+    buffer: Vec<u8>,
+}
+
+impl EscPos {
+    /// Inicializa la impresora y selecciona la página de códigos
+    pub fn new() -> Self {
+        let mut printer = Self::default();
+        printer.buffer.extend_from_slice(&[ESC, b'@']);
+        printer
+            .buffer
+            .extend_from_slice(&[ESC, b't', CODE_PAGE_PC850]);
+        printer
+    }
+
+    pub fn text(&mut self, text: &str) -> &mut Self {
+        self.buffer.extend(text.chars().map(encode_char));
+        self
+    }
+
+    pub fn line(&mut self, text: &str) -> &mut Self {
+        self.text(text).feed(1)
+    }
+
+    pub fn feed(&mut self, lines: u8) -> &mut Self {
+        if lines == 1 {
+            self.buffer.push(LF);
+        } else {
+            self.buffer.extend_from_slice(&[ESC, b'd', lines]);
+        }
+        self
+    }
+
+    pub fn bold(&mut self, enabled: bool) -> &mut Self {
+        self.buffer.extend_from_slice(&[ESC, b'E', enabled as u8]);
+        self
+    }
+
+    pub fn underline(&mut self, enabled: bool) -> &mut Self {
+        self.buffer.extend_from_slice(&[ESC, b'-', enabled as u8]);
+        self
+    }
+
+    /// Doble ancho y doble alto
+    pub fn double_size(&mut self, enabled: bool) -> &mut Self {
+        let size = if enabled { 0x11 } else { 0x00 };
+        self.buffer.extend_from_slice(&[GS, b'!', size]);
+        self
+    }
+
+    pub fn align(&mut self, align: Align) -> &mut Self {
+        let value = match align {
+            Align::Left => 0,
+            Align::Center => 1,
+            Align::Right => 2,
+        };
+        self.buffer.extend_from_slice(&[ESC, b'a', value]);
+        self
+    }
+
+    /// Avanza el papel y realiza un corte parcial
+    pub fn cut(&mut self) -> &mut Self {
+        self.buffer.extend_from_slice(&[GS, b'V', 66, 3]);
+        self
+    }
+
+    /// Código de barras CODE128 (juego B) con el texto impreso debajo.
+    /// Una `{` literal se envía como `{{` para que no se lea como cambio de juego.
+    pub fn barcode_code128(&mut self, data: &str) -> &mut Self {
+        let mut escaped: Vec<u8> = Vec::with_capacity(data.len());
+        for byte in data.chars().map(encode_char) {
+            let width = if byte == b'{' { 2 } else { 1 };
+            if escaped.len() + width > 253 {
+                break;
+            }
+            escaped.push(byte);
+            if byte == b'{' {
+                escaped.push(b'{');
+            }
+        }
+        let data = escaped;
+        self.buffer.extend_from_slice(&[GS, b'H', 2]); // Texto debajo del código
+        self.buffer.extend_from_slice(&[GS, b'h', 80]); // Alto en puntos
+        self.buffer.extend_from_slice(&[GS, b'w', 2]); // Ancho de módulo
+        self.buffer
+            .extend_from_slice(&[GS, b'k', 73, (data.len() + 2) as u8, b'{', b'B']);
+        self.buffer.extend_from_slice(&data);
+        self
+    }
+
+    /// Código QR modelo 2 con corrección de errores nivel M
+    pub fn qr(&mut self, data: &str, module_size: u8) -> &mut Self {
+        let data = data.as_bytes();
+        let length = data.len() + 3;
+        let (pl, ph) = ((length % 256) as u8, (length / 256) as u8);
+
+        // Modelo 2
+        self.buffer
+            .extend_from_slice(&[GS, b'(', b'k', 4, 0, 49, 65, 50, 0]);
+        // Tamaño del módulo
+        self.buffer
+            .extend_from_slice(&[GS, b'(', b'k', 3, 0, 49, 67, module_size.clamp(1, 16)]);
+        // Nivel de corrección M
+        self.buffer
+            .extend_from_slice(&[GS, b'(', b'k', 3, 0, 49, 69, 49]);
+        // Guardar datos e imprimir
+        self.buffer
+            .extend_from_slice(&[GS, b'(', b'k', pl, ph, 49, 80, 48]);
+        self.buffer.extend_from_slice(data);
+        self.buffer
+            .extend_from_slice(&[GS, b'(', b'k', 3, 0, 49, 81, 48]);
+        self
+    }
+
+    /// Pulso para abrir el cajón de dinero
+    pub fn open_drawer(&mut self, pin: DrawerPin) -> &mut Self {
+        let pin = match pin {
+            DrawerPin::Pin2 => 0,
+            DrawerPin::Pin5 => 1,
+        };
+        self.buffer.extend_from_slice(&[ESC, b'p', pin, 25, 250]);
+        self
+    }
+
+    /// Agrega las líneas de un ticket respetando alineación y negritas
+    pub fn receipt(&mut self, lines: &[ReceiptLine]) -> &mut Self {
+        for receipt_line in lines {
+            self.align(receipt_line.align);
+            if receipt_line.bold {
+                self.bold(true);
+            }
+            self.line(&receipt_line.text);
+            if receipt_line.bold {
+                self.bold(false);
+            }
+        }
+        self.align(Align::Left)
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.buffer
+    }
+}
@@ -0,0 +1,179 @@
+use sea_orm::ConnectionTrait;
+use tokio::{fs::OpenOptions, io::AsyncWriteExt};
+
+use super::escpos::EscPos;
+use super::structs::{PrintQuoteParams, PrintReceiptParams, PrinterConfig};
+use crate::customer_credit::structs::CreditStatementParams;
+use crate::customer_credit::CustomerCreditHandler::load_credit_statement;
+use crate::quotes::QuotesHandler::load_quote_detail;
+use crate::receipts::render::{build_credit_statement, build_quote, build_receipt};
+use crate::receipts::structs::{Align, StoreInfo};
+use crate::sales::SalesHandler::load_sale_detail;
+use crate::sessions::require_permission;
+use crate::AppState;
+
+const PRINTER_NOT_CONFIGURED: &str = "No hay una impresora configurada (PRINTER_DEVICE)";
+
+/// Envía los bytes al dispositivo o archivo configurado
+async fn write_to_device(config: &PrinterConfig, bytes: &[u8]) -> Result<(), String> {
+    let mut device = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&config.device)
+        .await
+        .map_err(|e| format!("No se pudo abrir la impresora '{}': {}", config.device, e))?;
+
+    device
+        .write_all(bytes)
+        .await
+        .map_err(|e| format!("Error al enviar datos a la impresora: {}", e))?;
+
+    device
+        .flush()
+        .await
+        .map_err(|e| format!("Error al enviar datos a la impresora: {}", e))
+}
+
+/// Imprime el ticket de una venta y opcionalmente abre el cajón
+pub async fn print_sale<C: ConnectionTrait>(
+    db: &C,
+    config: &PrinterConfig,
+    sale_id: &str,
+    reprint: bool,
+    open_drawer: bool,
+) -> Result<(), String> {
+    let detail = load_sale_detail(db, sale_id).await?;
+    let lines = build_receipt(
+        &StoreInfo::from_env(),
+        &detail,
+        config.paper_width.columns(),
+        reprint,
+    );
+
+    let mut printer = EscPos::new();
+    if open_drawer {
+        printer.open_drawer(config.drawer_pin);
+    }
+    printer
+        .receipt(&lines)
+        .feed(1)
+        .align(Align::Center)
+        .qr(&detail.sale.id, 5)
+        .feed(4)
+        .cut();
+
+    write_to_device(config, &printer.into_bytes()).await
+}
+
+/// Imprime (o reimprime) el ticket de una venta en la impresora configurada
+#[tauri::command]
+pub async fn print_sale_receipt(
+    state: tauri::State<'_, AppState>,
+    params: PrintReceiptParams,
+) -> Result<(), String> {
+    require_permission(&state, "sales.view")?;
+    // Abrir el cajón requiere el mismo permiso que open_cash_drawer
+    if params.open_drawer {
+        require_permission(&state, "sales.create")?;
+    }
+    let config = PrinterConfig::from_env().ok_or(PRINTER_NOT_CONFIGURED)?;
+
+    print_sale(
+        &state.database,
+        &config,
+        &params.sale_id,
+        params.reprint,
+        params.open_drawer,
+    )
+    .await
+}
+
+/// Imprime una cotización en la impresora configurada
+#[tauri::command]
+pub async fn print_quote(
+    state: tauri::State<'_, AppState>,
+    params: PrintQuoteParams,
+) -> Result<(), String> {
+    require_permission(&state, "quotes.view")?;
+    let config = PrinterConfig::from_env().ok_or(PRINTER_NOT_CONFIGURED)?;
+
+    let detail = load_quote_detail(&state.database, params.quote_id).await?;
+    let lines = build_quote(
+        &StoreInfo::from_env(),
+        &detail,
+        config.paper_width.columns(),
+    );
+
+    let mut printer = EscPos::new();
+    printer.receipt(&lines).feed(4).cut();
+
+    write_to_device(&config, &printer.into_bytes()).await
+}
+
+/// Imprime el estado de cuenta de un cliente en la impresora configurada
+#[tauri::command]
+pub async fn print_credit_statement(
+    state: tauri::State<'_, AppState>,
+    params: CreditStatementParams,
+) -> Result<(), String> {
+    require_permission(&state, "customer.credit")?;
+    let config = PrinterConfig::from_env().ok_or(PRINTER_NOT_CONFIGURED)?;
+
+    let statement = load_credit_statement(&state.database, &params).await?;
+    let lines = build_credit_statement(
+        &StoreInfo::from_env(),
+        &statement,
+        config.paper_width.columns(),
+    );
+
+    let mut printer = EscPos::new();
+    printer.receipt(&lines).feed(4).cut();
+
+    write_to_device(&config, &printer.into_bytes()).await
+}
+
+/// Abre el cajón de dinero sin imprimir
+#[tauri::command]
+pub async fn open_cash_drawer(state: tauri::State<'_, AppState>) -> Result<(), String> {
+    require_permission(&state, "sales.create")?;
+    let config = PrinterConfig::from_env().ok_or(PRINTER_NOT_CONFIGURED)?;
+
+    let mut printer = EscPos::new();
+    printer.open_drawer(config.drawer_pin);
+
+    write_to_device(&config, &printer.into_bytes()).await
+}
+
+/// Imprime una página de prueba para verificar la configuración de la impresora
+#[tauri::command]
+pub async fn print_test_page(state: tauri::State<'_, AppState>) -> Result<(), String> {
+    require_permission(&state, "sales.view")?;
+    let config = PrinterConfig::from_env().ok_or(PRINTER_NOT_CONFIGURED)?;
+    let store = StoreInfo::from_env();
+
+    let mut printer = EscPos::new();
+    printer
+        .align(Align::Center)
+        .double_size(true)
+        .line(&store.name)
+        .double_size(false)
+        .line("Página de prueba")
+        .feed(1)
+        .align(Align::Left)
+        .bold(true)
+        .line("Texto en negritas")
+        .bold(false)
+        .underline(true)
+        .line("Texto subrayado")
+        .underline(false)
+        .line("Acentos: áéíóú ñÑ ¿? ¡!")
+        .line(&"-".repeat(config.paper_width.columns()))
+        .align(Align::Center)
+        .barcode_code128("1234567890")
+        .feed(1)
+        .qr("https://example.com", 5)
+        .feed(4)
+        .cut();
+
+    write_to_device(&config, &printer.into_bytes()).await
+}
@@ -0,0 +1,5 @@
+pub mod escpos;
+pub mod handlers;
+pub mod structs;
+
+pub use handlers as PrinterHandler;
@@ -0,0 +1,69 @@
+use serde::{Deserialize, Serialize};
+use std::env;
+
+use crate::receipts::structs::PaperWidth;
+
+/// Pin del conector RJ11 donde está conectado el cajón de dinero
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum DrawerPin {
+    Pin2,
+    Pin5,
+}
+
+/// Configuración de la impresora térmica, tomada del archivo .env
+///
+/// - `PRINTER_DEVICE`: ruta del dispositivo o archivo (ej: /dev/usb/lp0, /dev/ttyUSB0, /tmp/ticket.bin)
+/// - `PRINTER_PAPER_WIDTH`: 58mm u 80mm (por defecto 80mm)
+/// - `PRINTER_DRAWER_PIN`: 2 o 5 (por defecto 2)
+/// - `PRINTER_AUTO_PRINT`: true para imprimir el ticket al crear cada venta
+#[derive(Debug, Clone)]
+pub struct PrinterConfig {
+    pub device: String,
+    pub paper_width: PaperWidth,
+    pub drawer_pin: DrawerPin,
+    pub auto_print: bool,
+}
+
+impl PrinterConfig {
+    /// Retorna None si no hay una impresora configurada
+    pub fn from_env() -> Option<Self> {
+        let device = env::var("PRINTER_DEVICE")
+            .ok()
+            .filter(|value| !value.trim().is_empty())?;
+
+        let paper_width = match env::var("PRINTER_PAPER_WIDTH").as_deref() {
+            Ok("58mm") => PaperWidth::Mm58,
+            _ => PaperWidth::Mm80,
+        };
+
+        let drawer_pin = match env::var("PRINTER_DRAWER_PIN").as_deref() {
+            Ok("5") => DrawerPin::Pin5,
+            _ => DrawerPin::Pin2,
+        };
+
+        let auto_print = env::var("PRINTER_AUTO_PRINT")
+            .map(|value| value.eq_ignore_ascii_case("true"))
+            .unwrap_or(false);
+
+        Some(Self {
+            device,
+            paper_width,
+            drawer_pin,
+            auto_print,
+        })
+    }
+}
+
+/// Parámetros para imprimir el ticket de una venta
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PrintReceiptParams {
+    pub sale_id: String,
+    pub reprint: bool,
+    pub open_drawer: bool,
+}
+
+/// Parámetros para imprimir una cotización
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PrintQuoteParams {
+    pub quote_id: i32,
+}
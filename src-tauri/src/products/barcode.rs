@@ -0,0 +1,143 @@
+//! Códigos de barras EAN-13 de báscula: prefijo 20–29, PLU, peso o precio y dígito verificador
+
+use std::env;
+
+use sea_orm::prelude::Decimal;
+
+/// Valor embebido en la etiqueta de la báscula
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScaleValue {
+    Weight(Decimal), // Kilogramos
+    Price(Decimal),  // Importe a cobrar con impuestos
+}
+
+/// Etiqueta de báscula decodificada: 2P CCCCC VVVVV D
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScaleBarcode {
+    pub plu: String, // Código del producto en la báscula, con ceros a la izquierda
+    pub value: ScaleValue,
+}
+
+/// Prefijos de las etiquetas de báscula, tomados del archivo .env
+///
+/// - `SCALE_WEIGHT_PREFIXES`: prefijos con peso en gramos (por defecto 20,21,22,23,24)
+/// - `SCALE_PRICE_PREFIXES`: prefijos con precio en centavos (por defecto 25,26,27,28,29)
+#[derive(Debug, Clone)]
+pub struct ScaleBarcodeConfig {
+    pub weight_prefixes: Vec<u8>,
+    pub price_prefixes: Vec<u8>,
+}
+
+impl ScaleBarcodeConfig {
+    pub fn from_env() -> Self {
+        let prefixes = |key: &str, default: &[u8]| {
+            let parsed: Vec<u8> = env::var(key)
+                .ok()
+                .map(|value| {
+                    value
+                        .split(',')
+                        .filter_map(|prefix| prefix.trim().parse::<u8>().ok())
+                        .filter(|prefix| (20..=29).contains(prefix))
+                        .collect()
+                })
+                .unwrap_or_default();
+
+            if parsed.is_empty() {
+                default.to_vec()
+            } else {
+                parsed
+            }
+        };
+
+        Self {
+            weight_prefixes: prefixes("SCALE_WEIGHT_PREFIXES", &[20, 21, 22, 23, 24]),
+            price_prefixes: prefixes("SCALE_PRICE_PREFIXES", &[25, 26, 27, 28, 29]),
+        }
+    }
+}
+
+/// Indica si el código tiene la forma de un EAN-13 (trece dígitos)
+pub fn is_ean13(code: &str) -> bool {
+    code.len() == 13 && code.bytes().all(|byte| byte.is_ascii_digit())
+}
+
+/// Valida el dígito verificador de un EAN-13: pesos 1 y 3 alternados sobre los
+/// primeros doce dígitos
+pub fn has_valid_check_digit(code: &str) -> bool {
+    if !is_ean13(code) {
+        return false;
+    }
+
+    let digits: Vec<u32> = code.bytes().map(|byte| u32::from(byte - b'0')).collect();
+    let sum: u32 = digits[..12]
+        .iter()
+        .enumerate()
+        .map(|(index, digit)| if index % 2 == 0 { *digit } else { digit * 3 })
+        .sum();
+
+    (10 - sum % 10) % 10 == digits[12]
+}
+
+/// Decodifica una etiqueta de báscula. Devuelve None si el prefijo no está configurado
+/// como peso ni como precio; el dígito verificador se valida antes.
+pub fn parse_scale_barcode(code: &str, config: &ScaleBarcodeConfig) -> Option<ScaleBarcode> {
+    if !is_ean13(code) {
+        return None;
+    }
+
+    let prefix: u8 = code[..2].parse().ok()?;
+    let raw_value: i64 = code[7..12].parse().ok()?;
+    let value = if config.weight_prefixes.contains(&prefix) {
+        ScaleValue::Weight(Decimal::new(raw_value, 3))
+    } else if config.price_prefixes.contains(&prefix) {
+        ScaleValue::Price(Decimal::new(raw_value, 2))
+    } else {
+        return None;
+    };
+
+    Some(ScaleBarcode {
+        plu: code[2..7].to_string(),
+        value,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> ScaleBarcodeConfig {
+        ScaleBarcodeConfig {
+            weight_prefixes: vec![20, 21, 22, 23, 24],
+            price_prefixes: vec![25, 26, 27, 28, 29],
+        }
+    }
+
+    #[test]
+    fn validates_ean13_check_digit() {
+        assert!(has_valid_check_digit("7501055300846"));
+        assert!(!has_valid_check_digit("7501055300847"));
+        assert!(!has_valid_check_digit("750105530084"));
+        assert!(!has_valid_check_digit("750105530084A"));
+    }
+
+    #[test]
+    fn decodes_weight_label() {
+        let barcode = parse_scale_barcode("2000123012346", &config()).unwrap();
+
+        assert_eq!(barcode.plu, "00123");
+        assert_eq!(barcode.value, ScaleValue::Weight(Decimal::new(1234, 3)));
+    }
+
+    #[test]
+    fn decodes_price_label() {
+        let barcode = parse_scale_barcode("2500123004599", &config()).unwrap();
+
+        assert_eq!(barcode.plu, "00123");
+        assert_eq!(barcode.value, ScaleValue::Price(Decimal::new(459, 2)));
+    }
+
+    #[test]
+    fn ignores_unconfigured_prefixes() {
+        assert_eq!(parse_scale_barcode("7501055300846", &config()), None);
+    }
+}
@@ -1,15 +1,175 @@
+use std::collections::HashMap;
+
 use sea_orm::{
-    ActiveModelTrait, ActiveValue, ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter,
-    QueryOrder, QuerySelect,
+    prelude::Decimal, ActiveModelTrait, ActiveValue, ColumnTrait, ConnectionTrait, EntityTrait,
+    PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, TransactionTrait,
 };
 
-use super::structs::{NewProduct, Product, ProductFilter, ProductListReturn, UpdateProduct};
-use crate::entities::{categories::Entity as Categories, prelude::Products, products};
+use super::barcode::{
+    has_valid_check_digit, is_ean13, parse_scale_barcode, ScaleBarcodeConfig, ScaleValue,
+};
+use super::structs::{
+    NewProduct, Product, ProductFilter, ProductListReturn, ProductLookup, ProductTax,
+    UpdateProduct, CODE_EXACT, CODE_SCALE_PRICE, CODE_SCALE_WEIGHT, QUANTITY_SCALE, TAX_IVA,
+    TAX_TYPES, UNITS_OF_MEASURE,
+};
+use crate::entities::{
+    categories::Entity as Categories,
+    prelude::{ProductTaxes, Products},
+    product_taxes, products,
+};
+use crate::sales::checkout::check_quantity;
+use crate::sales::pricing::compute_line;
 use crate::sessions::require_permission;
 use crate::AppState;
 
 const DB_ERROR: &str = "Error on DB connection";
 
+/// Valida la unidad de medida y que las existencias sean congruentes con ella.
+/// Las tarjetas de regalo se venden por pieza.
+fn validate_unit(
+    unit_of_measure: &str,
+    allow_fractions: bool,
+    is_gift_card: bool,
+    stock: Decimal,
+) -> Result<(), String> {
+    if !UNITS_OF_MEASURE.contains(&unit_of_measure) {
+        return Err(format!(
+            "Unidad de medida no válida. Opciones: {}",
+            UNITS_OF_MEASURE.join(", ")
+        ));
+    }
+    if is_gift_card && allow_fractions {
+        return Err("Las tarjetas de regalo no se venden en fracciones".to_string());
+    }
+    if stock < Decimal::ZERO {
+        return Err("Las existencias no pueden ser negativas".to_string());
+    }
+    if stock.round_dp(QUANTITY_SCALE) != stock {
+        return Err(format!(
+            "Las existencias admiten a lo más {} decimales",
+            QUANTITY_SCALE
+        ));
+    }
+    if !allow_fractions && !stock.fract().is_zero() {
+        return Err(
+            "Las existencias deben ser enteras si el producto no se vende en fracciones"
+                .to_string(),
+        );
+    }
+
+    Ok(())
+}
+
+/// Valida los impuestos de un producto: tipos conocidos sin repetir, tasas entre 0 y
+/// 100% y el mismo criterio de precio con o sin impuestos para todos. La venta de una
+/// tarjeta de regalo no causa IVA ni IEPS: se gravan los productos al canjearla.
+fn validate_taxes(taxes: &[ProductTax], is_gift_card: bool) -> Result<(), String> {
+    for (index, tax) in taxes.iter().enumerate() {
+        if !TAX_TYPES.contains(&tax.tax_type.as_str()) {
+            return Err(format!(
+                "Impuesto no válido. Opciones: {}",
+                TAX_TYPES.join(", ")
+            ));
+        }
+        if taxes[..index]
+            .iter()
+            .any(|other| other.tax_type == tax.tax_type)
+        {
+            return Err(format!(
+                "El impuesto {} está repetido",
+                tax.tax_type.to_uppercase()
+            ));
+        }
+        if tax.rate < Decimal::ZERO || tax.rate > Decimal::ONE_HUNDRED {
+            return Err("La tasa del impuesto debe estar entre 0 y 100%".to_string());
+        }
+        if tax.is_exempt && !tax.rate.is_zero() {
+            return Err(format!(
+                "El {} exento no lleva tasa; use tasa 0% si no es exento",
+                tax.tax_type.to_uppercase()
+            ));
+        }
+        if is_gift_card && !tax.is_exempt {
+            return Err(format!(
+                "Las tarjetas de regalo no causan {}; los impuestos se cobran al canjearlas",
+                tax.tax_type.to_uppercase()
+            ));
+        }
+    }
+
+    if taxes
+        .iter()
+        .any(|tax| tax.is_included != taxes[0].is_included)
+    {
+        return Err(
+            "Todos los impuestos del producto deben estar incluidos o excluidos del precio"
+                .to_string(),
+        );
+    }
+
+    Ok(())
+}
+
+/// Tasa de IVA que se conserva en products.tax para los cálculos de una sola tasa
+fn iva_rate(taxes: &[ProductTax]) -> Decimal {
+    taxes
+        .iter()
+        .find(|tax| tax.tax_type == TAX_IVA && !tax.is_exempt)
+        .map_or(Decimal::ZERO, |tax| tax.rate / Decimal::from(100))
+}
+
+/// Reemplaza los impuestos de un producto
+async fn save_product_taxes<C: ConnectionTrait>(
+    db: &C,
+    product_id: i32,
+    taxes: &[ProductTax],
+) -> Result<Vec<product_taxes::Model>, String> {
+    ProductTaxes::delete_many()
+        .filter(product_taxes::Column::ProductId.eq(product_id))
+        .exec(db)
+        .await
+        .map_err(|_| DB_ERROR)?;
+
+    let mut saved = Vec::with_capacity(taxes.len());
+    for tax in taxes {
+        let model = product_taxes::ActiveModel {
+            product_id: ActiveValue::Set(product_id),
+            tax_type: ActiveValue::Set(tax.tax_type.clone()),
+            rate: ActiveValue::Set(tax.rate / Decimal::from(100)), // Porcentaje a decimal
+            is_included: ActiveValue::Set(tax.is_included),
+            is_exempt: ActiveValue::Set(tax.is_exempt),
+            ..Default::default()
+        }
+        .insert(db)
+        .await
+        .map_err(|e| format!("Error al guardar los impuestos del producto: {:?}", e))?;
+        saved.push(model);
+    }
+
+    Ok(saved)
+}
+
+/// Impuestos configurados de varios productos, agrupados por producto
+pub async fn load_product_taxes<C: ConnectionTrait>(
+    db: &C,
+    product_ids: &[i32],
+) -> Result<HashMap<i32, Vec<product_taxes::Model>>, String> {
+    let mut taxes: HashMap<i32, Vec<product_taxes::Model>> = HashMap::new();
+
+    for tax in ProductTaxes::find()
+        .filter(product_taxes::Column::ProductId.is_in(product_ids.to_vec()))
+        .order_by_asc(product_taxes::Column::Id)
+        .all(db)
+        .await
+        .map_err(|_| DB_ERROR)?
+    {
+        taxes.entry(tax.product_id).or_default().push(tax);
+    }
+
+    Ok(taxes)
+}
+
 /// Obtiene una página de productos filtrados por estado (activo/inactivo).
 #[tauri::command]
 pub async fn get_products(
@@ -40,9 +200,18 @@ pub async fn get_products(
         .await
         .map_err(|_| DB_ERROR)?;
 
+    let product_ids: Vec<i32> = products_with_categories
+        .iter()
+        .map(|(product, _)| product.id)
+        .collect();
+    let mut taxes = load_product_taxes(db, &product_ids).await?;
+
     let products = products_with_categories
         .into_iter()
-        .map(|(product, category)| Product::from_with_category(product, category))
+        .map(|(product, category)| {
+            let product_taxes = taxes.remove(&product.id).unwrap_or_default();
+            Product::from_with_category(product, category).with_taxes(product_taxes)
+        })
         .collect();
 
     Ok(ProductListReturn {
@@ -52,6 +221,123 @@ pub async fn get_products(
     })
 }
 
+/// Busca un producto por código de barras para agregarlo a la venta. Si no hay
+/// coincidencia exacta, decodifica las etiquetas de báscula EAN-13 (prefijos 20–29)
+/// y regresa la cantidad o el precio impreso en ellas.
+#[tauri::command]
+pub async fn get_product_by_code(
+    state: tauri::State<'_, AppState>,
+    code: String,
+) -> Result<ProductLookup, String> {
+    require_permission(&state, "products.view")?;
+    let db = &state.database;
+
+    let code = code.trim();
+    if code.is_empty() {
+        return Err("Debe indicar el código de barras".to_string());
+    }
+
+    // 1. Coincidencia exacta con el código del producto
+    let exact = Products::find()
+        .find_also_related(Categories)
+        .filter(products::Column::Code.eq(code))
+        .one(db)
+        .await
+        .map_err(|_| DB_ERROR)?;
+
+    if let Some((product, category)) = exact {
+        if !product.is_active {
+            return Err(format!("El producto '{}' no está disponible", product.name));
+        }
+        let taxes = load_product_taxes(db, &[product.id])
+            .await?
+            .remove(&product.id)
+            .unwrap_or_default();
+        return Ok(ProductLookup {
+            product: Product::from_with_category(product, category).with_taxes(taxes),
+            code_type: CODE_EXACT.to_string(),
+            plu: None,
+            quantity: None,
+            embedded_price: None,
+        });
+    }
+
+    // 2. Un EAN-13 mal leído se rechaza antes de interpretarlo
+    if is_ean13(code) && !has_valid_check_digit(code) {
+        return Err(format!(
+            "El código {} tiene un dígito verificador inválido",
+            code
+        ));
+    }
+
+    let barcode = parse_scale_barcode(code, &ScaleBarcodeConfig::from_env())
+        .ok_or(format!("No existe un producto con el código {}", code))?;
+
+    // 3. El PLU de la báscula se registra como código del producto, con o sin ceros
+    let plu_without_zeros = barcode.plu.trim_start_matches('0').to_string();
+    let (product, category) = Products::find()
+        .find_also_related(Categories)
+        .filter(products::Column::Code.is_in([barcode.plu.clone(), plu_without_zeros]))
+        .one(db)
+        .await
+        .map_err(|_| DB_ERROR)?
+        .ok_or(format!("No existe un producto con el PLU {}", barcode.plu))?;
+
+    if !product.is_active {
+        return Err(format!("El producto '{}' no está disponible", product.name));
+    }
+
+    let taxes = load_product_taxes(db, &[product.id])
+        .await?
+        .remove(&product.id)
+        .unwrap_or_default();
+
+    // 4. Convertir el valor de la etiqueta a cantidad en la unidad del producto
+    let (code_type, quantity, embedded_price) = match barcode.value {
+        ScaleValue::Weight(kilograms) => {
+            let quantity = match product.unit_of_measure.as_str() {
+                "kg" => kilograms,
+                "g" => kilograms * Decimal::from(1000),
+                _ => {
+                    return Err(format!(
+                        "El producto '{}' no se vende por peso",
+                        product.name
+                    ))
+                }
+            };
+            (CODE_SCALE_WEIGHT, quantity, None)
+        }
+        ScaleValue::Price(price) => {
+            // Redondear a piezas enteras cobraría un importe distinto al de la etiqueta
+            if !product.allow_fractions {
+                return Err(format!(
+                    "El producto '{}' no se vende en fracciones; no admite etiquetas con importe",
+                    product.name
+                ));
+            }
+            // La etiqueta trae el importe con impuestos
+            let unit_price = compute_line(product.price, Decimal::ONE, &taxes, Decimal::ZERO).total;
+            if unit_price <= Decimal::ZERO {
+                return Err(format!("El producto '{}' no tiene precio", product.name));
+            }
+            (
+                CODE_SCALE_PRICE,
+                (price / unit_price).round_dp(QUANTITY_SCALE),
+                Some(price),
+            )
+        }
+    };
+    check_quantity(&product, quantity)?;
+
+    Ok(ProductLookup {
+        product: Product::from_with_category(product, category).with_taxes(taxes),
+        code_type: code_type.to_string(),
+        plu: Some(barcode.plu),
+        quantity: Some(quantity),
+        embedded_price,
+    })
+}
+
 #[tauri::command]
 pub async fn create_product(
     state: tauri::State<'_, AppState>,
@@ -70,13 +356,50 @@ pub async fn create_product(
     if product_barcode.is_some() {
         return Err("El código de barras registrado ya existe".to_string());
     }
+
+    validate_unit(
+        product_data.unit_of_measure.as_deref().unwrap_or("piece"),
+        product_data.allow_fractions.unwrap_or(false),
+        product_data.is_gift_card.unwrap_or(false),
+        product_data.stock,
+    )?;
+
+    // Sin impuestos explícitos, el producto causa IVA a la tasa de `tax`; las tarjetas
+    // de regalo no llevan impuestos
+    let is_gift_card = product_data.is_gift_card.unwrap_or(false);
+    let taxes = product_data.taxes.clone().unwrap_or_else(|| {
+        if is_gift_card {
+            return Vec::new();
+        }
+        vec![ProductTax {
+            tax_type: TAX_IVA.to_string(),
+            rate: product_data.tax,
+            is_included: false,
+            is_exempt: false,
+        }]
+    });
+    validate_taxes(&taxes, is_gift_card)?;
+
+    let txn = db
+        .begin()
+        .await
+        .map_err(|_| "Error al iniciar transacción")?;
+
     // Inserta y retorna el modelo insertado directamente
-    let inserted: products::Model = Products::insert(products::ActiveModel::from(product_data))
-        .exec_with_returning(db)
+    let mut active_model = products::ActiveModel::from(product_data);
+    active_model.tax = ActiveValue::Set(iva_rate(&taxes));
+    let inserted: products::Model = Products::insert(active_model)
+        .exec_with_returning(&txn)
         .await
         .map_err(|e| format!("Error al insertar el producto: {:?}", e))?;
 
-    Ok(Product::from(inserted))
+    let saved_taxes = save_product_taxes(&txn, inserted.id, &taxes).await?;
+
+    txn.commit()
+        .await
+        .map_err(|_| "Error al confirmar la transacción")?;
+
+    Ok(Product::from(inserted).with_taxes(saved_taxes))
 }
 
 #[tauri::command]
@@ -91,18 +414,82 @@ pub async fn update_product(
     let db_product = Products::find_by_id(id_product)
         .one(db)
         .await
-        .map_err(|_| DB_ERROR)?;
+        .map_err(|_| DB_ERROR)?
+        .ok_or("No se encontró producto a modificar")?;
 
-    if db_product.is_none() {
-        return Err("No se encontró producto a modificar".to_string());
-    }
+    // La unidad se valida con los valores resultantes de la actualización
+    validate_unit(
+        update_data
+            .unit_of_measure
+            .as_deref()
+            .unwrap_or(&db_product.unit_of_measure),
+        update_data
+            .allow_fractions
+            .unwrap_or(db_product.allow_fractions),
+        update_data.is_gift_card.unwrap_or(db_product.is_gift_card),
+        update_data.stock.unwrap_or(db_product.stock),
+    )?;
+
+    // Con solo `tax` se actualiza la tasa de IVA y se conservan los demás impuestos
+    let current_taxes: Vec<ProductTax> = load_product_taxes(db, &[id_product])
+        .await?
+        .remove(&id_product)
+        .unwrap_or_default()
+        .into_iter()
+        .map(ProductTax::from)
+        .collect();
+    let taxes = match (update_data.taxes.clone(), update_data.tax) {
+        (Some(taxes), _) => Some(taxes),
+        (None, Some(rate)) => {
+            let mut taxes = current_taxes.clone();
+            match taxes.iter_mut().find(|tax| tax.tax_type == TAX_IVA) {
+                Some(iva) => {
+                    iva.rate = rate;
+                    iva.is_exempt = false;
+                }
+                None => taxes.push(ProductTax {
+                    tax_type: TAX_IVA.to_string(),
+                    rate,
+                    is_included: current_taxes.first().is_some_and(|tax| tax.is_included),
+                    is_exempt: false,
+                }),
+            }
+            Some(taxes)
+        }
+        (None, None) => None,
+    };
+    // Se validan también los impuestos vigentes si el producto pasa a ser tarjeta de regalo
+    validate_taxes(
+        taxes.as_ref().unwrap_or(&current_taxes),
+        update_data.is_gift_card.unwrap_or(db_product.is_gift_card),
+    )?;
+
+    let txn = db
+        .begin()
+        .await
+        .map_err(|_| "Error al iniciar transacción")?;
 
     let mut active_model = products::ActiveModel::from(update_data);
     active_model.id = ActiveValue::Set(id_product);
+    if let Some(taxes) = &taxes {
+        active_model.tax = ActiveValue::Set(iva_rate(taxes));
+    }
+
+    let updated = active_model.update(&txn).await.map_err(|_| DB_ERROR)?;
 
-    let updated = active_model.update(db).await.map_err(|_| DB_ERROR)?;
+    let saved_taxes = match &taxes {
+        Some(taxes) => save_product_taxes(&txn, id_product, taxes).await?,
+        None => load_product_taxes(&txn, &[id_product])
+            .await?
+            .remove(&id_product)
+            .unwrap_or_default(),
+    };
+
+    txn.commit()
+        .await
+        .map_err(|_| "Error al confirmar la transacción")?;
 
-    Ok(Product::from(updated))
+    Ok(Product::from(updated).with_taxes(saved_taxes))
 }
 
 #[tauri::command]
@@ -1,3 +1,4 @@
+pub mod barcode;
 pub mod handlers;
 pub mod structs;
 
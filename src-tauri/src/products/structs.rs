@@ -1,7 +1,44 @@
-use crate::entities::{categories, products::{self, ActiveModel}};
+use crate::entities::{
+    categories, product_taxes,
+    products::{self, ActiveModel},
+};
 use sea_orm::{prelude::Decimal, ActiveValue::Set};
 use serde::{Deserialize, Serialize};
 
+/// Unidades de medida admitidas: pieza, kilogramo, gramo, litro y metro
+pub const UNITS_OF_MEASURE: [&str; 5] = ["piece", "kg", "g", "l", "m"];
+
+/// Máximo de decimales en cantidades y existencias (gramos en un producto por kilo)
+pub const QUANTITY_SCALE: u32 = 3;
+
+pub const TAX_IVA: &str = "iva";
+pub const TAX_IEPS: &str = "ieps";
+
+/// Impuestos que puede causar un producto
+pub const TAX_TYPES: [&str; 2] = [TAX_IVA, TAX_IEPS];
+
+/// Impuesto de un producto. La tasa se expresa en porcentaje, igual que `tax`.
+/// Exento no es lo mismo que tasa 0%: ambos no cobran impuesto, pero el CFDI y
+/// las declaraciones los distinguen.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ProductTax {
+    pub tax_type: String, // iva, ieps
+    pub rate: Decimal,
+    pub is_included: bool, // El precio del producto ya incluye el impuesto
+    pub is_exempt: bool,
+}
+
+impl From<product_taxes::Model> for ProductTax {
+    fn from(value: product_taxes::Model) -> Self {
+        Self {
+            tax_type: value.tax_type,
+            rate: value.rate * Decimal::from(100),
+            is_included: value.is_included,
+            is_exempt: value.is_exempt,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Product {
     pub id: i32,
@@ -9,11 +46,17 @@ pub struct Product {
     pub category_id: Option<i32>,
     pub category_name: Option<String>,
     pub code: String,
-    pub stock: i32,
+    pub stock: Decimal,
     pub is_active: bool,
     pub price: Decimal,
     pub cost: Decimal,
     pub tax: Decimal,
+    pub sat_product_key: String,
+    pub sat_unit_key: String,
+    pub is_gift_card: bool, // Al venderse emite una tarjeta de regalo
+    pub unit_of_measure: String,
+    pub allow_fractions: bool, // Se vende en cantidades fraccionarias (0.750 kg)
+    pub taxes: Vec<ProductTax>,
 }
 
 impl Product {
@@ -32,8 +75,20 @@ impl Product {
             price: product.price,
             cost: product.cost,
             tax: product.tax * Decimal::from(100),
+            sat_product_key: product.sat_product_key,
+            sat_unit_key: product.sat_unit_key,
+            is_gift_card: product.is_gift_card,
+            unit_of_measure: product.unit_of_measure,
+            allow_fractions: product.allow_fractions,
+            taxes: Vec::new(),
         }
     }
+
+    /// Agrega los impuestos configurados del producto
+    pub fn with_taxes(mut self, taxes: Vec<product_taxes::Model>) -> Self {
+        self.taxes = taxes.into_iter().map(ProductTax::from).collect();
+        self
+    }
 }
 
 impl From<products::Model> for Product {
@@ -49,6 +104,12 @@ impl From<products::Model> for Product {
             price: value.price,
             cost: value.cost,
             tax: value.tax * Decimal::from(100),
+            sat_product_key: value.sat_product_key,
+            sat_unit_key: value.sat_unit_key,
+            is_gift_card: value.is_gift_card,
+            unit_of_measure: value.unit_of_measure,
+            allow_fractions: value.allow_fractions,
+            taxes: Vec::new(),
         }
     }
 }
@@ -58,16 +119,22 @@ pub struct NewProduct {
     pub name: String,
     pub category_id: Option<i32>,
     pub code: String,
-    pub stock: i32,
+    pub stock: Decimal,
     pub price: Decimal,
     pub cost: Decimal,
     pub tax: Decimal,
+    pub sat_product_key: Option<String>, // ClaveProdServ, por defecto 01010101
+    pub sat_unit_key: Option<String>,    // ClaveUnidad, por defecto H87 (pieza)
+    pub is_gift_card: Option<bool>,
+    pub unit_of_measure: Option<String>, // Por defecto piece
+    pub allow_fractions: Option<bool>,
+    pub taxes: Option<Vec<ProductTax>>, // Por defecto, IVA a la tasa de `tax`
     pub created_by: String,
 }
 
 impl From<NewProduct> for products::ActiveModel {
     fn from(value: NewProduct) -> Self {
-        ActiveModel {
+        let mut active_model = ActiveModel {
             name: Set(value.name),
             category_id: Set(value.category_id),
             code: Set(value.code),
@@ -78,7 +145,26 @@ impl From<NewProduct> for products::ActiveModel {
             created_by: Set(value.created_by.clone()),
             updated_by: Set(value.created_by), // Al crear, created_by = updated_by
             ..Default::default()
+        };
+
+        // Si no se especifican, se usan los valores por defecto de la base de datos
+        if let Some(sat_product_key) = value.sat_product_key {
+            active_model.sat_product_key = Set(sat_product_key);
         }
+        if let Some(sat_unit_key) = value.sat_unit_key {
+            active_model.sat_unit_key = Set(sat_unit_key);
+        }
+        if let Some(is_gift_card) = value.is_gift_card {
+            active_model.is_gift_card = Set(is_gift_card);
+        }
+        if let Some(unit_of_measure) = value.unit_of_measure {
+            active_model.unit_of_measure = Set(unit_of_measure);
+        }
+        if let Some(allow_fractions) = value.allow_fractions {
+            active_model.allow_fractions = Set(allow_fractions);
+        }
+
+        active_model
     }
 }
 
@@ -87,11 +173,17 @@ pub struct UpdateProduct {
     pub name: Option<String>,
     pub category_id: Option<i32>,
     pub code: Option<String>,
-    pub stock: Option<i32>,
+    pub stock: Option<Decimal>,
     pub is_active: Option<bool>,
     pub price: Option<Decimal>,
     pub cost: Option<Decimal>,
     pub tax: Option<Decimal>,
+    pub sat_product_key: Option<String>,
+    pub sat_unit_key: Option<String>,
+    pub is_gift_card: Option<bool>,
+    pub unit_of_measure: Option<String>,
+    pub allow_fractions: Option<bool>,
+    pub taxes: Option<Vec<ProductTax>>, // Reemplaza todos los impuestos del producto
     pub updated_by: String,
 }
 
@@ -123,6 +215,21 @@ impl From<UpdateProduct> for products::ActiveModel {
         if let Some(tax) = value.tax {
             active_model.tax = Set(tax / Decimal::from(100)); // Convertir porcentaje a decimal
         }
+        if let Some(sat_product_key) = value.sat_product_key {
+            active_model.sat_product_key = Set(sat_product_key);
+        }
+        if let Some(sat_unit_key) = value.sat_unit_key {
+            active_model.sat_unit_key = Set(sat_unit_key);
+        }
+        if let Some(is_gift_card) = value.is_gift_card {
+            active_model.is_gift_card = Set(is_gift_card);
+        }
+        if let Some(unit_of_measure) = value.unit_of_measure {
+            active_model.unit_of_measure = Set(unit_of_measure);
+        }
+        if let Some(allow_fractions) = value.allow_fractions {
+            active_model.allow_fractions = Set(allow_fractions);
+        }
 
         // Siempre actualizar updated_by
         active_model.updated_by = Set(value.updated_by);
@@ -150,3 +257,17 @@ pub struct ProductListReturn {
     pub total_pages: u64,
     pub total_items: u64,
 }
+
+pub const CODE_EXACT: &str = "exact"; // Coincide con products.code
+pub const CODE_SCALE_WEIGHT: &str = "scale_weight"; // Etiqueta de báscula con peso
+pub const CODE_SCALE_PRICE: &str = "scale_price"; // Etiqueta de báscula con precio
+
+/// Producto encontrado por código de barras, con la cantidad o el precio de la etiqueta
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProductLookup {
+    pub product: Product,
+    pub code_type: String,
+    pub plu: Option<String>,
+    pub quantity: Option<Decimal>, // Cantidad a agregar en la unidad del producto
+    pub embedded_price: Option<Decimal>, // Importe impreso en la etiqueta, con impuestos
+}
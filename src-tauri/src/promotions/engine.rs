@@ -0,0 +1,256 @@
+//! Evaluación de promociones sobre las líneas de una venta
+
+use chrono::{Datelike, NaiveDateTime};
+use sea_orm::prelude::Decimal;
+
+use super::structs::{parse_weekdays, PromotionType};
+use crate::entities::promotions;
+
+/// Línea de venta candidata a recibir una promoción
+#[derive(Debug, Clone)]
+pub struct PromotionLine {
+    pub product_id: i32,
+    pub category_id: Option<i32>,
+    pub unit_price: Decimal,
+    pub quantity: Decimal, // Puede ser fraccionaria en productos a granel
+}
+
+/// Promoción aplicada a una línea y su descuento antes de impuestos
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AppliedPromotion {
+    pub promotion_id: i32,
+    pub amount: Decimal,
+}
+
+/// Indica si la promoción está vigente en la fecha y hora indicadas
+pub fn is_in_window(promotion: &promotions::Model, now: NaiveDateTime) -> bool {
+    if !promotion.is_active {
+        return false;
+    }
+
+    let date = now.date();
+    if promotion
+        .starts_on
+        .is_some_and(|starts_on| date < starts_on)
+        || promotion.ends_on.is_some_and(|ends_on| date > ends_on)
+    {
+        return false;
+    }
+
+    let weekdays = parse_weekdays(promotion.weekdays.as_deref());
+    if !weekdays.is_empty() && !weekdays.contains(&now.weekday().number_from_monday()) {
+        return false;
+    }
+
+    let time = now.time();
+    if promotion
+        .starts_at
+        .is_some_and(|starts_at| time < starts_at)
+        || promotion.ends_at.is_some_and(|ends_at| time > ends_at)
+    {
+        return false;
+    }
+
+    true
+}
+
+fn matches(promotion: &promotions::Model, line: &PromotionLine) -> bool {
+    match (promotion.product_id, promotion.category_id) {
+        (Some(product_id), _) => line.product_id == product_id,
+        (None, Some(category_id)) => line.category_id == Some(category_id),
+        (None, None) => false,
+    }
+}
+
+/// Calcula el descuento que otorgaría una promoción sobre las líneas disponibles.
+/// Devuelve el descuento por línea (índice, importe) de todas las líneas que participan.
+fn evaluate(
+    promotion: &promotions::Model,
+    lines: &[PromotionLine],
+    available: &[bool],
+) -> Vec<(usize, Decimal)> {
+    let participants: Vec<usize> = (0..lines.len())
+        .filter(|&index| available[index] && matches(promotion, &lines[index]))
+        .collect();
+
+    match PromotionType::parse(&promotion.promotion_type) {
+        Some(PromotionType::Percent) => {
+            let percent = promotion.discount_percent.unwrap_or_default();
+            participants
+                .into_iter()
+                .map(|index| {
+                    let line = &lines[index];
+                    let gross = line.unit_price * line.quantity;
+                    (index, (gross * percent / Decimal::ONE_HUNDRED).round_dp(2))
+                })
+                .collect()
+        }
+        Some(PromotionType::Nxm) => {
+            let (Some(buy), Some(pay)) = (promotion.buy_quantity, promotion.pay_quantity) else {
+                return Vec::new();
+            };
+            if buy <= 0 || pay < 0 || pay >= buy {
+                return Vec::new();
+            }
+
+            // Las piezas de todas las líneas participantes se agrupan y las más baratas salen gratis;
+            // solo cuentan grupos completos de N unidades
+            let total_units: Decimal = participants.iter().map(|&i| lines[i].quantity).sum();
+            let mut free_units =
+                (total_units / Decimal::from(buy)).floor() * Decimal::from(buy - pay);
+
+            let mut by_price = participants.clone();
+            by_price.sort_by(|a, b| lines[*a].unit_price.cmp(&lines[*b].unit_price));
+
+            let mut amounts: Vec<(usize, Decimal)> =
+                participants.iter().map(|&i| (i, Decimal::ZERO)).collect();
+            for index in by_price {
+                if free_units <= Decimal::ZERO {
+                    break;
+                }
+                let units = free_units.min(lines[index].quantity);
+                free_units -= units;
+                if let Some(entry) = amounts.iter_mut().find(|(i, _)| *i == index) {
+                    entry.1 = (lines[index].unit_price * units).round_dp(2);
+                }
+            }
+
+            amounts
+        }
+        None => Vec::new(),
+    }
+}
+
+/// Asigna a cada línea como máximo una promoción, eligiendo primero la que
+/// otorgue el mayor descuento total.
+pub fn apply_promotions(
+    lines: &[PromotionLine],
+    promotions: &[promotions::Model],
+    now: NaiveDateTime,
+) -> Vec<Option<AppliedPromotion>> {
+    let mut result = vec![None; lines.len()];
+    let mut available = vec![true; lines.len()];
+    let mut candidates: Vec<&promotions::Model> = promotions
+        .iter()
+        .filter(|promotion| is_in_window(promotion, now))
+        .collect();
+
+    loop {
+        let best = candidates
+            .iter()
+            .enumerate()
+            .map(|(position, promotion)| {
+                let amounts = evaluate(promotion, lines, &available);
+                let total: Decimal = amounts.iter().map(|(_, amount)| *amount).sum();
+                (position, amounts, total)
+            })
+            .filter(|(_, _, total)| *total > Decimal::ZERO)
+            .max_by(|a, b| a.2.cmp(&b.2));
+
+        let Some((position, amounts, _)) = best else {
+            break;
+        };
+
+        let promotion = candidates.remove(position);
+        // Todas las líneas que participan registran la promoción, aunque en un NxM
+        // algunas no tengan piezas gratis
+        for (index, amount) in amounts {
+            available[index] = false;
+            result[index] = Some(AppliedPromotion {
+                promotion_id: promotion.id,
+                amount: amount.round_dp(2),
+            });
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveDate, Utc};
+
+    fn nxm(id: i32, product_id: i32, buy: i32, pay: i32) -> promotions::Model {
+        let now = Utc::now().fixed_offset();
+        promotions::Model {
+            id,
+            name: format!("{}x{}", buy, pay),
+            promotion_type: "nxm".to_string(),
+            buy_quantity: Some(buy),
+            pay_quantity: Some(pay),
+            discount_percent: None,
+            product_id: Some(product_id),
+            category_id: None,
+            starts_on: None,
+            ends_on: None,
+            weekdays: None,
+            starts_at: None,
+            ends_at: None,
+            is_active: true,
+            created_at: now,
+            updated_at: now,
+            created_by: "admin".to_string(),
+            updated_by: "admin".to_string(),
+        }
+    }
+
+    fn line(product_id: i32, unit_price: i64, quantity: i64) -> PromotionLine {
+        PromotionLine {
+            product_id,
+            category_id: None,
+            unit_price: Decimal::from(unit_price),
+            quantity: Decimal::from(quantity),
+        }
+    }
+
+    fn now() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2026, 3, 2)
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn nxm_counts_only_complete_groups() {
+        let lines = [line(1, 10, 5)];
+        let applied = apply_promotions(&lines, &[nxm(7, 1, 3, 2)], now());
+
+        assert_eq!(
+            applied,
+            [Some(AppliedPromotion {
+                promotion_id: 7,
+                amount: Decimal::from(10),
+            })]
+        );
+    }
+
+    #[test]
+    fn nxm_gives_away_cheapest_units_across_lines() {
+        let lines = [line(1, 10, 2), line(1, 8, 1), line(2, 5, 3)];
+        let applied = apply_promotions(&lines, &[nxm(7, 1, 3, 2)], now());
+
+        assert_eq!(
+            applied,
+            [
+                Some(AppliedPromotion {
+                    promotion_id: 7,
+                    amount: Decimal::ZERO,
+                }),
+                Some(AppliedPromotion {
+                    promotion_id: 7,
+                    amount: Decimal::from(8),
+                }),
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn nxm_ignores_incomplete_groups() {
+        let lines = [line(1, 10, 2)];
+        let applied = apply_promotions(&lines, &[nxm(7, 1, 3, 2)], now());
+
+        assert_eq!(applied, [None]);
+    }
+}
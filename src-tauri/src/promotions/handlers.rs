@@ -0,0 +1,273 @@
+use chrono::{NaiveDate, NaiveTime};
+use sea_orm::{
+    prelude::Decimal, ActiveModelTrait, ActiveValue::Set, ColumnTrait, EntityTrait, QueryFilter,
+    QueryOrder,
+};
+
+use super::structs::{Promotion, PromotionFilter, PromotionInput, PromotionType};
+use crate::entities::{
+    prelude::{Categories, Products, Promotions},
+    promotions,
+};
+use crate::sessions::require_permission;
+use crate::AppState;
+
+const DB_ERROR: &str = "Error en la base de datos";
+
+/// Valores ya validados de una promoción
+struct ValidatedPromotion {
+    starts_on: Option<NaiveDate>,
+    ends_on: Option<NaiveDate>,
+    weekdays: Option<String>,
+    starts_at: Option<NaiveTime>,
+    ends_at: Option<NaiveTime>,
+}
+
+fn parse_date(value: Option<&String>) -> Result<Option<NaiveDate>, String> {
+    value
+        .map(|date| {
+            NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                .map_err(|_| format!("Fecha inválida: {}", date))
+        })
+        .transpose()
+}
+
+fn parse_time(value: Option<&String>) -> Result<Option<NaiveTime>, String> {
+    value
+        .map(|time| {
+            NaiveTime::parse_from_str(time, "%H:%M").map_err(|_| format!("Hora inválida: {}", time))
+        })
+        .transpose()
+}
+
+/// Valida las reglas de la promoción y su vigencia
+async fn validate_input(
+    db: &sea_orm::DatabaseConnection,
+    input: &PromotionInput,
+) -> Result<ValidatedPromotion, String> {
+    if input.name.trim().is_empty() {
+        return Err("El nombre de la promoción es obligatorio".to_string());
+    }
+
+    match input.promotion_type {
+        PromotionType::Nxm => match (input.buy_quantity, input.pay_quantity) {
+            (Some(buy), Some(pay)) if buy > 1 && pay > 0 && pay < buy => {}
+            _ => {
+                return Err(
+                    "En una promoción NxM se debe llevar más piezas de las que se pagan"
+                        .to_string(),
+                )
+            }
+        },
+        PromotionType::Percent => match input.discount_percent {
+            Some(percent) if percent > Decimal::ZERO && percent <= Decimal::ONE_HUNDRED => {}
+            _ => return Err("El porcentaje de descuento debe estar entre 0 y 100".to_string()),
+        },
+    }
+
+    // La promoción debe apuntar a un producto o a una categoría existente
+    match (input.product_id, input.category_id) {
+        (Some(product_id), None) => {
+            Products::find_by_id(product_id)
+                .one(db)
+                .await
+                .map_err(|_| DB_ERROR)?
+                .ok_or("Producto no encontrado")?;
+        }
+        (None, Some(category_id)) => {
+            Categories::find_by_id(category_id)
+                .one(db)
+                .await
+                .map_err(|_| DB_ERROR)?
+                .ok_or("Categoría no encontrada")?;
+        }
+        _ => return Err("La promoción debe aplicar a un producto o a una categoría".to_string()),
+    }
+
+    let starts_on = parse_date(input.starts_on.as_ref())?;
+    let ends_on = parse_date(input.ends_on.as_ref())?;
+    if let (Some(starts_on), Some(ends_on)) = (starts_on, ends_on) {
+        if starts_on > ends_on {
+            return Err("La fecha de inicio no puede ser posterior a la de fin".to_string());
+        }
+    }
+
+    let starts_at = parse_time(input.starts_at.as_ref())?;
+    let ends_at = parse_time(input.ends_at.as_ref())?;
+    if let (Some(starts_at), Some(ends_at)) = (starts_at, ends_at) {
+        if starts_at > ends_at {
+            return Err("La hora de inicio no puede ser posterior a la de fin".to_string());
+        }
+    }
+
+    let weekdays = match &input.weekdays {
+        Some(days) if !days.is_empty() => {
+            if days.iter().any(|day| !(1..=7).contains(day)) {
+                return Err("Los días de la semana van de 1 (lunes) a 7 (domingo)".to_string());
+            }
+            let mut days = days.clone();
+            days.sort_unstable();
+            days.dedup();
+            Some(
+                days.iter()
+                    .map(|day| day.to_string())
+                    .collect::<Vec<_>>()
+                    .join(","),
+            )
+        }
+        _ => None,
+    };
+
+    Ok(ValidatedPromotion {
+        starts_on,
+        ends_on,
+        weekdays,
+        starts_at,
+        ends_at,
+    })
+}
+
+/// Copia los datos validados al modelo activo
+fn fill_active_model(
+    active_model: &mut promotions::ActiveModel,
+    input: PromotionInput,
+    validated: ValidatedPromotion,
+    user_id: String,
+) {
+    let is_nxm = input.promotion_type == PromotionType::Nxm;
+
+    active_model.name = Set(input.name.trim().to_string());
+    active_model.promotion_type = Set(input.promotion_type.as_str().to_string());
+    active_model.buy_quantity = Set(input.buy_quantity.filter(|_| is_nxm));
+    active_model.pay_quantity = Set(input.pay_quantity.filter(|_| is_nxm));
+    active_model.discount_percent = Set(input.discount_percent.filter(|_| !is_nxm));
+    active_model.product_id = Set(input.product_id);
+    active_model.category_id = Set(input.category_id);
+    active_model.starts_on = Set(validated.starts_on);
+    active_model.ends_on = Set(validated.ends_on);
+    active_model.weekdays = Set(validated.weekdays);
+    active_model.starts_at = Set(validated.starts_at);
+    active_model.ends_at = Set(validated.ends_at);
+    if let Some(is_active) = input.is_active {
+        active_model.is_active = Set(is_active);
+    }
+    active_model.updated_by = Set(user_id);
+}
+
+/// Obtiene las promociones con filtros opcionales
+#[tauri::command]
+pub async fn get_promotions(
+    state: tauri::State<'_, AppState>,
+    filters: PromotionFilter,
+) -> Result<Vec<Promotion>, String> {
+    require_permission(&state, "promotions.view")?;
+    let db = &state.database;
+
+    let mut query = Promotions::find();
+
+    if let Some(status) = filters.status {
+        query = query.filter(promotions::Column::IsActive.eq(status));
+    }
+    if let Some(product_id) = filters.product_id {
+        query = query.filter(promotions::Column::ProductId.eq(product_id));
+    }
+    if let Some(category_id) = filters.category_id {
+        query = query.filter(promotions::Column::CategoryId.eq(category_id));
+    }
+
+    let promotions_list = query
+        .order_by_asc(promotions::Column::Name)
+        .all(db)
+        .await
+        .map_err(|_| DB_ERROR)?;
+
+    Ok(promotions_list.into_iter().map(Promotion::from).collect())
+}
+
+/// Crea una nueva promoción
+#[tauri::command]
+pub async fn create_promotion(
+    state: tauri::State<'_, AppState>,
+    promotion_data: PromotionInput,
+) -> Result<Promotion, String> {
+    let session = require_permission(&state, "promotions.manage")?;
+    let db = &state.database;
+
+    let validated = validate_input(db, &promotion_data).await?;
+
+    let mut active_model = promotions::ActiveModel {
+        created_by: Set(session.user_id.clone()),
+        ..Default::default()
+    };
+    fill_active_model(
+        &mut active_model,
+        promotion_data,
+        validated,
+        session.user_id,
+    );
+
+    let inserted = active_model
+        .insert(db)
+        .await
+        .map_err(|e| format!("Error al registrar la promoción: {:?}", e))?;
+
+    Ok(Promotion::from(inserted))
+}
+
+/// Reemplaza las reglas de una promoción existente
+#[tauri::command]
+pub async fn update_promotion(
+    state: tauri::State<'_, AppState>,
+    id: i32,
+    promotion_data: PromotionInput,
+) -> Result<Promotion, String> {
+    let session = require_permission(&state, "promotions.manage")?;
+    let db = &state.database;
+
+    let promotion = Promotions::find_by_id(id)
+        .one(db)
+        .await
+        .map_err(|_| DB_ERROR)?
+        .ok_or("No se encontró la promoción a modificar".to_string())?;
+
+    let validated = validate_input(db, &promotion_data).await?;
+
+    let mut active_model: promotions::ActiveModel = promotion.into();
+    fill_active_model(
+        &mut active_model,
+        promotion_data,
+        validated,
+        session.user_id,
+    );
+
+    let updated = active_model.update(db).await.map_err(|_| DB_ERROR)?;
+
+    Ok(Promotion::from(updated))
+}
+
+/// Desactiva una promoción (soft delete). Las ventas que la usaron la conservan.
+#[tauri::command]
+pub async fn delete_promotion(
+    state: tauri::State<'_, AppState>,
+    id: i32,
+) -> Result<Promotion, String> {
+    let session = require_permission(&state, "promotions.manage")?;
+    let db = &state.database;
+
+    let promotion = Promotions::find_by_id(id)
+        .one(db)
+        .await
+        .map_err(|_| "Error al consultar el ID de la promoción".to_string())?
+        .ok_or("No se encontró la promoción seleccionada".to_string())?;
+
+    let mut active_model: promotions::ActiveModel = promotion.into();
+    active_model.is_active = Set(false);
+    active_model.updated_by = Set(session.user_id);
+
+    let updated = active_model
+        .update(db)
+        .await
+        .map_err(|_| "Ocurrió un error al eliminar la promoción".to_string())?;
+
+    Ok(Promotion::from(updated))
+}
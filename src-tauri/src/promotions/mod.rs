@@ -0,0 +1,5 @@
+pub mod engine;
+pub mod handlers;
+pub mod structs;
+
+pub use handlers as PromotionsHandler;
@@ -0,0 +1,107 @@
+use crate::entities::promotions;
+use sea_orm::prelude::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Tipo de promoción
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum PromotionType {
+    Nxm,     // Lleva N y paga M (ej: 3x2)
+    Percent, // Porcentaje de descuento
+}
+
+impl PromotionType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PromotionType::Nxm => "nxm",
+            PromotionType::Percent => "percent",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "nxm" => Some(PromotionType::Nxm),
+            "percent" => Some(PromotionType::Percent),
+            _ => None,
+        }
+    }
+}
+
+/// Promoción (para lectura/respuesta)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Promotion {
+    pub id: i32,
+    pub name: String,
+    pub promotion_type: String,
+    pub buy_quantity: Option<i32>,
+    pub pay_quantity: Option<i32>,
+    pub discount_percent: Option<Decimal>,
+    pub product_id: Option<i32>,
+    pub category_id: Option<i32>,
+    pub starts_on: Option<String>, // AAAA-MM-DD
+    pub ends_on: Option<String>,
+    pub weekdays: Vec<u32>, // 1 = lunes ... 7 = domingo; vacío = todos los días
+    pub starts_at: Option<String>, // HH:MM
+    pub ends_at: Option<String>,
+    pub is_active: bool,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+impl From<promotions::Model> for Promotion {
+    fn from(value: promotions::Model) -> Self {
+        Self {
+            weekdays: parse_weekdays(value.weekdays.as_deref()),
+            id: value.id,
+            name: value.name,
+            promotion_type: value.promotion_type,
+            buy_quantity: value.buy_quantity,
+            pay_quantity: value.pay_quantity,
+            discount_percent: value.discount_percent,
+            product_id: value.product_id,
+            category_id: value.category_id,
+            starts_on: value.starts_on.map(|date| date.to_string()),
+            ends_on: value.ends_on.map(|date| date.to_string()),
+            starts_at: value.starts_at.map(|time| time.format("%H:%M").to_string()),
+            ends_at: value.ends_at.map(|time| time.format("%H:%M").to_string()),
+            is_active: value.is_active,
+            created_at: value.created_at.to_string(),
+            updated_at: value.updated_at.to_string(),
+        }
+    }
+}
+
+/// Convierte la lista de días guardada ("1,2,3") a números
+pub fn parse_weekdays(weekdays: Option<&str>) -> Vec<u32> {
+    weekdays
+        .unwrap_or_default()
+        .split(',')
+        .filter_map(|day| day.trim().parse().ok())
+        .collect()
+}
+
+/// Datos para crear o reemplazar una promoción
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PromotionInput {
+    pub name: String,
+    pub promotion_type: PromotionType,
+    pub buy_quantity: Option<i32>,
+    pub pay_quantity: Option<i32>,
+    pub discount_percent: Option<Decimal>,
+    pub product_id: Option<i32>,
+    pub category_id: Option<i32>,
+    pub starts_on: Option<String>,
+    pub ends_on: Option<String>,
+    pub weekdays: Option<Vec<u32>>,
+    pub starts_at: Option<String>,
+    pub ends_at: Option<String>,
+    pub is_active: Option<bool>,
+}
+
+/// Filtros para obtener promociones
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PromotionFilter {
+    pub status: Option<bool>,
+    pub product_id: Option<i32>,
+    pub category_id: Option<i32>,
+}
@@ -0,0 +1,405 @@
+use std::collections::HashMap;
+
+use chrono::{Local, NaiveDate};
+use sea_orm::{
+    prelude::Decimal, ActiveModelTrait, ActiveValue::Set, ColumnTrait, ConnectionTrait,
+    EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, TransactionTrait,
+};
+
+use super::structs::{
+    ConvertQuoteRequest, CreateQuoteRequest, Quote, QuoteDetailResponse, QuoteFilter,
+    QuoteLineDetail, QuoteListResponse, STATUS_CONVERTED, STATUS_OPEN,
+};
+use crate::entities::{
+    customers,
+    prelude::{Customers, Products, QuoteDetailTaxes, QuoteDetails, Quotes, Users},
+    quote_detail_taxes, quote_details, quotes,
+};
+use crate::sales::checkout::price_sale_items;
+use crate::sales::structs::{CreateSaleRequest, CreateSaleResponse, DiscountRequest, SaleLineTax};
+use crate::sales::SalesHandler::register_sale;
+use crate::sessions::require_permission;
+use crate::AppState;
+
+const DB_ERROR: &str = "Error en la base de datos";
+
+/// Carga el detalle de una cotización; compartido con la impresión
+pub async fn load_quote_detail<C: ConnectionTrait>(
+    db: &C,
+    quote_id: i32,
+) -> Result<QuoteDetailResponse, String> {
+    let (quote, customer) = Quotes::find_by_id(quote_id)
+        .find_also_related(Customers)
+        .one(db)
+        .await
+        .map_err(|_| DB_ERROR)?
+        .ok_or("Cotización no encontrada")?;
+
+    let details = QuoteDetails::find()
+        .find_also_related(Products)
+        .filter(quote_details::Column::QuoteId.eq(quote.id))
+        .order_by_asc(quote_details::Column::Id)
+        .all(db)
+        .await
+        .map_err(|_| DB_ERROR)?;
+
+    // Desglose de impuestos de cada línea
+    let detail_ids: Vec<i32> = details.iter().map(|(detail, _)| detail.id).collect();
+    let mut line_taxes: HashMap<i32, Vec<SaleLineTax>> = HashMap::new();
+    for tax in QuoteDetailTaxes::find()
+        .filter(quote_detail_taxes::Column::QuoteDetailId.is_in(detail_ids))
+        .order_by_asc(quote_detail_taxes::Column::Id)
+        .all(db)
+        .await
+        .map_err(|_| DB_ERROR)?
+    {
+        line_taxes
+            .entry(tax.quote_detail_id)
+            .or_default()
+            .push(SaleLineTax::from(tax));
+    }
+
+    let lines = details
+        .into_iter()
+        .map(|(detail, product)| QuoteLineDetail {
+            id: detail.id,
+            product_id: detail.product_id,
+            product_name: product.as_ref().map(|p| p.name.clone()),
+            product_code: product.as_ref().map(|p| p.code.clone()),
+            unit_of_measure: product.map(|p| p.unit_of_measure),
+            quantity: detail.quantity,
+            unit_price: detail.unit_price,
+            price_override: detail.price_override,
+            discount: DiscountRequest::from_stored(
+                detail.discount_type.as_deref(),
+                detail.discount_value,
+            ),
+            discount_amount: detail.discount_amount,
+            ticket_discount_amount: detail.ticket_discount_amount,
+            promotion_id: detail.promotion_id,
+            promotion_discount_amount: detail.promotion_discount_amount,
+            subtotal: detail.subtotal,
+            tax_rate: detail.tax_rate,
+            tax_amount: detail.tax_amount,
+            taxes: line_taxes.remove(&detail.id).unwrap_or_default(),
+            total: detail.total,
+        })
+        .collect();
+
+    let created_by_name = Users::find_by_id(quote.created_by.clone())
+        .one(db)
+        .await
+        .map_err(|_| DB_ERROR)?
+        .map(|user| format!("{} {}", user.first_name, user.last_name));
+
+    Ok(QuoteDetailResponse {
+        quote: Quote::from_model(quote, customer.map(|c| c.name)),
+        created_by_name,
+        lines,
+    })
+}
+
+/// Marca una cotización abierta como convertida en la venta indicada.
+/// Bloquea la cotización para que no se convierta dos veces desde cajas distintas.
+pub async fn mark_quote_converted<C: ConnectionTrait>(
+    db: &C,
+    quote_id: i32,
+    sale_id: &str,
+    user_id: &str,
+) -> Result<(), String> {
+    let quote = Quotes::find_by_id(quote_id)
+        .lock_exclusive()
+        .one(db)
+        .await
+        .map_err(|_| DB_ERROR)?
+        .ok_or("Cotización no encontrada")?;
+
+    if quote.status != STATUS_OPEN {
+        return Err("La cotización ya fue convertida en venta".to_string());
+    }
+
+    let mut active_model: quotes::ActiveModel = quote.into();
+    active_model.status = Set(STATUS_CONVERTED.to_string());
+    active_model.sale_id = Set(Some(sale_id.to_string()));
+    active_model.updated_by = Set(user_id.to_string());
+
+    active_model
+        .update(db)
+        .await
+        .map_err(|e| format!("Error al vincular la cotización: {:?}", e))?;
+
+    Ok(())
+}
+
+/// Obtiene una página de cotizaciones con filtros opcionales
+#[tauri::command]
+pub async fn get_quotes(
+    state: tauri::State<'_, AppState>,
+    filters: QuoteFilter,
+) -> Result<QuoteListResponse, String> {
+    require_permission(&state, "quotes.view")?;
+    let db = &state.database;
+
+    let mut query = Quotes::find();
+
+    if let Some(customer_id) = filters.customer_id {
+        query = query.filter(quotes::Column::CustomerId.eq(customer_id));
+    }
+    if let Some(status) = filters.status {
+        query = query.filter(quotes::Column::Status.eq(status));
+    }
+
+    // Contar total de items para paginación
+    let total_items = query.clone().count(db).await.map_err(|_| DB_ERROR)?;
+
+    let total_pages = if filters.limit > 0 {
+        (total_items as f64 / filters.limit as f64).ceil() as u64
+    } else {
+        1
+    };
+
+    let offset = (filters.page.saturating_sub(1)) * filters.limit;
+    let quotes_list = query
+        .order_by_desc(quotes::Column::CreatedAt)
+        .offset(offset)
+        .limit(filters.limit)
+        .all(db)
+        .await
+        .map_err(|_| DB_ERROR)?;
+
+    let customer_ids: Vec<i32> = quotes_list.iter().map(|q| q.customer_id).collect();
+    let customer_names: HashMap<i32, String> = Customers::find()
+        .filter(customers::Column::Id.is_in(customer_ids))
+        .all(db)
+        .await
+        .map_err(|_| DB_ERROR)?
+        .into_iter()
+        .map(|customer| (customer.id, customer.name))
+        .collect();
+
+    Ok(QuoteListResponse {
+        quotes: quotes_list
+            .into_iter()
+            .map(|quote| {
+                let customer_name = customer_names.get(&quote.customer_id).cloned();
+                Quote::from_model(quote, customer_name)
+            })
+            .collect(),
+        total_pages,
+        total_items,
+    })
+}
+
+/// Obtiene el detalle de una cotización
+#[tauri::command]
+pub async fn get_quote_detail(
+    state: tauri::State<'_, AppState>,
+    id: i32,
+) -> Result<QuoteDetailResponse, String> {
+    require_permission(&state, "quotes.view")?;
+
+    load_quote_detail(&state.database, id).await
+}
+
+/// Emite una cotización con los precios, promociones y descuentos vigentes.
+/// No aparta inventario; el stock se valida al convertirla en venta.
+#[tauri::command]
+pub async fn create_quote(
+    state: tauri::State<'_, AppState>,
+    request: CreateQuoteRequest,
+) -> Result<QuoteDetailResponse, String> {
+    // 1. Validar permiso
+    let session = require_permission(&state, "quotes.create")?;
+    let db = &state.database;
+
+    // 2. Validar items, precios manuales y vigencia
+    if request.items.is_empty() {
+        return Err("La cotización debe tener al menos un producto".to_string());
+    }
+
+    if request
+        .items
+        .iter()
+        .any(|item| item.quantity <= Decimal::ZERO)
+    {
+        return Err("Las cantidades deben ser positivas".to_string());
+    }
+
+    let has_price_override = request
+        .items
+        .iter()
+        .any(|item| item.price_override.is_some());
+    if has_price_override && !session.has_permission("sales.price_override") {
+        return Err("No tiene permisos para modificar el precio de venta".to_string());
+    }
+
+    if request.items.iter().any(|item| {
+        item.price_override
+            .is_some_and(|price| price < Decimal::ZERO)
+    }) {
+        return Err("El precio manual no puede ser negativo".to_string());
+    }
+
+    let valid_until = NaiveDate::parse_from_str(&request.valid_until, "%Y-%m-%d")
+        .map_err(|_| format!("Fecha inválida: {}", request.valid_until))?;
+    if valid_until < Local::now().date_naive() {
+        return Err("La vigencia de la cotización no puede ser una fecha pasada".to_string());
+    }
+
+    // 3. Iniciar transacción
+    let txn = db
+        .begin()
+        .await
+        .map_err(|_| "Error al iniciar transacción")?;
+
+    // 4. Validar cliente
+    let customer = Customers::find_by_id(request.customer_id)
+        .one(&txn)
+        .await
+        .map_err(|_| DB_ERROR)?
+        .ok_or(format!("Cliente {} no encontrado", request.customer_id))?;
+
+    if !customer.is_active {
+        return Err(format!("El cliente {} no está activo", customer.name));
+    }
+
+    // 5. Calcular importes con las mismas reglas que una venta
+    let priced = price_sale_items(
+        &txn,
+        &session,
+        &request.items,
+        request.discount.as_ref(),
+        None,
+    )
+    .await?;
+
+    // 6. Crear registro de la cotización
+    let quote = quotes::ActiveModel {
+        customer_id: Set(customer.id),
+        valid_until: Set(valid_until),
+        notes: Set(request
+            .notes
+            .map(|notes| notes.trim().to_string())
+            .filter(|notes| !notes.is_empty())),
+        discount_type: Set(request
+            .discount
+            .as_ref()
+            .map(|discount| discount.discount_type.as_str().to_string())),
+        discount_value: Set(request
+            .discount
+            .as_ref()
+            .map_or(Decimal::ZERO, |discount| discount.value)),
+        discount_amount: Set(priced.ticket_discount),
+        subtotal: Set(priced.subtotal),
+        total: Set(priced.total),
+        status: Set(STATUS_OPEN.to_string()),
+        created_by: Set(session.user_id.clone()),
+        updated_by: Set(session.user_id.clone()),
+        ..Default::default()
+    }
+    .insert(&txn)
+    .await
+    .map_err(|e| format!("Error al crear la cotización: {:?}", e))?;
+
+    // 7. Crear las líneas de la cotización
+    for line in &priced.lines {
+        let item = &line.item;
+
+        let detail = quote_details::ActiveModel {
+            quote_id: Set(quote.id),
+            product_id: Set(line.product.id),
+            quantity: Set(item.quantity),
+            unit_price: Set(line.unit_price),
+            price_override: Set(item.price_override),
+            discount_type: Set(item
+                .discount
+                .as_ref()
+                .map(|discount| discount.discount_type.as_str().to_string())),
+            discount_value: Set(item
+                .discount
+                .as_ref()
+                .map_or(Decimal::ZERO, |discount| discount.value)),
+            discount_amount: Set(line.line_discount),
+            ticket_discount_amount: Set(line.ticket_discount),
+            promotion_id: Set(line.promotion.map(|promotion| promotion.promotion_id)),
+            promotion_discount_amount: Set(line
+                .promotion
+                .map_or(Decimal::ZERO, |promotion| promotion.amount)),
+            subtotal: Set(line.totals.subtotal),
+            tax_rate: Set(line.totals.effective_rate()),
+            tax_amount: Set(line.totals.tax_amount),
+            total: Set(line.totals.total),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await
+        .map_err(|e| format!("Error al registrar detalle de la cotización: {:?}", e))?;
+
+        // Registrar el desglose de impuestos de la línea
+        for tax in &line.totals.taxes {
+            quote_detail_taxes::ActiveModel {
+                quote_detail_id: Set(detail.id),
+                tax_type: Set(tax.tax_type.clone()),
+                rate: Set(tax.rate),
+                is_included: Set(tax.is_included),
+                is_exempt: Set(tax.is_exempt),
+                base: Set(tax.base),
+                amount: Set(tax.amount),
+                ..Default::default()
+            }
+            .insert(&txn)
+            .await
+            .map_err(|e| format!("Error al registrar impuestos de la cotización: {:?}", e))?;
+        }
+    }
+
+    // 8. Commit transacción
+    txn.commit()
+        .await
+        .map_err(|_| "Error al confirmar la transacción")?;
+
+    load_quote_detail(db, quote.id).await
+}
+
+/// Convierte una cotización vigente en venta. Los productos se vuelven a validar
+/// contra el stock y los precios actuales con las mismas reglas que create_sale.
+#[tauri::command]
+pub async fn convert_quote_to_sale(
+    state: tauri::State<'_, AppState>,
+    request: ConvertQuoteRequest,
+) -> Result<CreateSaleResponse, String> {
+    require_permission(&state, "quotes.create")?;
+
+    let detail = load_quote_detail(&state.database, request.quote_id).await?;
+    let quote = &detail.quote;
+
+    // Con clave, el reintento de una conversión ya registrada llega a register_sale, que
+    // regresa la venta original; mark_quote_converted rechaza cualquier otra venta
+    if quote.status != STATUS_OPEN && request.idempotency_key.is_none() {
+        return Err("La cotización ya fue convertida en venta".to_string());
+    }
+
+    if quote.status == STATUS_OPEN && quote.expired {
+        return Err(format!(
+            "La cotización venció el {}; emita una nueva",
+            quote.valid_until
+        ));
+    }
+
+    let sale_request = CreateSaleRequest {
+        items: detail.sale_items(),
+        payments: request.payments,
+        subtotal: request.subtotal,
+        total: request.total,
+        customer_id: Some(quote.customer_id),
+        discount: DiscountRequest::from_stored(
+            quote.discount_type.as_deref(),
+            quote.discount_value,
+        ),
+        coupon_code: None,
+        parked_sale_id: None,
+        idempotency_key: request.idempotency_key,
+    };
+
+    register_sale(&state, sale_request, Some(quote.id), false).await
+}
@@ -0,0 +1,137 @@
+use crate::entities::quotes;
+use crate::sales::structs::{DiscountRequest, SaleItemRequest, SaleLineTax, SalePaymentRequest};
+use sea_orm::prelude::Decimal;
+use serde::{Deserialize, Serialize};
+
+pub const STATUS_OPEN: &str = "open";
+pub const STATUS_CONVERTED: &str = "converted";
+
+/// Cotización (para lectura/respuesta)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Quote {
+    pub id: i32,
+    pub customer_id: i32,
+    pub customer_name: Option<String>,
+    pub valid_until: String, // AAAA-MM-DD
+    pub expired: bool,
+    pub notes: Option<String>,
+    pub discount_type: Option<String>,
+    pub discount_value: Decimal,
+    pub discount_amount: Decimal,
+    pub subtotal: Decimal,
+    pub total: Decimal,
+    pub status: String,          // open, converted
+    pub sale_id: Option<String>, // Venta generada al convertirla
+    pub created_at: String,
+    pub updated_at: String,
+    pub created_by: String,
+    pub updated_by: String,
+}
+
+impl Quote {
+    pub fn from_model(value: quotes::Model, customer_name: Option<String>) -> Self {
+        Self {
+            id: value.id,
+            customer_id: value.customer_id,
+            customer_name,
+            valid_until: value.valid_until.to_string(),
+            expired: value.valid_until < chrono::Local::now().date_naive(),
+            notes: value.notes,
+            discount_type: value.discount_type,
+            discount_value: value.discount_value,
+            discount_amount: value.discount_amount,
+            subtotal: value.subtotal,
+            total: value.total,
+            status: value.status,
+            sale_id: value.sale_id,
+            created_at: value.created_at.to_string(),
+            updated_at: value.updated_at.to_string(),
+            created_by: value.created_by,
+            updated_by: value.updated_by,
+        }
+    }
+}
+
+// Línea cotizada con los importes vigentes al emitir la cotización
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct QuoteLineDetail {
+    pub id: i32,
+    pub product_id: i32,
+    pub product_name: Option<String>,
+    pub product_code: Option<String>,
+    pub unit_of_measure: Option<String>,
+    pub quantity: Decimal,
+    pub unit_price: Decimal,
+    pub price_override: Option<Decimal>,
+    pub discount: Option<DiscountRequest>,
+    pub discount_amount: Decimal,
+    pub ticket_discount_amount: Decimal,
+    pub promotion_id: Option<i32>,
+    pub promotion_discount_amount: Decimal,
+    pub subtotal: Decimal,
+    pub tax_rate: Decimal, // Tasa efectiva de todos los impuestos; el desglose está en `taxes`
+    pub tax_amount: Decimal,
+    pub taxes: Vec<SaleLineTax>,
+    pub total: Decimal,
+}
+
+// Detalle completo de una cotización
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct QuoteDetailResponse {
+    pub quote: Quote,
+    pub created_by_name: Option<String>,
+    pub lines: Vec<QuoteLineDetail>,
+}
+
+impl QuoteDetailResponse {
+    /// Líneas en el formato que recibe create_sale
+    pub fn sale_items(&self) -> Vec<SaleItemRequest> {
+        self.lines
+            .iter()
+            .map(|line| SaleItemRequest {
+                product_id: line.product_id,
+                quantity: line.quantity,
+                price_override: line.price_override,
+                discount: line.discount.clone(),
+            })
+            .collect()
+    }
+}
+
+// Request para emitir una cotización
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CreateQuoteRequest {
+    pub customer_id: i32,
+    pub valid_until: String, // AAAA-MM-DD
+    pub notes: Option<String>,
+    pub items: Vec<SaleItemRequest>,
+    pub discount: Option<DiscountRequest>,
+}
+
+// Request para convertir una cotización en venta.
+// subtotal y total se validan contra los precios vigentes, igual que en create_sale.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ConvertQuoteRequest {
+    pub quote_id: i32,
+    pub payments: Vec<SalePaymentRequest>,
+    pub subtotal: Decimal,
+    pub total: Decimal,
+    pub idempotency_key: Option<String>, // Generada por la caja; un reintento no duplica la venta
+}
+
+/// Filtros para obtener cotizaciones
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct QuoteFilter {
+    pub customer_id: Option<i32>,
+    pub status: Option<String>, // open, converted
+    pub page: u64,
+    pub limit: u64,
+}
+
+/// Respuesta paginada de cotizaciones
+#[derive(Serialize, Deserialize, Debug)]
+pub struct QuoteListResponse {
+    pub quotes: Vec<Quote>,
+    pub total_pages: u64,
+    pub total_items: u64,
+}
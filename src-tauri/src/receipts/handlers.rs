@@ -0,0 +1,82 @@
+use super::render::{build_credit_statement, build_quote, build_receipt, render_text};
+use super::structs::{
+    CreditStatementReceiptParams, CreditStatementReceiptResponse, QuoteReceiptParams,
+    QuoteReceiptResponse, ReceiptParams, ReceiptResponse, StoreInfo,
+};
+use crate::customer_credit::structs::CreditStatementParams;
+use crate::customer_credit::CustomerCreditHandler::load_credit_statement;
+use crate::quotes::QuotesHandler::load_quote_detail;
+use crate::sales::SalesHandler::load_sale_detail;
+use crate::sessions::require_permission;
+use crate::AppState;
+
+/// Genera el ticket de una venta; sirve también para reimprimir ventas anteriores
+#[tauri::command]
+pub async fn get_sale_receipt(
+    state: tauri::State<'_, AppState>,
+    params: ReceiptParams,
+) -> Result<ReceiptResponse, String> {
+    require_permission(&state, "sales.view")?;
+
+    let detail = load_sale_detail(&state.database, &params.sale_id).await?;
+    let columns = params.paper_width.columns();
+    let lines = build_receipt(&StoreInfo::from_env(), &detail, columns, params.reprint);
+    let text = render_text(&lines, columns);
+
+    Ok(ReceiptResponse {
+        sale_id: detail.sale.id,
+        columns,
+        lines,
+        text,
+    })
+}
+
+/// Genera una cotización en formato de ticket
+#[tauri::command]
+pub async fn get_quote_receipt(
+    state: tauri::State<'_, AppState>,
+    params: QuoteReceiptParams,
+) -> Result<QuoteReceiptResponse, String> {
+    require_permission(&state, "quotes.view")?;
+
+    let detail = load_quote_detail(&state.database, params.quote_id).await?;
+    let columns = params.paper_width.columns();
+    let lines = build_quote(&StoreInfo::from_env(), &detail, columns);
+    let text = render_text(&lines, columns);
+
+    Ok(QuoteReceiptResponse {
+        quote_id: detail.quote.id,
+        columns,
+        lines,
+        text,
+    })
+}
+
+/// Genera el estado de cuenta de un cliente en formato de ticket
+#[tauri::command]
+pub async fn get_credit_statement_receipt(
+    state: tauri::State<'_, AppState>,
+    params: CreditStatementReceiptParams,
+) -> Result<CreditStatementReceiptResponse, String> {
+    require_permission(&state, "customer.credit")?;
+
+    let statement = load_credit_statement(
+        &state.database,
+        &CreditStatementParams {
+            customer_id: params.customer_id,
+            date_from: params.date_from,
+            date_to: params.date_to,
+        },
+    )
+    .await?;
+    let columns = params.paper_width.columns();
+    let lines = build_credit_statement(&StoreInfo::from_env(), &statement, columns);
+    let text = render_text(&lines, columns);
+
+    Ok(CreditStatementReceiptResponse {
+        customer_id: statement.account.customer_id,
+        columns,
+        lines,
+        text,
+    })
+}
@@ -0,0 +1,5 @@
+pub mod handlers;
+pub mod render;
+pub mod structs;
+
+pub use handlers as ReceiptsHandler;
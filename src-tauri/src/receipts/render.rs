@@ -0,0 +1,670 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Local, NaiveDate};
+use sea_orm::prelude::Decimal;
+
+use super::structs::{Align, ReceiptLine, StoreInfo};
+use crate::customer_credit::structs::{
+    CreditStatement, ENTRY_CANCELLATION, ENTRY_CHARGE, ENTRY_PAYMENT, ENTRY_REFUND,
+};
+use crate::layaways::structs::LAYAWAY_PENDING;
+use crate::quotes::structs::QuoteDetailResponse;
+use crate::sales::pricing::line_gross;
+use crate::sales::structs::{SaleDetailResponse, SaleLineTax};
+
+fn line(text: impl Into<String>, align: Align, bold: bool) -> ReceiptLine {
+    ReceiptLine {
+        text: text.into(),
+        align,
+        bold,
+    }
+}
+
+fn truncate(text: &str, columns: usize) -> String {
+    text.chars().take(columns).collect()
+}
+
+fn money(amount: Decimal) -> String {
+    format!("${:.2}", amount)
+}
+
+/// Cantidad sin ceros sobrantes; las unidades distintas de pieza se indican (0.75 kg)
+fn quantity(value: Decimal, unit_of_measure: Option<&str>) -> String {
+    match unit_of_measure {
+        Some(unit) if unit != "piece" => format!("{} {}", value.normalize(), unit),
+        _ => value.normalize().to_string(),
+    }
+}
+
+/// Texto a la izquierda e importe a la derecha en una sola línea
+fn two_columns(left: &str, right: &str, columns: usize) -> String {
+    let right_len = right.chars().count();
+    let left = truncate(left, columns.saturating_sub(right_len + 1));
+    let padding = columns.saturating_sub(left.chars().count() + right_len);
+    format!("{}{}{}", left, " ".repeat(padding), right)
+}
+
+/// Divide un texto en líneas que caben en el ancho del papel
+fn wrap(text: &str, columns: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+
+    for word in text.split_whitespace() {
+        let word = truncate(word, columns);
+        let needed = if current.is_empty() {
+            word.chars().count()
+        } else {
+            current.chars().count() + 1 + word.chars().count()
+        };
+
+        if needed > columns && !current.is_empty() {
+            lines.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(&word);
+    }
+
+    if !current.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+/// Convierte la fecha guardada en la venta a hora local
+fn local_date(created_at: &str) -> String {
+    DateTime::parse_from_str(created_at, "%Y-%m-%d %H:%M:%S%.f %:z")
+        .map(|date| {
+            date.with_timezone(&Local)
+                .format("%d/%m/%Y %H:%M")
+                .to_string()
+        })
+        .unwrap_or_else(|_| truncate(created_at, 16))
+}
+
+/// Impuesto agrupado en el ticket: tipo, exento y tasa
+type TaxKey = (String, bool, Decimal);
+
+fn tax_label((tax_type, is_exempt, rate): &TaxKey) -> String {
+    if *is_exempt {
+        format!("{} exento", tax_type.to_uppercase())
+    } else {
+        format!(
+            "{} {}%",
+            tax_type.to_uppercase(),
+            (rate * Decimal::from(100)).normalize()
+        )
+    }
+}
+
+/// Acumula el desglose de impuestos de una línea
+fn add_taxes(taxes: &mut BTreeMap<TaxKey, (Decimal, Decimal)>, line_taxes: &[SaleLineTax]) {
+    for tax in line_taxes {
+        let entry = taxes
+            .entry((tax.tax_type.clone(), tax.is_exempt, tax.rate))
+            .or_insert((Decimal::ZERO, Decimal::ZERO));
+        entry.0 += tax.base;
+        entry.1 += tax.amount;
+    }
+}
+
+/// Etiqueta del descuento; solo los porcentuales muestran la tasa
+fn discount_label(discount_type: Option<&str>, value: Decimal) -> String {
+    match discount_type {
+        Some("percent") => format!(" {}%", value.normalize()),
+        _ => String::new(),
+    }
+}
+
+/// Encabezado con los datos de la tienda
+fn store_header(store: &StoreInfo, columns: usize) -> Vec<ReceiptLine> {
+    let mut lines = Vec::new();
+
+    for text in wrap(&store.name, columns) {
+        lines.push(line(text, Align::Center, true));
+    }
+    if let Some(address) = &store.address {
+        for text in wrap(address, columns) {
+            lines.push(line(text, Align::Center, false));
+        }
+    }
+    if let Some(rfc) = &store.rfc {
+        lines.push(line(
+            truncate(&format!("RFC: {}", rfc), columns),
+            Align::Center,
+            false,
+        ));
+    }
+    if let Some(phone) = &store.phone {
+        lines.push(line(
+            truncate(&format!("Tel: {}", phone), columns),
+            Align::Center,
+            false,
+        ));
+    }
+
+    lines
+}
+
+/// Desglose de impuestos por tipo y tasa: (base, impuesto)
+fn push_taxes(
+    lines: &mut Vec<ReceiptLine>,
+    taxes: &BTreeMap<TaxKey, (Decimal, Decimal)>,
+    columns: usize,
+) {
+    for (key, (base, amount)) in taxes {
+        lines.push(line(
+            two_columns(
+                &format!("{} s/{}", tax_label(key), money(*base)),
+                &money(*amount),
+                columns,
+            ),
+            Align::Left,
+            false,
+        ));
+    }
+}
+
+/// Construye las líneas del ticket de una venta
+pub fn build_receipt(
+    store: &StoreInfo,
+    detail: &SaleDetailResponse,
+    columns: usize,
+    reprint: bool,
+) -> Vec<ReceiptLine> {
+    let separator = "-".repeat(columns);
+    let sale = &detail.sale;
+
+    // Encabezado
+    let mut lines = store_header(store, columns);
+    if reprint {
+        lines.push(line("*** REIMPRESIÓN ***", Align::Center, true));
+    }
+    let pending_layaway = sale.layaway_status.as_deref() == Some(LAYAWAY_PENDING);
+    if pending_layaway {
+        lines.push(line("*** APARTADO ***", Align::Center, true));
+    }
+    if !sale.status {
+        lines.push(line("*** VENTA CANCELADA ***", Align::Center, true));
+    }
+
+    lines.push(line(separator.clone(), Align::Left, false));
+    lines.push(line(
+        truncate(&format!("Ticket: {}", sale.id), columns),
+        Align::Left,
+        false,
+    ));
+    lines.push(line(
+        format!("Fecha: {}", local_date(&sale.created_at)),
+        Align::Left,
+        false,
+    ));
+    if let Some(cashier) = &detail.created_by_name {
+        lines.push(line(
+            truncate(&format!("Cajero: {}", cashier), columns),
+            Align::Left,
+            false,
+        ));
+    }
+    lines.push(line(separator.clone(), Align::Left, false));
+
+    // Productos
+    let mut taxes: BTreeMap<TaxKey, (Decimal, Decimal)> = BTreeMap::new();
+    for item in &detail.lines {
+        let name = item
+            .product_name
+            .clone()
+            .unwrap_or_else(|| format!("Producto {}", item.product_id));
+        lines.push(line(truncate(&name, columns), Align::Left, false));
+        lines.push(line(
+            two_columns(
+                &format!(
+                    "  {} x {}",
+                    quantity(item.quantity, item.unit_of_measure.as_deref()),
+                    money(item.unit_price)
+                ),
+                &money(line_gross(item.unit_price, item.quantity)),
+                columns,
+            ),
+            Align::Left,
+            false,
+        ));
+        if item.promotion_discount_amount > Decimal::ZERO {
+            let promotion = item.promotion_name.as_deref().unwrap_or("Promoción");
+            lines.push(line(
+                two_columns(
+                    &format!("  {}", promotion),
+                    &format!("-{}", money(item.promotion_discount_amount)),
+                    columns,
+                ),
+                Align::Left,
+                false,
+            ));
+        }
+        if item.discount_amount > Decimal::ZERO {
+            lines.push(line(
+                two_columns(
+                    &format!(
+                        "  Descuento{}",
+                        discount_label(item.discount_type.as_deref(), item.discount_value)
+                    ),
+                    &format!("-{}", money(item.discount_amount)),
+                    columns,
+                ),
+                Align::Left,
+                false,
+            ));
+        }
+
+        add_taxes(&mut taxes, &item.taxes);
+    }
+    lines.push(line(separator.clone(), Align::Left, false));
+
+    // Totales y desglose de impuestos
+    if sale.discount_amount > Decimal::ZERO {
+        lines.push(line(
+            two_columns(
+                &format!(
+                    "Descuento{}",
+                    discount_label(sale.discount_type.as_deref(), sale.discount_value)
+                ),
+                &format!("-{}", money(sale.discount_amount)),
+                columns,
+            ),
+            Align::Left,
+            false,
+        ));
+    }
+    if sale.coupon_discount_amount > Decimal::ZERO {
+        let coupon = detail.coupon_code.as_deref().unwrap_or_default();
+        lines.push(line(
+            two_columns(
+                &format!("Cupón {}", coupon),
+                &format!("-{}", money(sale.coupon_discount_amount)),
+                columns,
+            ),
+            Align::Left,
+            false,
+        ));
+    }
+    lines.push(line(
+        two_columns("Subtotal", &money(sale.subtotal), columns),
+        Align::Left,
+        false,
+    ));
+    push_taxes(&mut lines, &taxes, columns);
+    lines.push(line(
+        two_columns("TOTAL", &money(sale.total), columns),
+        Align::Left,
+        true,
+    ));
+    lines.push(line(separator.clone(), Align::Left, false));
+
+    // Formas de pago y cambio
+    for payment in &detail.payments {
+        let method = payment
+            .payment_method_name
+            .clone()
+            .unwrap_or_else(|| "Pago".to_string());
+        lines.push(line(
+            two_columns(&method, &money(payment.amount), columns),
+            Align::Left,
+            false,
+        ));
+    }
+    if sale.change_amount > Decimal::ZERO {
+        lines.push(line(
+            two_columns("Cambio", &money(sale.change_amount), columns),
+            Align::Left,
+            false,
+        ));
+    }
+
+    // Saldo y fecha límite del apartado
+    if pending_layaway {
+        let paid: Decimal = detail.payments.iter().map(|payment| payment.amount).sum();
+        lines.push(line(
+            two_columns("Saldo pendiente", &money(sale.total - paid), columns),
+            Align::Left,
+            true,
+        ));
+        if let Some(due_date) = &sale.layaway_due_date {
+            let due_date = NaiveDate::parse_from_str(due_date, "%Y-%m-%d")
+                .map(|date| date.format("%d/%m/%Y").to_string())
+                .unwrap_or_else(|_| due_date.clone());
+            lines.push(line(
+                format!("Fecha límite: {}", due_date),
+                Align::Left,
+                false,
+            ));
+        }
+    }
+    if sale.layaway_penalty_amount > Decimal::ZERO {
+        lines.push(line(
+            two_columns("Penalización", &money(sale.layaway_penalty_amount), columns),
+            Align::Left,
+            false,
+        ));
+    }
+
+    // Tarjetas de regalo vendidas, con el código para canjearlas
+    if !detail.gift_cards.is_empty() {
+        lines.push(line(separator.clone(), Align::Left, false));
+        lines.push(line("TARJETAS DE REGALO".to_string(), Align::Center, true));
+        for card in &detail.gift_cards {
+            lines.push(line(
+                two_columns(&card.code, &money(card.initial_amount), columns),
+                Align::Left,
+                false,
+            ));
+        }
+    }
+
+    // Devoluciones registradas
+    if !detail.refunds.is_empty() {
+        lines.push(line(separator.clone(), Align::Left, false));
+        for refund in &detail.refunds {
+            lines.push(line(
+                two_columns(
+                    &format!("Devolución #{}", refund.id),
+                    &format!("-{}", money(refund.amount)),
+                    columns,
+                ),
+                Align::Left,
+                false,
+            ));
+        }
+    }
+
+    lines.push(line(separator, Align::Left, false));
+    for text in wrap(&store.footer, columns) {
+        lines.push(line(text, Align::Center, false));
+    }
+
+    lines
+}
+
+/// Construye las líneas de una cotización impresa
+pub fn build_quote(
+    store: &StoreInfo,
+    detail: &QuoteDetailResponse,
+    columns: usize,
+) -> Vec<ReceiptLine> {
+    let separator = "-".repeat(columns);
+    let quote = &detail.quote;
+
+    // Encabezado
+    let mut lines = store_header(store, columns);
+    lines.push(line(
+        format!("COTIZACIÓN #{}", quote.id),
+        Align::Center,
+        true,
+    ));
+
+    lines.push(line(separator.clone(), Align::Left, false));
+    lines.push(line(
+        format!("Fecha: {}", local_date(&quote.created_at)),
+        Align::Left,
+        false,
+    ));
+    let valid_until = NaiveDate::parse_from_str(&quote.valid_until, "%Y-%m-%d")
+        .map(|date| date.format("%d/%m/%Y").to_string())
+        .unwrap_or_else(|_| quote.valid_until.clone());
+    lines.push(line(
+        format!("Vigencia: {}", valid_until),
+        Align::Left,
+        false,
+    ));
+    if let Some(customer) = &quote.customer_name {
+        lines.push(line(
+            truncate(&format!("Cliente: {}", customer), columns),
+            Align::Left,
+            false,
+        ));
+    }
+    if let Some(seller) = &detail.created_by_name {
+        lines.push(line(
+            truncate(&format!("Atendió: {}", seller), columns),
+            Align::Left,
+            false,
+        ));
+    }
+    lines.push(line(separator.clone(), Align::Left, false));
+
+    // Productos
+    let mut taxes: BTreeMap<TaxKey, (Decimal, Decimal)> = BTreeMap::new();
+    for item in &detail.lines {
+        let name = item
+            .product_name
+            .clone()
+            .unwrap_or_else(|| format!("Producto {}", item.product_id));
+        lines.push(line(truncate(&name, columns), Align::Left, false));
+        lines.push(line(
+            two_columns(
+                &format!(
+                    "  {} x {}",
+                    quantity(item.quantity, item.unit_of_measure.as_deref()),
+                    money(item.unit_price)
+                ),
+                &money(line_gross(item.unit_price, item.quantity)),
+                columns,
+            ),
+            Align::Left,
+            false,
+        ));
+        let line_discount = item.promotion_discount_amount + item.discount_amount;
+        if line_discount > Decimal::ZERO {
+            lines.push(line(
+                two_columns(
+                    "  Descuento",
+                    &format!("-{}", money(line_discount)),
+                    columns,
+                ),
+                Align::Left,
+                false,
+            ));
+        }
+
+        add_taxes(&mut taxes, &item.taxes);
+    }
+    lines.push(line(separator.clone(), Align::Left, false));
+
+    // Totales y desglose de impuestos
+    if quote.discount_amount > Decimal::ZERO {
+        lines.push(line(
+            two_columns(
+                &format!(
+                    "Descuento{}",
+                    discount_label(quote.discount_type.as_deref(), quote.discount_value)
+                ),
+                &format!("-{}", money(quote.discount_amount)),
+                columns,
+            ),
+            Align::Left,
+            false,
+        ));
+    }
+    lines.push(line(
+        two_columns("Subtotal", &money(quote.subtotal), columns),
+        Align::Left,
+        false,
+    ));
+    push_taxes(&mut lines, &taxes, columns);
+    lines.push(line(
+        two_columns("TOTAL", &money(quote.total), columns),
+        Align::Left,
+        true,
+    ));
+    lines.push(line(separator, Align::Left, false));
+
+    // Notas y condiciones
+    if let Some(notes) = &quote.notes {
+        for text in wrap(notes, columns) {
+            lines.push(line(text, Align::Left, false));
+        }
+    }
+    for text in wrap(
+        "Precios sujetos a existencias. Esta cotización no es un comprobante de pago.",
+        columns,
+    ) {
+        lines.push(line(text, Align::Center, false));
+    }
+
+    lines
+}
+
+fn entry_label(entry_type: &str) -> &str {
+    match entry_type {
+        ENTRY_CHARGE => "Cargo",
+        ENTRY_PAYMENT => "Abono",
+        ENTRY_REFUND => "Devolución",
+        ENTRY_CANCELLATION => "Cancelación",
+        _ => entry_type,
+    }
+}
+
+/// Fecha AAAA-MM-DD en formato dd/mm/aaaa
+fn short_date(date: &str) -> String {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .map(|date| date.format("%d/%m/%Y").to_string())
+        .unwrap_or_else(|_| date.to_string())
+}
+
+/// Construye las líneas del estado de cuenta de un cliente
+pub fn build_credit_statement(
+    store: &StoreInfo,
+    statement: &CreditStatement,
+    columns: usize,
+) -> Vec<ReceiptLine> {
+    let separator = "-".repeat(columns);
+    let account = &statement.account;
+
+    // Encabezado
+    let mut lines = store_header(store, columns);
+    lines.push(line("ESTADO DE CUENTA", Align::Center, true));
+
+    lines.push(line(separator.clone(), Align::Left, false));
+    lines.push(line(
+        truncate(&format!("Cliente: {}", account.customer_name), columns),
+        Align::Left,
+        false,
+    ));
+    let period = match (&statement.date_from, &statement.date_to) {
+        (Some(from), Some(to)) => format!("Periodo: {} - {}", short_date(from), short_date(to)),
+        (Some(from), None) => format!("Desde: {}", short_date(from)),
+        (None, Some(to)) => format!("Hasta: {}", short_date(to)),
+        (None, None) => "Periodo: todos los movimientos".to_string(),
+    };
+    lines.push(line(truncate(&period, columns), Align::Left, false));
+    lines.push(line(
+        format!("Emitido: {}", local_date(&statement.generated_at)),
+        Align::Left,
+        false,
+    ));
+    lines.push(line(separator.clone(), Align::Left, false));
+
+    // Movimientos del periodo
+    lines.push(line(
+        two_columns("Saldo anterior", &money(statement.opening_balance), columns),
+        Align::Left,
+        false,
+    ));
+    for entry in &statement.entries {
+        lines.push(line(
+            two_columns(
+                &format!(
+                    "{} {}",
+                    truncate(&local_date(&entry.created_at), 10),
+                    entry_label(&entry.entry_type)
+                ),
+                &money(entry.amount),
+                columns,
+            ),
+            Align::Left,
+            false,
+        ));
+        lines.push(line(
+            two_columns("  Saldo", &money(entry.balance_after), columns),
+            Align::Left,
+            false,
+        ));
+    }
+    lines.push(line(separator.clone(), Align::Left, false));
+
+    // Resumen del periodo
+    lines.push(line(
+        two_columns("Cargos", &money(statement.charges_total), columns),
+        Align::Left,
+        false,
+    ));
+    lines.push(line(
+        two_columns(
+            "Abonos",
+            &format!("-{}", money(statement.credits_total)),
+            columns,
+        ),
+        Align::Left,
+        false,
+    ));
+    lines.push(line(
+        two_columns("SALDO", &money(statement.closing_balance), columns),
+        Align::Left,
+        true,
+    ));
+    lines.push(line(separator.clone(), Align::Left, false));
+
+    // Situación actual de la cuenta
+    lines.push(line(
+        two_columns("Límite de crédito", &money(account.credit_limit), columns),
+        Align::Left,
+        false,
+    ));
+    lines.push(line(
+        two_columns("Saldo actual", &money(account.balance), columns),
+        Align::Left,
+        false,
+    ));
+    lines.push(line(
+        two_columns("Disponible", &money(account.available_credit), columns),
+        Align::Left,
+        false,
+    ));
+    for (label, amount) in [
+        ("0 a 30 días", account.aging.current),
+        ("31 a 60 días", account.aging.days_31_60),
+        ("61 a 90 días", account.aging.days_61_90),
+        ("Más de 90 días", account.aging.over_90),
+    ] {
+        if amount > Decimal::ZERO {
+            lines.push(line(
+                two_columns(&format!("  {}", label), &money(amount), columns),
+                Align::Left,
+                false,
+            ));
+        }
+    }
+    lines.push(line(separator, Align::Left, false));
+    for text in wrap(&store.footer, columns) {
+        lines.push(line(text, Align::Center, false));
+    }
+
+    lines
+}
+
+/// Convierte las líneas del ticket en texto de ancho fijo
+pub fn render_text(lines: &[ReceiptLine], columns: usize) -> String {
+    lines
+        .iter()
+        .map(|receipt_line| {
+            let width = receipt_line.text.chars().count();
+            let padding = columns.saturating_sub(width);
+            match receipt_line.align {
+                Align::Left => receipt_line.text.clone(),
+                Align::Center => format!("{}{}", " ".repeat(padding / 2), receipt_line.text),
+                Align::Right => format!("{}{}", " ".repeat(padding), receipt_line.text),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
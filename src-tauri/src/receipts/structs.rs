@@ -0,0 +1,115 @@
+use serde::{Deserialize, Serialize};
+use std::env;
+
+/// Ancho del papel de la impresora térmica
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum PaperWidth {
+    #[serde(rename = "58mm")]
+    Mm58,
+    #[serde(rename = "80mm")]
+    Mm80,
+}
+
+impl PaperWidth {
+    /// Número de columnas con la fuente estándar (Font A)
+    pub fn columns(self) -> usize {
+        match self {
+            PaperWidth::Mm58 => 32,
+            PaperWidth::Mm80 => 48,
+        }
+    }
+}
+
+/// Alineación de una línea del ticket
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Align {
+    Left,
+    Center,
+    Right,
+}
+
+/// Línea del ticket con su formato; el texto nunca excede el ancho del papel
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ReceiptLine {
+    pub text: String,
+    pub align: Align,
+    pub bold: bool,
+}
+
+/// Parámetros para generar el ticket de una venta
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ReceiptParams {
+    pub sale_id: String,
+    pub paper_width: PaperWidth,
+    pub reprint: bool,
+}
+
+/// Parámetros para generar una cotización impresa
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct QuoteReceiptParams {
+    pub quote_id: i32,
+    pub paper_width: PaperWidth,
+}
+
+/// Cotización generada en formato de ticket
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct QuoteReceiptResponse {
+    pub quote_id: i32,
+    pub columns: usize,
+    pub lines: Vec<ReceiptLine>,
+    pub text: String,
+}
+
+/// Parámetros para generar el estado de cuenta impreso de un cliente
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CreditStatementReceiptParams {
+    pub customer_id: i32,
+    pub date_from: Option<String>, // AAAA-MM-DD
+    pub date_to: Option<String>,
+    pub paper_width: PaperWidth,
+}
+
+/// Estado de cuenta generado en formato de ticket
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CreditStatementReceiptResponse {
+    pub customer_id: i32,
+    pub columns: usize,
+    pub lines: Vec<ReceiptLine>,
+    pub text: String,
+}
+
+/// Ticket generado
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ReceiptResponse {
+    pub sale_id: String,
+    pub columns: usize,
+    pub lines: Vec<ReceiptLine>,
+    /// Ticket completo en texto de ancho fijo
+    pub text: String,
+}
+
+/// Datos del encabezado del ticket, configurables en el archivo .env
+#[derive(Debug, Clone)]
+pub struct StoreInfo {
+    pub name: String,
+    pub address: Option<String>,
+    pub rfc: Option<String>,
+    pub phone: Option<String>,
+    pub footer: String,
+}
+
+impl StoreInfo {
+    pub fn from_env() -> Self {
+        let optional = |key: &str| env::var(key).ok().filter(|value| !value.trim().is_empty());
+
+        Self {
+            name: optional("STORE_NAME").unwrap_or_else(|| "Punto de Venta".to_string()),
+            address: optional("STORE_ADDRESS"),
+            rfc: optional("STORE_RFC"),
+            phone: optional("STORE_PHONE"),
+            footer: optional("RECEIPT_FOOTER")
+                .unwrap_or_else(|| "¡Gracias por su compra!".to_string()),
+        }
+    }
+}
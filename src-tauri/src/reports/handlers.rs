@@ -6,14 +6,19 @@ use crate::AppState;
 
 use super::structs::{
     CategoryReportItem, CategoryReportParams, CategoryReportResult, DashboardParams,
-    DashboardResult, PaymentMethodReportItem, PaymentMethodReportParams, PaymentMethodReportResult,
-    ProductReportItem, ProductReportParams, ProductReportResult, RefundsReportParams,
-    RefundsReportResult, SalesOverTimeItem, SalesOverTimeParams, SalesOverTimeResult, TimeGrouping,
-    TopRefundedProduct,
+    DashboardResult, DiscountedProductItem, DiscountsByUserItem, DiscountsReportParams,
+    DiscountsReportResult, PaymentMethodReportItem, PaymentMethodReportParams,
+    PaymentMethodReportResult, ProductReportItem, ProductReportParams, ProductReportResult,
+    PromotionReportItem, PromotionsReportParams, PromotionsReportResult, RefundsReportParams,
+    RefundsReportResult, SalesOverTimeItem, SalesOverTimeParams, SalesOverTimeResult,
+    TaxSummaryItem, TaxSummaryParams, TaxSummaryResult, TimeGrouping, TopRefundedProduct,
 };
 
 const DB_ERROR: &str = "Error al consultar la base de datos.";
 
+// Las ventas se filtran por completed_at: un apartado cuenta como ingreso
+// hasta que se liquida y mientras está pendiente no tiene fecha de cierre.
+
 // ============================================================================
 // 1. DASHBOARD EJECUTIVO DE VENTAS
 // ============================================================================
@@ -34,7 +39,7 @@ struct DominantPaymentRaw {
 #[derive(FromQueryResult)]
 struct TopProductRaw {
     product_name: Option<String>,
-    total_quantity: Option<i64>,
+    total_quantity: Option<Decimal>,
 }
 
 #[tauri::command]
@@ -58,14 +63,14 @@ pub async fn get_dashboard_report(
                 FROM refunds r
                 INNER JOIN sales s2 ON r.sale_id = s2.id
                 WHERE s2.status = true
-                AND DATE(s2.created_at) >= $1::date
-                AND DATE(s2.created_at) <= $2::date
+                AND DATE(s2.completed_at) >= $1::date
+                AND DATE(s2.completed_at) <= $2::date
             ), 0) as total_refunded,
             COUNT(s.id) as sales_count
         FROM sales s
         WHERE s.status = true
-        AND DATE(s.created_at) >= $1::date
-        AND DATE(s.created_at) <= $2::date
+        AND DATE(s.completed_at) >= $1::date
+        AND DATE(s.completed_at) <= $2::date
         "#,
         [
             params.date_from.clone().into(),
@@ -105,8 +110,8 @@ pub async fn get_dashboard_report(
         INNER JOIN payment_methods pm ON sp.payment_method_id = pm.id
         INNER JOIN sales s ON sp.sale_id = s.id
         WHERE s.status = true
-        AND DATE(s.created_at) >= $1::date
-        AND DATE(s.created_at) <= $2::date
+        AND DATE(s.completed_at) >= $1::date
+        AND DATE(s.completed_at) <= $2::date
         GROUP BY pm.id, pm.name
         ORDER BY total_amount DESC
         LIMIT 1
@@ -136,13 +141,13 @@ pub async fn get_dashboard_report(
         r#"
         SELECT
             p.name as product_name,
-            SUM(sd.quantity)::bigint as total_quantity
+            SUM(sd.quantity) as total_quantity
         FROM sale_details sd
         INNER JOIN products p ON sd.product_id = p.id
         INNER JOIN sales s ON sd.sale_id = s.id
         WHERE s.status = true
-        AND DATE(s.created_at) >= $1::date
-        AND DATE(s.created_at) <= $2::date
+        AND DATE(s.completed_at) >= $1::date
+        AND DATE(s.completed_at) <= $2::date
         GROUP BY p.id, p.name
         ORDER BY total_quantity DESC
         LIMIT 1
@@ -159,8 +164,8 @@ pub async fn get_dashboard_report(
         .map_err(|_| DB_ERROR)?;
 
     let (top_product, top_product_quantity) = match product_result {
-        Some(p) => (p.product_name, p.total_quantity.unwrap_or(0)),
-        None => (None, 0),
+        Some(p) => (p.product_name, p.total_quantity.unwrap_or(Decimal::ZERO)),
+        None => (None, Decimal::ZERO),
     };
 
     Ok(DashboardResult {
@@ -199,9 +204,9 @@ pub async fn get_sales_over_time_report(
 
     // Determinar el formato de agrupación según el parámetro
     let date_format = match params.grouping {
-        TimeGrouping::Day => "TO_CHAR(s.created_at, 'YYYY-MM-DD')",
-        TimeGrouping::Week => "TO_CHAR(s.created_at, 'IYYY-\"W\"IW')",
-        TimeGrouping::Month => "TO_CHAR(s.created_at, 'YYYY-MM')",
+        TimeGrouping::Day => "TO_CHAR(s.completed_at, 'YYYY-MM-DD')",
+        TimeGrouping::Week => "TO_CHAR(s.completed_at, 'IYYY-\"W\"IW')",
+        TimeGrouping::Month => "TO_CHAR(s.completed_at, 'YYYY-MM')",
     };
 
     let query_str = format!(
@@ -217,8 +222,8 @@ pub async fn get_sales_over_time_report(
                 ), 0) as refund_total
             FROM sales s
             WHERE s.status = true
-            AND DATE(s.created_at) >= $1::date
-            AND DATE(s.created_at) <= $2::date
+            AND DATE(s.completed_at) >= $1::date
+            AND DATE(s.completed_at) <= $2::date
         )
         SELECT
             period,
@@ -292,8 +297,8 @@ struct ProductReportRaw {
     product_id: i32,
     product_name: String,
     category_name: Option<String>,
-    quantity_sold: Option<i64>,
-    quantity_refunded: Option<i64>,
+    quantity_sold: Option<Decimal>,
+    quantity_refunded: Option<Decimal>,
     gross_revenue: Option<Decimal>,
     refunded_amount: Option<Decimal>,
 }
@@ -326,16 +331,16 @@ pub async fn get_product_report(
             p.id as product_id,
             p.name as product_name,
             c.name as category_name,
-            COALESCE(SUM(sd.quantity), 0)::bigint as quantity_sold,
+            COALESCE(SUM(sd.quantity), 0) as quantity_sold,
             COALESCE((
-                SELECT SUM(rd.quantity)::bigint
+                SELECT SUM(rd.quantity)
                 FROM refund_details rd
                 INNER JOIN refunds r ON rd.refund_id = r.id
                 INNER JOIN sales rs ON r.sale_id = rs.id
                 WHERE rd.product_id = p.id
-                AND DATE(rs.created_at) >= $1::date
-                AND DATE(rs.created_at) <= $2::date
-            ), 0)::bigint as quantity_refunded,
+                AND DATE(rs.completed_at) >= $1::date
+                AND DATE(rs.completed_at) <= $2::date
+            ), 0) as quantity_refunded,
             COALESCE(SUM(sd.total), 0) as gross_revenue,
             COALESCE((
                 SELECT SUM(rd.quantity * rd.unit_price)
@@ -343,15 +348,15 @@ pub async fn get_product_report(
                 INNER JOIN refunds r ON rd.refund_id = r.id
                 INNER JOIN sales rs ON r.sale_id = rs.id
                 WHERE rd.product_id = p.id
-                AND DATE(rs.created_at) >= $1::date
-                AND DATE(rs.created_at) <= $2::date
+                AND DATE(rs.completed_at) >= $1::date
+                AND DATE(rs.completed_at) <= $2::date
             ), 0) as refunded_amount
         FROM products p
         LEFT JOIN categories c ON p.category_id = c.id
         LEFT JOIN sale_details sd ON sd.product_id = p.id
         LEFT JOIN sales s ON sd.sale_id = s.id AND s.status = true
-            AND DATE(s.created_at) >= $1::date
-            AND DATE(s.created_at) <= $2::date
+            AND DATE(s.completed_at) >= $1::date
+            AND DATE(s.completed_at) <= $2::date
         WHERE 1=1 {conditions}
         GROUP BY p.id, p.name, c.name
         HAVING COALESCE(SUM(sd.quantity), 0) > 0 OR EXISTS (
@@ -359,8 +364,8 @@ pub async fn get_product_report(
             INNER JOIN refunds r2 ON rd2.refund_id = r2.id
             INNER JOIN sales s2 ON r2.sale_id = s2.id
             WHERE rd2.product_id = p.id
-            AND DATE(s2.created_at) >= $1::date
-            AND DATE(s2.created_at) <= $2::date
+            AND DATE(s2.completed_at) >= $1::date
+            AND DATE(s2.completed_at) <= $2::date
         )
         ORDER BY gross_revenue DESC
         "#,
@@ -388,14 +393,14 @@ pub async fn get_product_report(
         .map_err(|_| DB_ERROR)?;
 
     // Calcular totales
-    let mut total_quantity_sold: i64 = 0;
-    let mut total_quantity_refunded: i64 = 0;
+    let mut total_quantity_sold = Decimal::ZERO;
+    let mut total_quantity_refunded = Decimal::ZERO;
     let mut total_gross_revenue = Decimal::ZERO;
     let mut total_refunded_amount = Decimal::ZERO;
 
     for row in &results {
-        total_quantity_sold += row.quantity_sold.unwrap_or(0);
-        total_quantity_refunded += row.quantity_refunded.unwrap_or(0);
+        total_quantity_sold += row.quantity_sold.unwrap_or(Decimal::ZERO);
+        total_quantity_refunded += row.quantity_refunded.unwrap_or(Decimal::ZERO);
         total_gross_revenue += row.gross_revenue.unwrap_or(Decimal::ZERO);
         total_refunded_amount += row.refunded_amount.unwrap_or(Decimal::ZERO);
     }
@@ -407,8 +412,8 @@ pub async fn get_product_report(
     let items: Vec<ProductReportItem> = results
         .into_iter()
         .map(|row| {
-            let quantity_sold = row.quantity_sold.unwrap_or(0);
-            let quantity_refunded = row.quantity_refunded.unwrap_or(0);
+            let quantity_sold = row.quantity_sold.unwrap_or(Decimal::ZERO);
+            let quantity_refunded = row.quantity_refunded.unwrap_or(Decimal::ZERO);
             let gross_revenue = row.gross_revenue.unwrap_or(Decimal::ZERO);
             let refunded_amount = row.refunded_amount.unwrap_or(Decimal::ZERO);
             let net_revenue = gross_revenue - refunded_amount;
@@ -452,7 +457,7 @@ struct CategoryReportRaw {
     category_id: Option<i32>,
     category_name: Option<String>,
     net_sales: Option<Decimal>,
-    quantity_sold: Option<i64>,
+    quantity_sold: Option<Decimal>,
 }
 
 #[tauri::command]
@@ -485,14 +490,14 @@ pub async fn get_category_report(
             INNER JOIN products p ON sd.product_id = p.id
             LEFT JOIN categories c ON p.category_id = c.id
             WHERE s.status = true
-            AND DATE(s.created_at) >= $1::date
-            AND DATE(s.created_at) <= $2::date
+            AND DATE(s.completed_at) >= $1::date
+            AND DATE(s.completed_at) <= $2::date
         )
         SELECT
             category_id,
             COALESCE(category_name, 'Sin categoría') as category_name,
             SUM(sale_total - refund_total) as net_sales,
-            SUM(quantity)::bigint as quantity_sold
+            SUM(quantity) as quantity_sold
         FROM category_sales
         GROUP BY category_id, category_name
         ORDER BY net_sales DESC
@@ -510,11 +515,11 @@ pub async fn get_category_report(
 
     // Calcular totales
     let mut total_net_sales = Decimal::ZERO;
-    let mut total_quantity_sold: i64 = 0;
+    let mut total_quantity_sold = Decimal::ZERO;
 
     for row in &results {
         total_net_sales += row.net_sales.unwrap_or(Decimal::ZERO);
-        total_quantity_sold += row.quantity_sold.unwrap_or(0);
+        total_quantity_sold += row.quantity_sold.unwrap_or(Decimal::ZERO);
     }
 
     // Construir items con porcentajes
@@ -534,7 +539,7 @@ pub async fn get_category_report(
                     .category_name
                     .unwrap_or_else(|| "Sin categoría".to_string()),
                 net_sales,
-                quantity_sold: row.quantity_sold.unwrap_or(0),
+                quantity_sold: row.quantity_sold.unwrap_or(Decimal::ZERO),
                 share_percentage,
             }
         })
@@ -581,8 +586,8 @@ pub async fn get_payment_method_report(
         INNER JOIN payment_methods pm ON sp.payment_method_id = pm.id
         INNER JOIN sales s ON sp.sale_id = s.id
         WHERE s.status = true
-        AND DATE(s.created_at) >= $1::date
-        AND DATE(s.created_at) <= $2::date
+        AND DATE(s.completed_at) >= $1::date
+        AND DATE(s.completed_at) <= $2::date
         GROUP BY pm.id, pm.name
         ORDER BY total_amount DESC
         "#,
@@ -649,7 +654,7 @@ struct RefundsMainRaw {
 struct TopRefundedProductRaw {
     product_id: i32,
     product_name: String,
-    quantity_refunded: Option<i64>,
+    quantity_refunded: Option<Decimal>,
     amount_refunded: Option<Decimal>,
 }
 
@@ -663,7 +668,8 @@ pub async fn get_refunds_report(
 
     let db = &state.database;
 
-    // Consulta principal
+    // Consulta principal. Los reembolsos por cancelación de ventas y apartados no son
+    // devoluciones de mercancía.
     let main_query = Statement::from_sql_and_values(
         DatabaseBackend::Postgres,
         r#"
@@ -672,21 +678,24 @@ pub async fn get_refunds_report(
                 SELECT SUM(r.amount)
                 FROM refunds r
                 INNER JOIN sales s ON r.sale_id = s.id
-                WHERE DATE(r.created_at) >= $1::date
+                WHERE s.status = true
+                AND DATE(r.created_at) >= $1::date
                 AND DATE(r.created_at) <= $2::date
             ), 0) as total_refunded,
             COALESCE((
                 SELECT COUNT(r.id)::bigint
                 FROM refunds r
-                WHERE DATE(r.created_at) >= $1::date
+                INNER JOIN sales s ON r.sale_id = s.id
+                WHERE s.status = true
+                AND DATE(r.created_at) >= $1::date
                 AND DATE(r.created_at) <= $2::date
             ), 0) as refunds_count,
             COALESCE((
                 SELECT SUM(s.total)
                 FROM sales s
                 WHERE s.status = true
-                AND DATE(s.created_at) >= $1::date
-                AND DATE(s.created_at) <= $2::date
+                AND DATE(s.completed_at) >= $1::date
+                AND DATE(s.completed_at) <= $2::date
             ), 0) as gross_sales
         "#,
         [
@@ -722,7 +731,7 @@ pub async fn get_refunds_report(
         SELECT
             p.id as product_id,
             p.name as product_name,
-            SUM(rd.quantity)::bigint as quantity_refunded,
+            SUM(rd.quantity) as quantity_refunded,
             SUM(rd.quantity * rd.unit_price) as amount_refunded
         FROM refund_details rd
         INNER JOIN products p ON rd.product_id = p.id
@@ -749,7 +758,7 @@ pub async fn get_refunds_report(
         .map(|row| TopRefundedProduct {
             product_id: row.product_id,
             product_name: row.product_name,
-            quantity_refunded: row.quantity_refunded.unwrap_or(0),
+            quantity_refunded: row.quantity_refunded.unwrap_or(Decimal::ZERO),
             amount_refunded: row.amount_refunded.unwrap_or(Decimal::ZERO),
         })
         .collect();
@@ -762,3 +771,329 @@ pub async fn get_refunds_report(
         top_refunded_products,
     })
 }
+
+// ============================================================================
+// 7. REPORTE DE DESCUENTOS
+// ============================================================================
+
+#[derive(FromQueryResult)]
+struct DiscountsMainRaw {
+    line_discounts: Option<Decimal>,
+    ticket_discounts: Option<Decimal>,
+    discounted_sales_count: Option<i64>,
+    gross_amount: Option<Decimal>,
+}
+
+#[derive(FromQueryResult)]
+struct DiscountsByUserRaw {
+    user_id: String,
+    user_name: String,
+    sales_count: Option<i64>,
+    total_discount: Option<Decimal>,
+}
+
+#[derive(FromQueryResult)]
+struct DiscountedProductRaw {
+    product_id: i32,
+    product_name: String,
+    quantity: Option<Decimal>,
+    total_discount: Option<Decimal>,
+}
+
+#[tauri::command]
+pub async fn get_discounts_report(
+    state: tauri::State<'_, AppState>,
+    params: DiscountsReportParams,
+) -> Result<DiscountsReportResult, String> {
+    require_permission(&state, "reports.sales").map_err(|e| e.to_string())?;
+    validate_date_range(&params.date_from, &params.date_to).map_err(|e| e.to_string())?;
+
+    let db = &state.database;
+
+    // Totales del período (solo ventas activas). El bruto es el precio de venta por
+    // cantidad, la misma base sobre la que se calculan los descuentos; el subtotal no
+    // sirve porque en precios con impuestos incluidos ya no los contiene
+    let main_query = Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        r#"
+        SELECT
+            SUM(sd.discount_amount) as line_discounts,
+            SUM(sd.ticket_discount_amount) as ticket_discounts,
+            COUNT(DISTINCT s.id) FILTER (
+                WHERE sd.discount_amount > 0 OR sd.ticket_discount_amount > 0
+            )::bigint as discounted_sales_count,
+            SUM(ROUND(sd.unit_price * sd.quantity, 2)) as gross_amount
+        FROM sale_details sd
+        INNER JOIN sales s ON sd.sale_id = s.id
+        WHERE s.status = true
+        AND DATE(s.completed_at) >= $1::date
+        AND DATE(s.completed_at) <= $2::date
+        "#,
+        [
+            params.date_from.clone().into(),
+            params.date_to.clone().into(),
+        ],
+    );
+
+    let main_result = DiscountsMainRaw::find_by_statement(main_query)
+        .one(db)
+        .await
+        .map_err(|_| DB_ERROR)?
+        .unwrap_or(DiscountsMainRaw {
+            line_discounts: Some(Decimal::ZERO),
+            ticket_discounts: Some(Decimal::ZERO),
+            discounted_sales_count: Some(0),
+            gross_amount: Some(Decimal::ZERO),
+        });
+
+    let line_discounts = main_result.line_discounts.unwrap_or(Decimal::ZERO);
+    let ticket_discounts = main_result.ticket_discounts.unwrap_or(Decimal::ZERO);
+    let total_discounts = line_discounts + ticket_discounts;
+    let gross_amount = main_result.gross_amount.unwrap_or(Decimal::ZERO);
+
+    let discount_percentage = if gross_amount > Decimal::ZERO {
+        (total_discounts / gross_amount) * Decimal::from(100)
+    } else {
+        Decimal::ZERO
+    };
+
+    // Descuentos por cajero
+    let by_user_query = Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        r#"
+        SELECT
+            u.id as user_id,
+            CONCAT(u.first_name, ' ', u.last_name) as user_name,
+            COUNT(DISTINCT s.id)::bigint as sales_count,
+            SUM(sd.discount_amount + sd.ticket_discount_amount) as total_discount
+        FROM sale_details sd
+        INNER JOIN sales s ON sd.sale_id = s.id
+        INNER JOIN users u ON s.created_by = u.id
+        WHERE s.status = true
+        AND (sd.discount_amount > 0 OR sd.ticket_discount_amount > 0)
+        AND DATE(s.completed_at) >= $1::date
+        AND DATE(s.completed_at) <= $2::date
+        GROUP BY u.id, u.first_name, u.last_name
+        ORDER BY total_discount DESC
+        "#,
+        [
+            params.date_from.clone().into(),
+            params.date_to.clone().into(),
+        ],
+    );
+
+    let by_user: Vec<DiscountsByUserItem> = DiscountsByUserRaw::find_by_statement(by_user_query)
+        .all(db)
+        .await
+        .map_err(|_| DB_ERROR)?
+        .into_iter()
+        .map(|row| DiscountsByUserItem {
+            user_id: row.user_id,
+            user_name: row.user_name,
+            sales_count: row.sales_count.unwrap_or(0),
+            total_discount: row.total_discount.unwrap_or(Decimal::ZERO),
+        })
+        .collect();
+
+    // Productos con más descuento (top 10)
+    let top_products_query = Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        r#"
+        SELECT
+            p.id as product_id,
+            p.name as product_name,
+            SUM(sd.quantity) as quantity,
+            SUM(sd.discount_amount + sd.ticket_discount_amount) as total_discount
+        FROM sale_details sd
+        INNER JOIN sales s ON sd.sale_id = s.id
+        INNER JOIN products p ON sd.product_id = p.id
+        WHERE s.status = true
+        AND (sd.discount_amount > 0 OR sd.ticket_discount_amount > 0)
+        AND DATE(s.completed_at) >= $1::date
+        AND DATE(s.completed_at) <= $2::date
+        GROUP BY p.id, p.name
+        ORDER BY total_discount DESC
+        LIMIT 10
+        "#,
+        [
+            params.date_from.clone().into(),
+            params.date_to.clone().into(),
+        ],
+    );
+
+    let top_discounted_products: Vec<DiscountedProductItem> =
+        DiscountedProductRaw::find_by_statement(top_products_query)
+            .all(db)
+            .await
+            .map_err(|_| DB_ERROR)?
+            .into_iter()
+            .map(|row| DiscountedProductItem {
+                product_id: row.product_id,
+                product_name: row.product_name,
+                quantity: row.quantity.unwrap_or(Decimal::ZERO),
+                total_discount: row.total_discount.unwrap_or(Decimal::ZERO),
+            })
+            .collect();
+
+    Ok(DiscountsReportResult {
+        line_discounts,
+        ticket_discounts,
+        total_discounts,
+        discounted_sales_count: main_result.discounted_sales_count.unwrap_or(0),
+        gross_amount,
+        discount_percentage,
+        by_user,
+        top_discounted_products,
+    })
+}
+
+// ============================================================================
+// 8. REPORTE DE PROMOCIONES
+// ============================================================================
+
+#[derive(FromQueryResult)]
+struct PromotionReportRaw {
+    promotion_id: i32,
+    promotion_name: String,
+    promotion_type: String,
+    sales_count: Option<i64>,
+    units_sold: Option<Decimal>,
+    discount_amount: Option<Decimal>,
+    net_sales: Option<Decimal>,
+}
+
+#[tauri::command]
+pub async fn get_promotions_report(
+    state: tauri::State<'_, AppState>,
+    params: PromotionsReportParams,
+) -> Result<PromotionsReportResult, String> {
+    require_permission(&state, "reports.sales").map_err(|e| e.to_string())?;
+    validate_date_range(&params.date_from, &params.date_to).map_err(|e| e.to_string())?;
+
+    let db = &state.database;
+
+    let query = Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        r#"
+        SELECT
+            pr.id as promotion_id,
+            pr.name as promotion_name,
+            pr.promotion_type,
+            COUNT(DISTINCT s.id)::bigint as sales_count,
+            SUM(sd.quantity) as units_sold,
+            SUM(sd.promotion_discount_amount) as discount_amount,
+            SUM(sd.total) as net_sales
+        FROM sale_details sd
+        INNER JOIN sales s ON sd.sale_id = s.id
+        INNER JOIN promotions pr ON sd.promotion_id = pr.id
+        WHERE s.status = true
+        AND DATE(s.completed_at) >= $1::date
+        AND DATE(s.completed_at) <= $2::date
+        GROUP BY pr.id, pr.name, pr.promotion_type
+        ORDER BY discount_amount DESC
+        "#,
+        [
+            params.date_from.clone().into(),
+            params.date_to.clone().into(),
+        ],
+    );
+
+    let items: Vec<PromotionReportItem> = PromotionReportRaw::find_by_statement(query)
+        .all(db)
+        .await
+        .map_err(|_| DB_ERROR)?
+        .into_iter()
+        .map(|row| PromotionReportItem {
+            promotion_id: row.promotion_id,
+            promotion_name: row.promotion_name,
+            promotion_type: row.promotion_type,
+            sales_count: row.sales_count.unwrap_or(0),
+            units_sold: row.units_sold.unwrap_or(Decimal::ZERO),
+            discount_amount: row.discount_amount.unwrap_or(Decimal::ZERO),
+            net_sales: row.net_sales.unwrap_or(Decimal::ZERO),
+        })
+        .collect();
+
+    let total_discount = items.iter().map(|item| item.discount_amount).sum();
+    let total_net_sales = items.iter().map(|item| item.net_sales).sum();
+
+    Ok(PromotionsReportResult {
+        items,
+        total_discount,
+        total_net_sales,
+    })
+}
+
+// ============================================================================
+// 9. RESUMEN DE IMPUESTOS
+// ============================================================================
+
+#[derive(FromQueryResult)]
+struct TaxSummaryRaw {
+    tax_type: String,
+    rate: Decimal,
+    is_exempt: bool,
+    sales_count: Option<i64>,
+    base: Option<Decimal>,
+    amount: Option<Decimal>,
+}
+
+/// Impuestos trasladados en las ventas completadas del período, agrupados por
+/// impuesto y tasa. Exento y tasa 0% se reportan por separado.
+#[tauri::command]
+pub async fn get_tax_summary_report(
+    state: tauri::State<'_, AppState>,
+    params: TaxSummaryParams,
+) -> Result<TaxSummaryResult, String> {
+    require_permission(&state, "reports.financial").map_err(|e| e.to_string())?;
+    validate_date_range(&params.date_from, &params.date_to).map_err(|e| e.to_string())?;
+
+    let db = &state.database;
+
+    let query = Statement::from_sql_and_values(
+        DatabaseBackend::Postgres,
+        r#"
+        SELECT
+            sdt.tax_type,
+            sdt.rate,
+            sdt.is_exempt,
+            COUNT(DISTINCT s.id)::bigint as sales_count,
+            SUM(sdt.base) as base,
+            SUM(sdt.amount) as amount
+        FROM sale_detail_taxes sdt
+        INNER JOIN sale_details sd ON sdt.sale_detail_id = sd.id
+        INNER JOIN sales s ON sd.sale_id = s.id
+        WHERE s.status = true
+        AND DATE(s.completed_at) >= $1::date
+        AND DATE(s.completed_at) <= $2::date
+        GROUP BY sdt.tax_type, sdt.rate, sdt.is_exempt
+        ORDER BY sdt.tax_type DESC, sdt.is_exempt, sdt.rate DESC
+        "#,
+        [
+            params.date_from.clone().into(),
+            params.date_to.clone().into(),
+        ],
+    );
+
+    let items: Vec<TaxSummaryItem> = TaxSummaryRaw::find_by_statement(query)
+        .all(db)
+        .await
+        .map_err(|_| DB_ERROR)?
+        .into_iter()
+        .map(|row| TaxSummaryItem {
+            tax_type: row.tax_type,
+            rate: row.rate,
+            is_exempt: row.is_exempt,
+            sales_count: row.sales_count.unwrap_or(0),
+            base: row.base.unwrap_or(Decimal::ZERO),
+            amount: row.amount.unwrap_or(Decimal::ZERO),
+        })
+        .collect();
+
+    let total_amount = items.iter().map(|item| item.amount).sum();
+
+    Ok(TaxSummaryResult {
+        items,
+        total_amount,
+    })
+}
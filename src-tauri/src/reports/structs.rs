@@ -45,7 +45,7 @@ pub struct DashboardResult {
     /// Producto más vendido
     pub top_product: Option<String>,
     /// Cantidad del producto más vendido
-    pub top_product_quantity: i64,
+    pub top_product_quantity: Decimal,
 }
 
 // ============================================================================
@@ -103,11 +103,11 @@ pub struct ProductReportItem {
     pub product_name: String,
     pub category_name: Option<String>,
     /// Cantidad vendida
-    pub quantity_sold: i64,
+    pub quantity_sold: Decimal,
     /// Cantidad reembolsada
-    pub quantity_refunded: i64,
+    pub quantity_refunded: Decimal,
     /// Cantidad neta (vendida - reembolsada)
-    pub net_quantity: i64,
+    pub net_quantity: Decimal,
     /// Ingreso bruto
     pub gross_revenue: Decimal,
     /// Ingreso neto (gross_revenue - refunded_amount)
@@ -121,9 +121,9 @@ pub struct ProductReportItem {
 pub struct ProductReportResult {
     pub items: Vec<ProductReportItem>,
     /// Totales
-    pub total_quantity_sold: i64,
-    pub total_quantity_refunded: i64,
-    pub total_net_quantity: i64,
+    pub total_quantity_sold: Decimal,
+    pub total_quantity_refunded: Decimal,
+    pub total_net_quantity: Decimal,
     pub total_gross_revenue: Decimal,
     pub total_net_revenue: Decimal,
 }
@@ -147,7 +147,7 @@ pub struct CategoryReportItem {
     /// Ventas netas de la categoría
     pub net_sales: Decimal,
     /// Cantidad vendida
-    pub quantity_sold: i64,
+    pub quantity_sold: Decimal,
     /// Porcentaje del total
     pub share_percentage: Decimal,
 }
@@ -158,7 +158,7 @@ pub struct CategoryReportResult {
     pub items: Vec<CategoryReportItem>,
     /// Totales
     pub total_net_sales: Decimal,
-    pub total_quantity_sold: i64,
+    pub total_quantity_sold: Decimal,
 }
 
 // ============================================================================
@@ -210,7 +210,7 @@ pub struct RefundsReportParams {
 pub struct TopRefundedProduct {
     pub product_id: i32,
     pub product_name: String,
-    pub quantity_refunded: i64,
+    pub quantity_refunded: Decimal,
     pub amount_refunded: Decimal,
 }
 
@@ -228,3 +228,124 @@ pub struct RefundsReportResult {
     /// Productos más reembolsados (top 10)
     pub top_refunded_products: Vec<TopRefundedProduct>,
 }
+
+// ============================================================================
+// 7. REPORTE DE DESCUENTOS
+// ============================================================================
+
+/// Parámetros para el reporte de descuentos
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DiscountsReportParams {
+    pub date_from: String,
+    pub date_to: String,
+}
+
+/// Descuentos otorgados por un usuario
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DiscountsByUserItem {
+    pub user_id: String,
+    pub user_name: String,
+    pub sales_count: i64,
+    pub total_discount: Decimal,
+}
+
+/// Producto con más descuento otorgado
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DiscountedProductItem {
+    pub product_id: i32,
+    pub product_name: String,
+    pub quantity: Decimal,
+    pub total_discount: Decimal,
+}
+
+/// Resultado del reporte de descuentos
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DiscountsReportResult {
+    /// Descuentos aplicados por línea
+    pub line_discounts: Decimal,
+    /// Descuentos aplicados al ticket
+    pub ticket_discounts: Decimal,
+    /// Suma de ambos descuentos (antes de impuestos)
+    pub total_discounts: Decimal,
+    /// Ventas con al menos un descuento
+    pub discounted_sales_count: i64,
+    /// Importe bruto de las ventas del período antes de descuentos
+    pub gross_amount: Decimal,
+    /// Porcentaje de descuento sobre el importe bruto
+    pub discount_percentage: Decimal,
+    /// Descuentos por cajero
+    pub by_user: Vec<DiscountsByUserItem>,
+    /// Productos con más descuento (top 10)
+    pub top_discounted_products: Vec<DiscountedProductItem>,
+}
+
+// ============================================================================
+// 8. REPORTE DE PROMOCIONES
+// ============================================================================
+
+/// Parámetros para el reporte de promociones
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PromotionsReportParams {
+    pub date_from: String,
+    pub date_to: String,
+}
+
+/// Impacto de una promoción en el período
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PromotionReportItem {
+    pub promotion_id: i32,
+    pub promotion_name: String,
+    pub promotion_type: String,
+    /// Ventas en las que se aplicó
+    pub sales_count: i64,
+    /// Piezas vendidas con la promoción
+    pub units_sold: Decimal,
+    /// Descuento otorgado por la promoción (antes de impuestos)
+    pub discount_amount: Decimal,
+    /// Venta neta con impuestos de las líneas promocionadas
+    pub net_sales: Decimal,
+}
+
+/// Resultado del reporte de promociones
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PromotionsReportResult {
+    pub items: Vec<PromotionReportItem>,
+    pub total_discount: Decimal,
+    pub total_net_sales: Decimal,
+}
+
+// ============================================================================
+// 9. RESUMEN DE IMPUESTOS
+// ============================================================================
+
+/// Parámetros para el resumen de impuestos
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TaxSummaryParams {
+    pub date_from: String,
+    pub date_to: String,
+}
+
+/// Impuesto trasladado agrupado por tipo y tasa
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TaxSummaryItem {
+    /// iva, ieps
+    pub tax_type: String,
+    /// Tasa en decimal (0.16 = 16%)
+    pub rate: Decimal,
+    /// Exento; se reporta aparte de la tasa 0%
+    pub is_exempt: bool,
+    /// Ventas con líneas gravadas por este impuesto
+    pub sales_count: i64,
+    /// Base gravable
+    pub base: Decimal,
+    /// Impuesto trasladado
+    pub amount: Decimal,
+}
+
+/// Resultado del resumen de impuestos
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TaxSummaryResult {
+    pub items: Vec<TaxSummaryItem>,
+    /// Total de impuestos trasladados
+    pub total_amount: Decimal,
+}
@@ -0,0 +1,277 @@
+//! Cálculo de importes de una venta: precios, promociones y descuentos
+
+use chrono::Local;
+use sea_orm::{
+    prelude::Decimal, sea_query::Condition, ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter,
+};
+
+use super::pricing::{
+    compute_line, discount_amount, exceeds_discount_limit, line_gross, prorate, LineTotals,
+};
+use super::structs::{DiscountRequest, DiscountType, SaleItemRequest};
+use crate::entities::{
+    coupon_batches,
+    prelude::{Products, Promotions},
+    products, promotions,
+};
+use crate::products::structs::QUANTITY_SCALE;
+use crate::products::ProductHandlers::load_product_taxes;
+use crate::promotions::engine::{apply_promotions, AppliedPromotion, PromotionLine};
+use crate::sessions::structs::Session;
+
+const DB_ERROR: &str = "Error en la base de datos";
+
+/// Línea de venta con sus importes calculados
+#[derive(Debug, Clone)]
+pub struct PricedLine {
+    pub item: SaleItemRequest,
+    pub product: products::Model,
+    pub unit_price: Decimal,
+    pub promotion: Option<AppliedPromotion>,
+    pub line_discount: Decimal,   // Descuento manual de la línea
+    pub ticket_discount: Decimal, // Parte prorrateada del descuento al ticket
+    pub coupon_discount: Decimal, // Parte prorrateada del cupón
+    pub totals: LineTotals,
+}
+
+/// Venta con todas sus líneas calculadas
+#[derive(Debug, Clone)]
+pub struct PricedSale {
+    pub lines: Vec<PricedLine>,
+    pub ticket_discount: Decimal,
+    pub coupon_discount: Decimal,
+    pub subtotal: Decimal,
+    pub total: Decimal,
+}
+
+/// Verifica que haya existencias para cada línea. Las cotizaciones no lo requieren;
+/// las ventas lo vuelven a garantizar al descontar el stock dentro de la transacción.
+pub fn check_stock(lines: &[PricedLine]) -> Result<(), String> {
+    for line in lines {
+        if line.product.stock < line.item.quantity {
+            return Err(format!(
+                "Stock insuficiente para '{}'. Disponible: {}, Solicitado: {}",
+                line.product.name,
+                line.product.stock.normalize(),
+                line.item.quantity.normalize()
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Valida que la cantidad de un producto sea positiva, con a lo más tres decimales,
+/// y entera si el producto no se vende en fracciones
+pub fn check_quantity(product: &products::Model, quantity: Decimal) -> Result<(), String> {
+    if quantity <= Decimal::ZERO {
+        return Err("Las cantidades deben ser positivas".to_string());
+    }
+    if quantity.round_dp(QUANTITY_SCALE) != quantity {
+        return Err(format!(
+            "La cantidad de '{}' admite a lo más {} decimales",
+            product.name, QUANTITY_SCALE
+        ));
+    }
+    if !product.allow_fractions && !quantity.fract().is_zero() {
+        return Err(format!(
+            "El producto '{}' solo se vende en cantidades enteras",
+            product.name
+        ));
+    }
+
+    Ok(())
+}
+
+/// Importe del cupón sobre la base indicada; un monto fijo no excede la base
+fn coupon_amount(base: Decimal, batch: &coupon_batches::Model) -> Result<Decimal, String> {
+    if base < batch.min_purchase {
+        return Err(format!(
+            "El cupón requiere una compra mínima de {} antes de impuestos",
+            batch.min_purchase
+        ));
+    }
+
+    match batch.discount_type.as_str() {
+        "percent" => discount_amount(
+            base,
+            &DiscountRequest {
+                discount_type: DiscountType::Percent,
+                value: batch.discount_value,
+            },
+        ),
+        _ => Ok(batch.discount_value.min(base).round_dp(2)),
+    }
+}
+
+/// Valida los productos y calcula los importes de la venta con precios del catálogo,
+/// promociones vigentes, descuentos manuales dentro del límite del perfil y el cupón
+/// canjeado, si lo hay.
+pub async fn price_sale_items<C: ConnectionTrait>(
+    db: &C,
+    session: &Session,
+    items: &[SaleItemRequest],
+    discount: Option<&DiscountRequest>,
+    coupon: Option<&coupon_batches::Model>,
+) -> Result<PricedSale, String> {
+    // 1. Validar que los productos existan y estén activos, y sus cantidades
+    let mut products_list = Vec::with_capacity(items.len());
+    for item in items {
+        let product = Products::find_by_id(item.product_id)
+            .one(db)
+            .await
+            .map_err(|_| format!("Error al consultar producto {}", item.product_id))?
+            .ok_or(format!("Producto {} no encontrado", item.product_id))?;
+
+        if !product.is_active {
+            return Err(format!("El producto '{}' no está disponible", product.name));
+        }
+        check_quantity(&product, item.quantity)?;
+
+        products_list.push(product);
+    }
+
+    // 2. Evaluar promociones vigentes de los productos y sus categorías
+    let product_ids: Vec<i32> = products_list.iter().map(|product| product.id).collect();
+    let product_taxes = load_product_taxes(db, &product_ids).await?;
+    let category_ids: Vec<i32> = products_list
+        .iter()
+        .filter_map(|product| product.category_id)
+        .collect();
+
+    let promotions_list = Promotions::find()
+        .filter(promotions::Column::IsActive.eq(true))
+        .filter(
+            Condition::any()
+                .add(promotions::Column::ProductId.is_in(product_ids))
+                .add(promotions::Column::CategoryId.is_in(category_ids)),
+        )
+        .all(db)
+        .await
+        .map_err(|_| DB_ERROR)?;
+
+    let unit_prices: Vec<Decimal> = items
+        .iter()
+        .zip(&products_list)
+        .map(|(item, product)| item.price_override.unwrap_or(product.price))
+        .collect();
+
+    let promotion_lines: Vec<PromotionLine> = items
+        .iter()
+        .zip(&products_list)
+        .zip(&unit_prices)
+        .map(|((item, product), unit_price)| PromotionLine {
+            product_id: product.id,
+            category_id: product.category_id,
+            unit_price: *unit_price,
+            quantity: item.quantity,
+        })
+        .collect();
+
+    let applied = apply_promotions(
+        &promotion_lines,
+        &promotions_list,
+        Local::now().naive_local(),
+    );
+
+    // 3. Descuentos manuales por línea sobre el importe con promoción
+    let mut net_amounts = Vec::with_capacity(items.len());
+    let mut line_discounts = Vec::with_capacity(items.len());
+    for ((item, unit_price), promotion) in items.iter().zip(&unit_prices).zip(&applied) {
+        let promotion_amount = promotion.map_or(Decimal::ZERO, |p| p.amount);
+        let base = line_gross(*unit_price, item.quantity) - promotion_amount;
+        let line_discount = match &item.discount {
+            Some(discount) => discount_amount(base, discount)?,
+            None => Decimal::ZERO,
+        };
+
+        net_amounts.push(base - line_discount);
+        line_discounts.push(line_discount);
+    }
+
+    // 4. Prorratear el descuento al ticket sobre el importe neto de cada línea
+    let ticket_discount = match discount {
+        Some(discount) => discount_amount(net_amounts.iter().sum(), discount)?,
+        None => Decimal::ZERO,
+    };
+    let ticket_shares = prorate(ticket_discount, &net_amounts);
+
+    // 5. Aplicar el cupón sobre el importe que queda después de los demás descuentos
+    let coupon_bases: Vec<Decimal> = net_amounts
+        .iter()
+        .zip(&ticket_shares)
+        .map(|(net, share)| net - share)
+        .collect();
+    let coupon_discount = match coupon {
+        Some(batch) => coupon_amount(coupon_bases.iter().sum(), batch)?,
+        None => Decimal::ZERO,
+    };
+    let coupon_shares = prorate(coupon_discount, &coupon_bases);
+
+    // 6. Calcular importes finales de cada línea
+    let mut lines = Vec::with_capacity(items.len());
+    let mut subtotal = Decimal::ZERO;
+    let mut total = Decimal::ZERO;
+
+    for (index, (item, product)) in items.iter().zip(products_list).enumerate() {
+        let promotion = applied[index];
+        let promotion_amount = promotion.map_or(Decimal::ZERO, |p| p.amount);
+        let totals = compute_line(
+            unit_prices[index],
+            item.quantity,
+            product_taxes
+                .get(&product.id)
+                .map_or(&[][..], |taxes| taxes.as_slice()),
+            promotion_amount + line_discounts[index] + ticket_shares[index] + coupon_shares[index],
+        );
+
+        subtotal += totals.subtotal;
+        total += totals.total;
+        lines.push(PricedLine {
+            item: item.clone(),
+            product,
+            unit_price: unit_prices[index],
+            promotion,
+            line_discount: line_discounts[index],
+            ticket_discount: ticket_shares[index],
+            coupon_discount: coupon_shares[index],
+            totals,
+        });
+    }
+
+    // 7. Validar que los descuentos manuales estén dentro del límite del perfil.
+    // Las promociones y los cupones no cuentan para el límite.
+    let has_manual_discount = lines
+        .iter()
+        .any(|line| line.line_discount + line.ticket_discount > Decimal::ZERO);
+    if has_manual_discount {
+        if !session.has_permission("sales.discount") {
+            return Err("No tiene permisos para aplicar descuentos".to_string());
+        }
+
+        if let Some(max_percent) = session.max_discount_percent {
+            for line in &lines {
+                let promotion_amount = line.promotion.map_or(Decimal::ZERO, |p| p.amount);
+                if exceeds_discount_limit(
+                    line.line_discount + line.ticket_discount,
+                    line.totals.gross - promotion_amount,
+                    max_percent,
+                ) {
+                    return Err(format!(
+                        "El descuento en '{}' excede el máximo permitido para su perfil ({}%)",
+                        line.product.name,
+                        max_percent.normalize()
+                    ));
+                }
+            }
+        }
+    }
+
+    Ok(PricedSale {
+        lines,
+        ticket_discount,
+        coupon_discount,
+        subtotal,
+        total,
+    })
+}
@@ -1,21 +1,55 @@
+use std::collections::HashMap;
+
+use chrono::{Duration, Local, Utc};
 use sea_orm::{
-    prelude::Decimal, ActiveModelTrait, ActiveValue::Set, ColumnTrait, EntityTrait, PaginatorTrait,
-    QueryFilter, QueryOrder, QuerySelect, TransactionTrait,
+    prelude::{Decimal, Expr},
+    sea_query::{LikeExpr, Query},
+    ActiveModelTrait,
+    ActiveValue::Set,
+    ColumnTrait, ConnectionTrait, EntityTrait, ExprTrait, Order, PaginatorTrait, QueryFilter,
+    QueryOrder, QuerySelect, TransactionTrait,
 };
 
+use super::checkout::{check_quantity, check_stock, price_sale_items};
+use super::pricing::amounts_match;
 use super::structs::{
-    CreateSaleRequest, CreateSaleResponse, PaymentMethodResponse, Sale, SaleFilter,
-    SaleListResponse,
+    CancelSaleRequest, CreateRefundRequest, CreateRefundResponse, CreateSaleRequest,
+    CreateSaleResponse, PaymentMethodResponse, RefundDetail, RefundLineDetail, Sale,
+    SaleDetailResponse, SaleFilter, SaleLineDetail, SaleLineTax, SaleListResponse,
+    SalePaymentDetail, SalePaymentRequest, SalePreviewLine, SalePreviewRequest,
+    SalePreviewResponse,
 };
+use crate::cash_sessions::structs::TENDER_CASH;
+use crate::cash_sessions::CashSessionsHandler::{find_open_session, require_open_session};
+use crate::coupons::CouponsHandler::find_redeemable_coupon;
+use crate::customer_credit::structs::{ENTRY_CANCELLATION, ENTRY_CHARGE, TENDER_CREDIT};
+use crate::customer_credit::CustomerCreditHandler::{credit_refund, post_credit_entry};
 use crate::entities::{
-    payment_methods,
-    prelude::{PaymentMethods, Products, Sales},
-    products, sale_details, sale_payments, sales,
+    cash_sessions, coupons, payment_methods,
+    prelude::{
+        Coupons, Customers, ParkedSales, PaymentMethods, Products, Promotions, RefundDetails,
+        Refunds, SaleDetailTaxes, SaleDetails, SalePayments, Sales, Users,
+    },
+    products, promotions, refund_details, refunds, sale_detail_taxes, sale_details, sale_payments,
+    sales, users,
+};
+use crate::gift_cards::structs::{GiftCard, ENTRY_ISSUE, TENDER_GIFT_CARD};
+use crate::gift_cards::GiftCardsHandler::{
+    issue_gift_card, redeem_gift_card, refund_to_gift_card, reverse_sale_gift_cards,
+    sale_gift_cards,
 };
-use crate::sessions::require_permission;
+use crate::layaways::structs::{LayawayConfig, LAYAWAY_PENDING};
+use crate::loyalty::structs::TENDER_POINTS;
+use crate::loyalty::LoyaltyHandler::{
+    accrue_sale_points, redeem_sale_points, refund_sale_points, reverse_sale_points,
+};
+use crate::printer::{structs::PrinterConfig, PrinterHandler::print_sale};
+use crate::quotes::QuotesHandler::mark_quote_converted;
+use crate::sessions::{require_permission, structs::Session};
 use crate::AppState;
 
 const DB_ERROR: &str = "Error en la base de datos";
+const IDEMPOTENCY_KEY_MAX_LEN: usize = 64; // Largo de sales.idempotency_key
 
 /// Obtiene una página de ventas con filtros opcionales
 #[tauri::command]
@@ -50,6 +84,77 @@ pub async fn get_sales(
         }
     }
 
+    // Filtrar por cliente
+    if let Some(customer_id) = filters.customer_id {
+        query = query.filter(sales::Column::CustomerId.eq(customer_id));
+    }
+
+    // Filtrar por cajero
+    if let Some(created_by) = filters.created_by {
+        query = query.filter(sales::Column::CreatedBy.eq(created_by));
+    }
+
+    // Filtrar por rango de total
+    if let Some(min_total) = filters.min_total {
+        query = query.filter(sales::Column::Total.gte(min_total));
+    }
+    if let Some(max_total) = filters.max_total {
+        query = query.filter(sales::Column::Total.lte(max_total));
+    }
+
+    // Filtrar por inicio del folio
+    if let Some(id_prefix) = filters.id_prefix {
+        let id_prefix = id_prefix.trim().to_lowercase();
+        if !id_prefix.is_empty() {
+            // `%` y `_` se buscan literalmente, no como comodines de LIKE
+            let pattern = id_prefix
+                .replace('\\', "\\\\")
+                .replace('%', "\\%")
+                .replace('_', "\\_");
+            query = query.filter(
+                sales::Column::Id.like(LikeExpr::new(format!("{}%", pattern)).escape('\\')),
+            );
+        }
+    }
+
+    // Ventas pagadas (total o parcialmente) con la forma de pago indicada
+    if let Some(payment_method_id) = filters.payment_method_id {
+        query = query.filter(
+            sales::Column::Id.in_subquery(
+                Query::select()
+                    .column(sale_payments::Column::SaleId)
+                    .from(SalePayments)
+                    .and_where(sale_payments::Column::PaymentMethodId.eq(payment_method_id))
+                    .to_owned(),
+            ),
+        );
+    }
+
+    // Ventas que incluyen el producto indicado
+    if let Some(product_id) = filters.product_id {
+        query = query.filter(
+            sales::Column::Id.in_subquery(
+                Query::select()
+                    .column(sale_details::Column::SaleId)
+                    .from(SaleDetails)
+                    .and_where(sale_details::Column::ProductId.eq(product_id))
+                    .to_owned(),
+            ),
+        );
+    }
+
+    // Ordenamiento: por fecha o total, descendente por defecto
+    let sort_column = match filters.sort_by.as_deref() {
+        None | Some("date") => sales::Column::CreatedAt,
+        Some("total") => sales::Column::Total,
+        Some(_) => return Err("Campo de ordenamiento inválido".to_string()),
+    };
+    let sort_order = match filters.sort_order.as_deref() {
+        None | Some("desc") => Order::Desc,
+        Some("asc") => Order::Asc,
+        Some(_) => return Err("Dirección de ordenamiento inválida".to_string()),
+    };
+
     // Contar total de items para paginación
     let total_items = query.clone().count(db).await.map_err(|_| DB_ERROR)?;
 
@@ -62,6 +167,7 @@ pub async fn get_sales(
     // Aplicar paginación y ordenamiento
     let offset = (filters.page.saturating_sub(1)) * filters.limit;
     let sales_models = query
+        .order_by(sort_column, sort_order)
         .order_by_desc(sales::Column::CreatedAt)
         .offset(offset)
         .limit(filters.limit)
@@ -79,6 +185,305 @@ pub async fn get_sales(
     })
 }
 
+/// Obtiene el detalle completo de una venta: líneas, pagos, devoluciones y usuarios
+#[tauri::command]
+pub async fn get_sale_detail(
+    state: tauri::State<'_, AppState>,
+    sale_id: String,
+) -> Result<SaleDetailResponse, String> {
+    require_permission(&state, "sales.view")?;
+    load_sale_detail(&state.database, &sale_id).await
+}
+
+/// Nombres de las promociones indicadas
+async fn promotion_names<C: ConnectionTrait>(
+    db: &C,
+    promotion_ids: Vec<i32>,
+) -> Result<HashMap<i32, String>, String> {
+    if promotion_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    Ok(Promotions::find()
+        .filter(promotions::Column::Id.is_in(promotion_ids))
+        .all(db)
+        .await
+        .map_err(|_| DB_ERROR)?
+        .into_iter()
+        .map(|promotion| (promotion.id, promotion.name))
+        .collect())
+}
+
+/// Carga el detalle de una venta; compartido con la impresión de tickets
+pub async fn load_sale_detail<C: ConnectionTrait>(
+    db: &C,
+    sale_id: &str,
+) -> Result<SaleDetailResponse, String> {
+    let sale = Sales::find_by_id(sale_id)
+        .one(db)
+        .await
+        .map_err(|_| DB_ERROR)?
+        .ok_or("Venta no encontrada")?;
+
+    // Líneas con su producto y promoción
+    let details = SaleDetails::find()
+        .find_also_related(Products)
+        .filter(sale_details::Column::SaleId.eq(&sale.id))
+        .order_by_asc(sale_details::Column::Id)
+        .all(db)
+        .await
+        .map_err(|_| DB_ERROR)?;
+
+    let promotion_ids: Vec<i32> = details
+        .iter()
+        .filter_map(|(detail, _)| detail.promotion_id)
+        .collect();
+    let promotion_names = promotion_names(db, promotion_ids).await?;
+
+    // Desglose de impuestos de cada línea
+    let detail_ids: Vec<i32> = details.iter().map(|(detail, _)| detail.id).collect();
+    let mut line_taxes: HashMap<i32, Vec<SaleLineTax>> = HashMap::new();
+    for tax in SaleDetailTaxes::find()
+        .filter(sale_detail_taxes::Column::SaleDetailId.is_in(detail_ids))
+        .order_by_asc(sale_detail_taxes::Column::Id)
+        .all(db)
+        .await
+        .map_err(|_| DB_ERROR)?
+    {
+        line_taxes
+            .entry(tax.sale_detail_id)
+            .or_default()
+            .push(SaleLineTax::from(tax));
+    }
+
+    let lines = details
+        .into_iter()
+        .map(|(detail, product)| SaleLineDetail {
+            id: detail.id,
+            product_id: detail.product_id,
+            product_name: product.as_ref().map(|p| p.name.clone()),
+            product_code: product.as_ref().map(|p| p.code.clone()),
+            unit_of_measure: product.map(|p| p.unit_of_measure),
+            quantity: detail.quantity,
+            unit_price: detail.unit_price,
+            discount_type: detail.discount_type,
+            discount_value: detail.discount_value,
+            discount_amount: detail.discount_amount,
+            ticket_discount_amount: detail.ticket_discount_amount,
+            promotion_name: detail
+                .promotion_id
+                .and_then(|id| promotion_names.get(&id).cloned()),
+            promotion_id: detail.promotion_id,
+            promotion_discount_amount: detail.promotion_discount_amount,
+            coupon_discount_amount: detail.coupon_discount_amount,
+            subtotal: detail.subtotal,
+            tax_rate: detail.tax_rate,
+            tax_amount: detail.tax_amount,
+            taxes: line_taxes.remove(&detail.id).unwrap_or_default(),
+            total: detail.total,
+        })
+        .collect();
+
+    // Pagos con su método
+    let payments = SalePayments::find()
+        .find_also_related(PaymentMethods)
+        .filter(sale_payments::Column::SaleId.eq(&sale.id))
+        .order_by_asc(sale_payments::Column::Id)
+        .all(db)
+        .await
+        .map_err(|_| DB_ERROR)?
+        .into_iter()
+        .map(|(payment, method)| SalePaymentDetail {
+            id: payment.id,
+            payment_method_id: payment.payment_method_id,
+            payment_method_name: method.map(|m| m.name),
+            amount: payment.amount,
+            created_at: payment.created_at.to_string(),
+        })
+        .collect();
+
+    // Devoluciones con sus líneas
+    let refunds_models = Refunds::find()
+        .filter(refunds::Column::SaleId.eq(&sale.id))
+        .order_by_asc(refunds::Column::CreatedAt)
+        .all(db)
+        .await
+        .map_err(|_| DB_ERROR)?;
+
+    let refund_ids: Vec<i32> = refunds_models.iter().map(|r| r.id).collect();
+    let refund_lines = RefundDetails::find()
+        .find_also_related(Products)
+        .filter(refund_details::Column::RefundId.is_in(refund_ids))
+        .order_by_asc(refund_details::Column::Id)
+        .all(db)
+        .await
+        .map_err(|_| DB_ERROR)?;
+
+    // Nombres completos de los usuarios involucrados
+    let mut user_ids: Vec<String> = vec![sale.created_by.clone(), sale.updated_by.clone()];
+    user_ids.extend(sale.cancelled_by.clone());
+    user_ids.extend(refunds_models.iter().map(|r| r.created_by.clone()));
+
+    let user_names: HashMap<String, String> = Users::find()
+        .filter(users::Column::Id.is_in(user_ids))
+        .all(db)
+        .await
+        .map_err(|_| DB_ERROR)?
+        .into_iter()
+        .map(|user| (user.id, format!("{} {}", user.first_name, user.last_name)))
+        .collect();
+
+    let refunds = refunds_models
+        .into_iter()
+        .map(|refund| RefundDetail {
+            lines: refund_lines
+                .iter()
+                .filter(|(line, _)| line.refund_id == refund.id)
+                .map(|(line, product)| RefundLineDetail {
+                    id: line.id,
+                    product_id: line.product_id,
+                    product_name: product.as_ref().map(|p| p.name.clone()),
+                    quantity: line.quantity,
+                    unit_price: line.unit_price,
+                })
+                .collect(),
+            id: refund.id,
+            amount: refund.amount,
+            reason: refund.reason,
+            payment_method_id: refund.payment_method_id,
+            created_at: refund.created_at.to_string(),
+            created_by_name: user_names.get(&refund.created_by).cloned(),
+            created_by: refund.created_by,
+        })
+        .collect();
+
+    // Nombre del cliente, si la venta tiene uno asignado
+    let customer_name = match sale.customer_id {
+        Some(customer_id) => Customers::find_by_id(customer_id)
+            .one(db)
+            .await
+            .map_err(|_| DB_ERROR)?
+            .map(|customer| customer.name),
+        None => None,
+    };
+
+    // Código del cupón canjeado, si lo hay
+    let coupon_code = match sale.coupon_id {
+        Some(coupon_id) => Coupons::find_by_id(coupon_id)
+            .one(db)
+            .await
+            .map_err(|_| DB_ERROR)?
+            .map(|coupon| coupon.code),
+        None => None,
+    };
+
+    // Tarjetas de regalo vendidas, con su código para el ticket
+    let gift_cards = sale_gift_cards(db, &sale.id).await?;
+
+    Ok(SaleDetailResponse {
+        customer_name,
+        coupon_code,
+        created_by_name: user_names.get(&sale.created_by).cloned(),
+        updated_by_name: user_names.get(&sale.updated_by).cloned(),
+        cancelled_by_name: sale
+            .cancelled_by
+            .as_ref()
+            .and_then(|id| user_names.get(id).cloned()),
+        sale: Sale::from(sale),
+        lines,
+        payments,
+        refunds,
+        gift_cards,
+    })
+}
+
+/// Calcula los importes de una venta con promociones y descuentos sin registrarla
+#[tauri::command]
+pub async fn preview_sale(
+    state: tauri::State<'_, AppState>,
+    request: SalePreviewRequest,
+) -> Result<SalePreviewResponse, String> {
+    let session = require_permission(&state, "sales.create")?;
+
+    build_sale_preview(&state.database, &session, &request).await
+}
+
+/// Calcula los importes de una venta con las mismas reglas que create_sale
+pub async fn build_sale_preview<C: ConnectionTrait>(
+    db: &C,
+    session: &Session,
+    request: &SalePreviewRequest,
+) -> Result<SalePreviewResponse, String> {
+    if request
+        .items
+        .iter()
+        .any(|item| item.quantity <= Decimal::ZERO)
+    {
+        return Err("Las cantidades deben ser positivas".to_string());
+    }
+
+    let coupon_batch = match &request.coupon_code {
+        Some(code) => Some(find_redeemable_coupon(db, code).await?.1),
+        None => None,
+    };
+
+    let priced = price_sale_items(
+        db,
+        session,
+        &request.items,
+        request.discount.as_ref(),
+        coupon_batch.as_ref(),
+    )
+    .await?;
+    check_stock(&priced.lines)?;
+
+    let promotion_ids: Vec<i32> = priced
+        .lines
+        .iter()
+        .filter_map(|line| line.promotion.map(|promotion| promotion.promotion_id))
+        .collect();
+    let promotion_names = promotion_names(db, promotion_ids).await?;
+
+    let lines = priced
+        .lines
+        .into_iter()
+        .map(|line| SalePreviewLine {
+            product_id: line.product.id,
+            product_name: line.product.name,
+            unit_of_measure: line.product.unit_of_measure,
+            quantity: line.item.quantity,
+            unit_price: line.unit_price,
+            promotion_id: line.promotion.map(|promotion| promotion.promotion_id),
+            promotion_name: line
+                .promotion
+                .and_then(|promotion| promotion_names.get(&promotion.promotion_id).cloned()),
+            promotion_discount_amount: line
+                .promotion
+                .map_or(Decimal::ZERO, |promotion| promotion.amount),
+            discount_amount: line.line_discount,
+            ticket_discount_amount: line.ticket_discount,
+            coupon_discount_amount: line.coupon_discount,
+            subtotal: line.totals.subtotal,
+            tax_amount: line.totals.tax_amount,
+            taxes: line
+                .totals
+                .taxes
+                .into_iter()
+                .map(SaleLineTax::from)
+                .collect(),
+            total: line.totals.total,
+        })
+        .collect();
+
+    Ok(SalePreviewResponse {
+        lines,
+        discount_amount: priced.ticket_discount,
+        coupon_discount_amount: priced.coupon_discount,
+        subtotal: priced.subtotal,
+        total: priced.total,
+    })
+}
+
 /// Obtiene los métodos de pago activos
 #[tauri::command]
 pub async fn get_payment_methods(
@@ -105,140 +510,1155 @@ pub async fn create_sale(
     state: tauri::State<'_, AppState>,
     request: CreateSaleRequest,
 ) -> Result<CreateSaleResponse, String> {
-    // 1. Validar permiso
-    let session = require_permission(&state, "sales.create")?;
-    let db = &state.database;
+    register_sale(&state, request, None, false).await
+}
 
-    // 2. Iniciar transacción
-    let txn = db
-        .begin()
+/// Valida que las formas de pago existan y estén activas, con montos positivos
+pub async fn load_tenders<C: ConnectionTrait>(
+    db: &C,
+    payments: &[SalePaymentRequest],
+) -> Result<Vec<(payment_methods::Model, Decimal)>, String> {
+    if payments.is_empty() {
+        return Err("La venta debe tener al menos una forma de pago".to_string());
+    }
+
+    if payments
+        .iter()
+        .any(|payment| payment.amount <= Decimal::ZERO)
+    {
+        return Err("Los montos de pago deben ser positivos".to_string());
+    }
+
+    let mut tenders = Vec::with_capacity(payments.len());
+    for payment in payments {
+        let payment_method = PaymentMethods::find_by_id(payment.payment_method_id)
+            .one(db)
+            .await
+            .map_err(|_| "Error al validar método de pago")?
+            .ok_or("Método de pago no válido")?;
+
+        if !payment_method.is_active {
+            return Err(format!(
+                "El método de pago '{}' no está disponible",
+                payment_method.name
+            ));
+        }
+
+        if payment_method.tender == TENDER_GIFT_CARD
+            && payment
+                .gift_card_code
+                .as_deref()
+                .is_none_or(|code| code.trim().is_empty())
+        {
+            return Err("Indique el código de la tarjeta de regalo".to_string());
+        }
+
+        tenders.push((payment_method, payment.amount));
+    }
+
+    Ok(tenders)
+}
+
+/// Crea un registro de pago por cada forma de pago, recibido en el turno indicado.
+/// El cambio se descuenta de los pagos en efectivo para que la suma sea igual a lo cobrado.
+/// Lo pagado con tarjeta de regalo se descuenta de su saldo.
+pub async fn insert_payments<C: ConnectionTrait>(
+    db: &C,
+    sale_id: &str,
+    payments: &[SalePaymentRequest],
+    tenders: &[(payment_methods::Model, Decimal)],
+    change: Decimal,
+    cash_session_id: i32,
+    user_id: &str,
+) -> Result<(), String> {
+    let mut pending_change = change;
+    for (payment, (method, amount)) in payments.iter().zip(tenders) {
+        let mut applied = *amount;
+        if method.tender == TENDER_CASH && pending_change > Decimal::ZERO {
+            let deducted = pending_change.min(applied);
+            applied -= deducted;
+            pending_change -= deducted;
+        }
+
+        if applied == Decimal::ZERO {
+            continue;
+        }
+
+        let gift_card_id = match payment
+            .gift_card_code
+            .as_deref()
+            .filter(|_| method.tender == TENDER_GIFT_CARD)
+        {
+            Some(code) => Some(
+                redeem_gift_card(db, code, applied, sale_id, user_id)
+                    .await?
+                    .id,
+            ),
+            None => None,
+        };
+
+        let payment = sale_payments::ActiveModel {
+            sale_id: Set(sale_id.to_string()),
+            payment_method_id: Set(method.id),
+            amount: Set(applied),
+            cash_session_id: Set(Some(cash_session_id)),
+            gift_card_id: Set(gift_card_id),
+            ..Default::default()
+        };
+
+        payment
+            .insert(db)
+            .await
+            .map_err(|e| format!("Error al registrar pago: {:?}", e))?;
+    }
+
+    Ok(())
+}
+
+/// Calcula el cambio de un cobro; solo el efectivo puede exceder el importe a cobrar
+pub fn tender_change(
+    tenders: &[(payment_methods::Model, Decimal)],
+    due: Decimal,
+) -> Result<Decimal, String> {
+    let paid: Decimal = tenders.iter().map(|(_, amount)| *amount).sum();
+    let cash_paid: Decimal = tenders
+        .iter()
+        .filter(|(method, _)| method.tender == TENDER_CASH)
+        .map(|(_, amount)| *amount)
+        .sum();
+
+    let change = (paid - due).max(Decimal::ZERO);
+    if change > cash_paid {
+        return Err("Solo los pagos en efectivo pueden exceder el total de la venta".to_string());
+    }
+
+    Ok(change)
+}
+
+/// Forma de reembolso indicada o efectivo por defecto. El efectivo sale del turno
+/// de caja abierto del usuario; los demás reembolsos se ligan al turno si hay uno.
+pub async fn resolve_refund_method<C: ConnectionTrait>(
+    db: &C,
+    payment_method_id: Option<i32>,
+    user_id: &str,
+) -> Result<(payment_methods::Model, Option<cash_sessions::Model>), String> {
+    let refund_method = match payment_method_id {
+        Some(payment_method_id) => PaymentMethods::find_by_id(payment_method_id)
+            .one(db)
+            .await
+            .map_err(|_| DB_ERROR)?
+            .filter(|method| method.is_active)
+            .ok_or("Método de reembolso no válido")?,
+        None => PaymentMethods::find()
+            .filter(payment_methods::Column::Tender.eq(TENDER_CASH))
+            .one(db)
+            .await
+            .map_err(|_| DB_ERROR)?
+            .ok_or("No está configurado el método de pago en efectivo")?,
+    };
+
+    let cash_session = if refund_method.tender == TENDER_CASH {
+        Some(require_open_session(db, user_id).await?)
+    } else {
+        find_open_session(db, user_id).await?
+    };
+
+    Ok((refund_method, cash_session))
+}
+
+/// Regresa al inventario las piezas de una venta y libera el uso de su cupón
+pub async fn restore_sale_inventory<C: ConnectionTrait>(
+    db: &C,
+    sale: &sales::Model,
+    user_id: &str,
+) -> Result<(), String> {
+    let details = SaleDetails::find()
+        .filter(sale_details::Column::SaleId.eq(&sale.id))
+        .all(db)
         .await
-        .map_err(|_| "Error al iniciar transacción")?;
+        .map_err(|_| DB_ERROR)?;
 
-    // 3. Validar método de pago existe y está activo
-    let payment_method = PaymentMethods::find_by_id(request.payment_method_id)
-        .one(&txn)
+    for detail in &details {
+        Products::update_many()
+            .col_expr(
+                products::Column::Stock,
+                Expr::col(products::Column::Stock).add(detail.quantity),
+            )
+            .col_expr(
+                products::Column::UpdatedBy,
+                Expr::value(user_id.to_string()),
+            )
+            .filter(products::Column::Id.eq(detail.product_id))
+            .exec(db)
+            .await
+            .map_err(|e| format!("Error al actualizar inventario: {:?}", e))?;
+    }
+
+    // Liberar el uso del cupón canjeado para que pueda volver a usarse
+    if let Some(coupon_id) = sale.coupon_id {
+        Coupons::update_many()
+            .col_expr(
+                coupons::Column::TimesUsed,
+                Expr::col(coupons::Column::TimesUsed).sub(1),
+            )
+            .filter(coupons::Column::Id.eq(coupon_id))
+            .filter(coupons::Column::TimesUsed.gt(0))
+            .exec(db)
+            .await
+            .map_err(|e| format!("Error al liberar el cupón: {:?}", e))?;
+    }
+
+    Ok(())
+}
+
+/// Request de una venta con clave de idempotencia, sin la clave, para compararlo en
+/// los reintentos. Incluye el origen porque la misma clave no puede cobrar una venta
+/// y un apartado.
+fn idempotency_payload(
+    request: &CreateSaleRequest,
+    quote_id: Option<i32>,
+    layaway: bool,
+) -> Result<String, String> {
+    let mut request = request.clone();
+    request.idempotency_key = None;
+
+    serde_json::to_string(&serde_json::json!({
+        "request": request,
+        "quote_id": quote_id,
+        "layaway": layaway,
+    }))
+    .map_err(|e| format!("Error al registrar la clave de idempotencia: {:?}", e))
+}
+
+/// Busca la venta registrada con la clave de idempotencia. Si el request coincide,
+/// regresa la respuesta original; si es distinto, el reintento se rechaza. Solo se
+/// devuelve la respuesta a quien registró la venta.
+async fn find_idempotent_sale<C: ConnectionTrait>(
+    db: &C,
+    key: &str,
+    payload: &str,
+    user_id: &str,
+) -> Result<Option<CreateSaleResponse>, String> {
+    let sale = match Sales::find()
+        .filter(sales::Column::IdempotencyKey.eq(key))
+        .one(db)
         .await
-        .map_err(|_| "Error al validar método de pago")?
-        .ok_or("Método de pago no válido")?;
+        .map_err(|_| DB_ERROR)?
+    {
+        Some(sale) => sale,
+        None => return Ok(None),
+    };
+
+    // La clave es única en toda la tabla; la de otro usuario no se puede reutilizar
+    if sale.created_by != user_id {
+        return Err(format!("La clave {} ya fue usada por otro usuario", key));
+    }
 
-    if !payment_method.is_active {
-        return Err("El método de pago seleccionado no está disponible".to_string());
+    if sale.idempotency_payload.as_deref() != Some(payload) {
+        return Err(format!(
+            "La clave {} ya se usó en la venta {} con datos distintos",
+            key, sale.id
+        ));
     }
 
-    // 4. Validar items no vacío
+    let stored = sale.idempotency_response.ok_or(format!(
+        "La venta {} no tiene respuesta registrada",
+        sale.id
+    ))?;
+
+    serde_json::from_str(&stored)
+        .map(Some)
+        .map_err(|e| format!("Error al leer la respuesta de la venta: {:?}", e))
+}
+
+/// Registra una venta con todas las validaciones de create_sale.
+/// Si viene de una cotización, la marca como convertida en la misma transacción.
+/// Como apartado, la venta queda pendiente con el anticipo y las piezas reservadas.
+pub async fn register_sale(
+    state: &tauri::State<'_, AppState>,
+    request: CreateSaleRequest,
+    quote_id: Option<i32>,
+    layaway: bool,
+) -> Result<CreateSaleResponse, String> {
+    // 1. Validar permiso
+    let session = require_permission(state, "sales.create")?;
+    let db = &state.database;
+
+    // Un reintento con la misma clave regresa la venta ya registrada sin volver a cobrarla
+    let idempotency = match request.idempotency_key.as_deref().map(str::trim) {
+        Some(key) => {
+            if key.is_empty() || key.len() > IDEMPOTENCY_KEY_MAX_LEN {
+                return Err(format!(
+                    "La clave de idempotencia debe tener entre 1 y {} caracteres",
+                    IDEMPOTENCY_KEY_MAX_LEN
+                ));
+            }
+            let payload = idempotency_payload(&request, quote_id, layaway)?;
+            if let Some(response) =
+                find_idempotent_sale(db, key, &payload, &session.user_id).await?
+            {
+                return Ok(response);
+            }
+            Some((key.to_string(), payload))
+        }
+        None => None,
+    };
+
+    // 2. Validar items no vacío y precios manuales autorizados
     if request.items.is_empty() {
         return Err("La venta debe tener al menos un producto".to_string());
     }
 
-    // 5. Validar stock disponible para todos los productos
-    for item in &request.items {
-        let product = Products::find_by_id(item.product_id)
+    if request
+        .items
+        .iter()
+        .any(|item| item.quantity <= Decimal::ZERO)
+    {
+        return Err("Las cantidades deben ser positivas".to_string());
+    }
+
+    let has_price_override = request
+        .items
+        .iter()
+        .any(|item| item.price_override.is_some());
+    if has_price_override && !session.has_permission("sales.price_override") {
+        return Err("No tiene permisos para modificar el precio de venta".to_string());
+    }
+
+    if request.items.iter().any(|item| {
+        item.price_override
+            .is_some_and(|price| price < Decimal::ZERO)
+    }) {
+        return Err("El precio manual no puede ser negativo".to_string());
+    }
+
+    // 3. Iniciar transacción
+    let txn = db
+        .begin()
+        .await
+        .map_err(|_| "Error al iniciar transacción")?;
+
+    // La venta se registra en el turno de caja abierto del cajero
+    let cash_session = require_open_session(&txn, &session.user_id).await?;
+
+    // Validar cliente si se especifica
+    if let Some(customer_id) = request.customer_id {
+        let customer = Customers::find_by_id(customer_id)
             .one(&txn)
             .await
-            .map_err(|_| format!("Error al consultar producto {}", item.product_id))?
-            .ok_or(format!("Producto {} no encontrado", item.product_id))?;
+            .map_err(|_| DB_ERROR)?
+            .ok_or(format!("Cliente {} no encontrado", customer_id))?;
 
-        if !product.is_active {
-            return Err(format!("El producto '{}' no está disponible", product.name));
+        if !customer.is_active {
+            return Err(format!("El cliente {} no está activo", customer.name));
         }
+    } else if layaway {
+        return Err("El apartado requiere un cliente".to_string());
+    }
 
-        if product.stock < item.quantity {
-            return Err(format!(
-                "Stock insuficiente para '{}'. Disponible: {}, Solicitado: {}",
-                product.name, product.stock, item.quantity
-            ));
+    // 4. Validar que los métodos de pago existan y estén activos
+    let tenders = load_tenders(&txn, &request.payments).await?;
+
+    // Lo pagado a crédito se carga a la cuenta del cliente
+    let credit_amount: Decimal = tenders
+        .iter()
+        .filter(|(method, _)| method.tender == TENDER_CREDIT)
+        .map(|(_, amount)| *amount)
+        .sum();
+    if credit_amount > Decimal::ZERO {
+        if layaway {
+            return Err("Los abonos de un apartado no se pueden pagar a crédito".to_string());
         }
+        if request.customer_id.is_none() {
+            return Err("La venta a crédito requiere un cliente".to_string());
+        }
+    }
 
-        if item.quantity <= 0 {
-            return Err("Las cantidades deben ser positivas".to_string());
+    // Lo pagado con puntos se descuenta del saldo de puntos del cliente
+    let points_amount: Decimal = tenders
+        .iter()
+        .filter(|(method, _)| method.tender == TENDER_POINTS)
+        .map(|(_, amount)| *amount)
+        .sum();
+    if points_amount > Decimal::ZERO {
+        if layaway {
+            return Err("Los abonos de un apartado no se pueden pagar con puntos".to_string());
+        }
+        if request.customer_id.is_none() {
+            return Err("El pago con puntos requiere un cliente".to_string());
         }
     }
 
-    // 6. Generar ID de venta
+    // Validar y bloquear el cupón hasta el commit para que no se canjee dos veces
+    let coupon = match &request.coupon_code {
+        Some(code) => Some(find_redeemable_coupon(&txn, code).await?),
+        None => None,
+    };
+
+    // 5. Validar productos y calcular importes con precios del catálogo, promociones y cupón
+    let priced = price_sale_items(
+        &txn,
+        &session,
+        &request.items,
+        request.discount.as_ref(),
+        coupon.as_ref().map(|(_, batch)| batch),
+    )
+    .await?;
+    check_stock(&priced.lines)?;
+    let (subtotal, total) = (priced.subtotal, priced.total);
+
+    if layaway && priced.lines.iter().any(|line| line.product.is_gift_card) {
+        return Err("Las tarjetas de regalo no se pueden apartar".to_string());
+    }
+
+    // 6. Rechazar ventas cuyos totales no coinciden con los calculados
+    if !amounts_match(request.subtotal, subtotal, priced.lines.len())
+        || !amounts_match(request.total, total, priced.lines.len())
+    {
+        return Err(format!(
+            "Los totales de la venta no coinciden con los precios vigentes. Subtotal: {}, Total: {}",
+            subtotal, total
+        ));
+    }
+
+    // 7. Validar que los pagos cubran el total (o el anticipo del apartado) y calcular el cambio
+    let paid: Decimal = tenders.iter().map(|(_, amount)| *amount).sum();
+    let cash_paid: Decimal = tenders
+        .iter()
+        .filter(|(method, _)| method.tender == TENDER_CASH)
+        .map(|(_, amount)| *amount)
+        .sum();
+
+    let layaway_config = LayawayConfig::from_env();
+    let (change, balance_due) = if layaway {
+        let min_down_payment =
+            (total * layaway_config.min_down_payment_percent / Decimal::from(100)).round_dp(2);
+        if paid < min_down_payment {
+            return Err(format!(
+                "El anticipo es insuficiente. Mínimo: {}, Pagado: {}",
+                min_down_payment, paid
+            ));
+        }
+        if paid >= total {
+            return Err(
+                "El anticipo cubre el total; registre la operación como venta normal".to_string(),
+            );
+        }
+        (Decimal::ZERO, total - paid)
+    } else {
+        if paid < total {
+            return Err(format!(
+                "El pago es insuficiente. Total: {}, Pagado: {}",
+                total, paid
+            ));
+        }
+        (tender_change(&tenders, total)?, Decimal::ZERO)
+    };
+
+    // 8. Generar ID de venta
     let sale_id = cuid2::create_id();
 
-    // 7. Crear registro de venta principal
+    // 9. Crear registro de venta principal
     let sale = sales::ActiveModel {
         id: Set(sale_id.clone()),
-        subtotal: Set(request.subtotal),
-        total: Set(request.total),
+        subtotal: Set(subtotal),
+        total: Set(total),
         status: Set(true),
+        change_amount: Set(change),
+        customer_id: Set(request.customer_id),
+        discount_type: Set(request
+            .discount
+            .as_ref()
+            .map(|discount| discount.discount_type.as_str().to_string())),
+        discount_value: Set(request
+            .discount
+            .as_ref()
+            .map_or(Decimal::ZERO, |discount| discount.value)),
+        discount_amount: Set(priced.ticket_discount),
+        coupon_id: Set(coupon.as_ref().map(|(coupon, _)| coupon.id)),
+        coupon_discount_amount: Set(priced.coupon_discount),
+        cash_session_id: Set(Some(cash_session.id)),
+        layaway_status: Set(layaway.then(|| LAYAWAY_PENDING.to_string())),
+        layaway_due_date: Set(
+            layaway.then(|| Local::now().date_naive() + Duration::days(layaway_config.term_days))
+        ),
+        completed_at: Set((!layaway).then(|| Utc::now().fixed_offset())),
         created_by: Set(session.user_id.clone()),
         updated_by: Set(session.user_id.clone()),
+        idempotency_key: Set(idempotency.as_ref().map(|(key, _)| key.clone())),
+        idempotency_payload: Set(idempotency.as_ref().map(|(_, payload)| payload.clone())),
         ..Default::default()
     };
 
-    let inserted_sale = sale
-        .insert(&txn)
-        .await
-        .map_err(|e| format!("Error al crear venta: {:?}", e))?;
+    let inserted_sale = match sale.insert(&txn).await {
+        Ok(inserted_sale) => inserted_sale,
+        Err(e) => {
+            // Otro envío con la misma clave se registró mientras se validaba este
+            if let Some((key, payload)) = &idempotency {
+                drop(txn);
+                if let Some(response) =
+                    find_idempotent_sale(db, key, payload, &session.user_id).await?
+                {
+                    return Ok(response);
+                }
+            }
+            return Err(format!("Error al crear venta: {:?}", e));
+        }
+    };
 
-    // 8. Crear detalles de venta y actualizar stock
-    for item in &request.items {
-        // Calcular totales de línea
-        let subtotal = item.unit_price * Decimal::from(item.quantity);
-        let tax_amount = subtotal * item.tax_rate;
-        let total = subtotal + tax_amount;
+    // Eliminar el ticket en espera cobrado. Si otra caja ya lo cobró o descartó,
+    // no se afecta ninguna fila y la venta se rechaza.
+    if let Some(parked_sale_id) = request.parked_sale_id {
+        let deleted = ParkedSales::delete_by_id(parked_sale_id)
+            .exec(&txn)
+            .await
+            .map_err(|_| DB_ERROR)?;
+
+        if deleted.rows_affected == 0 {
+            return Err("El ticket en espera ya fue cobrado o descartado".to_string());
+        }
+    }
+
+    // Vincular la cotización convertida con la venta
+    if let Some(quote_id) = quote_id {
+        mark_quote_converted(&txn, quote_id, &sale_id, &session.user_id).await?;
+    }
+
+    // Consumir un uso del cupón; la venta queda como registro del canje
+    if let Some((coupon, batch)) = &coupon {
+        let redeemed = Coupons::update_many()
+            .col_expr(
+                coupons::Column::TimesUsed,
+                Expr::col(coupons::Column::TimesUsed).add(1),
+            )
+            .filter(coupons::Column::Id.eq(coupon.id))
+            .filter(coupons::Column::TimesUsed.lt(batch.usage_limit))
+            .exec(&txn)
+            .await
+            .map_err(|e| format!("Error al canjear el cupón: {:?}", e))?;
+
+        if redeemed.rows_affected == 0 {
+            return Err(format!("El cupón {} ya fue utilizado", coupon.code));
+        }
+    }
+
+    // 10. Crear detalles de venta y actualizar stock (en un apartado las piezas quedan reservadas)
+    for line in &priced.lines {
+        let (item, product) = (&line.item, &line.product);
 
         // Insertar detalle
         let detail = sale_details::ActiveModel {
             sale_id: Set(sale_id.clone()),
-            product_id: Set(item.product_id),
+            product_id: Set(product.id),
             quantity: Set(item.quantity),
-            unit_price: Set(item.unit_price),
-            subtotal: Set(subtotal),
-            tax_rate: Set(item.tax_rate),
-            tax_amount: Set(tax_amount),
-            total: Set(total),
+            unit_price: Set(line.unit_price),
+            discount_type: Set(item
+                .discount
+                .as_ref()
+                .map(|discount| discount.discount_type.as_str().to_string())),
+            discount_value: Set(item
+                .discount
+                .as_ref()
+                .map_or(Decimal::ZERO, |discount| discount.value)),
+            discount_amount: Set(line.line_discount),
+            ticket_discount_amount: Set(line.ticket_discount),
+            promotion_id: Set(line.promotion.map(|promotion| promotion.promotion_id)),
+            promotion_discount_amount: Set(line
+                .promotion
+                .map_or(Decimal::ZERO, |promotion| promotion.amount)),
+            coupon_discount_amount: Set(line.coupon_discount),
+            subtotal: Set(line.totals.subtotal),
+            tax_rate: Set(line.totals.effective_rate()),
+            tax_amount: Set(line.totals.tax_amount),
+            total: Set(line.totals.total),
             ..Default::default()
         };
 
-        detail
+        let detail = detail
             .insert(&txn)
             .await
             .map_err(|e| format!("Error al registrar detalle de venta: {:?}", e))?;
 
-        // Actualizar stock del producto (restar cantidad)
-        let product = Products::find_by_id(item.product_id)
-            .one(&txn)
+        // Registrar el desglose de impuestos de la línea
+        for tax in &line.totals.taxes {
+            sale_detail_taxes::ActiveModel {
+                sale_detail_id: Set(detail.id),
+                tax_type: Set(tax.tax_type.clone()),
+                rate: Set(tax.rate),
+                is_included: Set(tax.is_included),
+                is_exempt: Set(tax.is_exempt),
+                base: Set(tax.base),
+                amount: Set(tax.amount),
+                ..Default::default()
+            }
+            .insert(&txn)
             .await
-            .map_err(|_| "Error al actualizar stock")?
-            .ok_or("Producto no encontrado")?;
-
-        let mut product_active: products::ActiveModel = product.into();
-        product_active.stock = Set(product_active.stock.unwrap() - item.quantity);
-        product_active.updated_by = Set(session.user_id.clone());
+            .map_err(|e| format!("Error al registrar impuestos de la venta: {:?}", e))?;
+        }
 
-        product_active
-            .update(&txn)
+        // Actualizar stock del producto (restar cantidad) sin permitir negativos
+        let updated = Products::update_many()
+            .col_expr(
+                products::Column::Stock,
+                Expr::col(products::Column::Stock).sub(item.quantity),
+            )
+            .col_expr(
+                products::Column::UpdatedBy,
+                Expr::value(session.user_id.clone()),
+            )
+            .filter(products::Column::Id.eq(product.id))
+            .filter(products::Column::Stock.gte(item.quantity))
+            .exec(&txn)
             .await
             .map_err(|e| format!("Error al actualizar inventario: {:?}", e))?;
+
+        if updated.rows_affected == 0 {
+            return Err(format!("Stock insuficiente para '{}'", product.name));
+        }
+    }
+
+    // Emitir una tarjeta por cada pieza vendida, con el importe pagado por ella
+    let mut gift_cards = Vec::new();
+    for line in priced.lines.iter().filter(|line| line.product.is_gift_card) {
+        // Las tarjetas de regalo siempre se venden en piezas enteras
+        let amount = (line.totals.total / line.item.quantity).round_dp(2);
+        let mut pieces = line.item.quantity;
+        while pieces > Decimal::ZERO {
+            pieces -= Decimal::ONE;
+            let card = issue_gift_card(
+                &txn,
+                amount,
+                None,
+                ENTRY_ISSUE,
+                Some(&sale_id),
+                None,
+                None,
+                &session.user_id,
+            )
+            .await?;
+            gift_cards.push(GiftCard::from(card));
+        }
+    }
+
+    // 11. Crear un registro de pago por cada forma de pago
+    insert_payments(
+        &txn,
+        &sale_id,
+        &request.payments,
+        &tenders,
+        change,
+        cash_session.id,
+        &session.user_id,
+    )
+    .await?;
+
+    // Cargar a la cuenta del cliente lo pagado a crédito, sin exceder su límite
+    if let Some(customer_id) = request
+        .customer_id
+        .filter(|_| credit_amount > Decimal::ZERO)
+    {
+        post_credit_entry(
+            &txn,
+            customer_id,
+            ENTRY_CHARGE,
+            credit_amount,
+            Some(&sale_id),
+            None,
+            None,
+            None,
+            &session.user_id,
+        )
+        .await?;
+    }
+
+    // Canjear los puntos usados como pago y acumular los de la venta (un apartado
+    // acumula al liquidarse)
+    if let Some(customer_id) = request
+        .customer_id
+        .filter(|_| points_amount > Decimal::ZERO)
+    {
+        redeem_sale_points(&txn, customer_id, points_amount, &sale_id, &session.user_id).await?;
+    }
+    let points_earned = if layaway {
+        0
+    } else {
+        accrue_sale_points(&txn, &inserted_sale, &session.user_id).await?
+    };
+
+    let mut response = CreateSaleResponse {
+        sale_id: inserted_sale.id,
+        subtotal: inserted_sale.subtotal,
+        total: inserted_sale.total,
+        amount_paid: paid,
+        change,
+        balance_due,
+        points_earned,
+        gift_cards,
+        created_at: inserted_sale.created_at.to_string(),
+        print_error: None,
+    };
+
+    // Guardar la respuesta para devolverla en los reintentos con la misma clave
+    if idempotency.is_some() {
+        let stored = serde_json::to_string(&response)
+            .map_err(|e| format!("Error al guardar la respuesta de la venta: {:?}", e))?;
+        Sales::update_many()
+            .col_expr(sales::Column::IdempotencyResponse, Expr::value(stored))
+            .filter(sales::Column::Id.eq(&sale_id))
+            .exec(&txn)
+            .await
+            .map_err(|e| format!("Error al guardar la respuesta de la venta: {:?}", e))?;
+    }
+
+    // 12. Commit transacción
+    txn.commit()
+        .await
+        .map_err(|_| "Error al confirmar la transacción")?;
+
+    // 13. Imprimir ticket si está configurado; un error de impresión no revierte la venta
+    response.print_error = match PrinterConfig::from_env().filter(|config| config.auto_print) {
+        Some(config) => print_sale(db, &config, &sale_id, false, cash_paid > Decimal::ZERO)
+            .await
+            .err(),
+        None => None,
+    };
+
+    // 14. Retornar respuesta
+    Ok(response)
+}
+
+/// Registra una devolución parcial o total sobre una venta
+#[tauri::command]
+pub async fn create_refund(
+    state: tauri::State<'_, AppState>,
+    request: CreateRefundRequest,
+) -> Result<CreateRefundResponse, String> {
+    // 1. Validar permiso
+    let session = require_permission(&state, "sales.refund")?;
+    let db = &state.database;
+
+    // 2. Validar datos de entrada
+    if request.items.is_empty() {
+        return Err("La devolución debe tener al menos un producto".to_string());
+    }
+
+    if request
+        .items
+        .iter()
+        .any(|item| item.quantity <= Decimal::ZERO)
+    {
+        return Err("Las cantidades deben ser positivas".to_string());
+    }
+
+    let reason = request.reason.trim();
+    if reason.is_empty() {
+        return Err("Debe indicar el motivo de la devolución".to_string());
+    }
+
+    // 3. Iniciar transacción
+    let txn = db
+        .begin()
+        .await
+        .map_err(|_| "Error al iniciar transacción")?;
+
+    // 4. Bloquear la venta para serializar devoluciones concurrentes
+    let sale = Sales::find_by_id(request.sale_id.clone())
+        .lock_exclusive()
+        .one(&txn)
+        .await
+        .map_err(|_| DB_ERROR)?
+        .ok_or("Venta no encontrada")?;
+
+    if !sale.status {
+        return Err("No se puede devolver una venta cancelada".to_string());
+    }
+
+    if sale.layaway_status.as_deref() == Some(LAYAWAY_PENDING) {
+        return Err("No se puede devolver un apartado pendiente de liquidar".to_string());
+    }
+
+    // 5. Cantidades e importes vendidos por producto
+    let details = SaleDetails::find()
+        .filter(sale_details::Column::SaleId.eq(&sale.id))
+        .all(&txn)
+        .await
+        .map_err(|_| DB_ERROR)?;
+
+    let mut sold: HashMap<i32, (Decimal, Decimal)> = HashMap::new();
+    for detail in &details {
+        let entry = sold
+            .entry(detail.product_id)
+            .or_insert((Decimal::ZERO, Decimal::ZERO));
+        entry.0 += detail.quantity;
+        entry.1 += detail.total;
+    }
+
+    // 6. Cantidades ya devueltas por producto
+    let previous_refunds = RefundDetails::find()
+        .inner_join(Refunds)
+        .filter(refunds::Column::SaleId.eq(&sale.id))
+        .all(&txn)
+        .await
+        .map_err(|_| DB_ERROR)?;
+
+    let mut refunded: HashMap<i32, Decimal> = HashMap::new();
+    for detail in &previous_refunds {
+        *refunded.entry(detail.product_id).or_insert(Decimal::ZERO) += detail.quantity;
+    }
+
+    // 7. Validar que ninguna línea exceda lo vendido menos lo ya devuelto
+    let mut requested: HashMap<i32, Decimal> = HashMap::new();
+    for item in &request.items {
+        *requested.entry(item.product_id).or_insert(Decimal::ZERO) += item.quantity;
+    }
+
+    // Una tarjeta de regalo vendida no se devuelve; su saldo sigue vigente. Las demás
+    // cantidades respetan la unidad de medida del producto.
+    let requested_products = Products::find()
+        .filter(products::Column::Id.is_in(requested.keys().copied()))
+        .all(&txn)
+        .await
+        .map_err(|_| DB_ERROR)?;
+    for product in &requested_products {
+        if product.is_gift_card {
+            return Err(format!(
+                "La tarjeta de regalo '{}' no se puede devolver",
+                product.name
+            ));
+        }
+        check_quantity(product, requested[&product.id])?;
+    }
+
+    let mut lines: Vec<(i32, Decimal, Decimal)> = Vec::new();
+    let mut amount = Decimal::ZERO;
+
+    for (product_id, quantity) in requested {
+        let (sold_quantity, sold_total) = sold.get(&product_id).copied().ok_or(format!(
+            "El producto {} no pertenece a la venta",
+            product_id
+        ))?;
+
+        let available = sold_quantity - refunded.get(&product_id).copied().unwrap_or(Decimal::ZERO);
+        if quantity > available {
+            return Err(format!(
+                "Cantidad a devolver excedida para el producto {}. Disponible: {}, Solicitado: {}",
+                product_id, available, quantity
+            ));
+        }
+
+        // El precio unitario devuelto incluye impuestos, igual que sale_details.total
+        let unit_price = (sold_total / sold_quantity).round_dp(2);
+        let line_amount = (sold_total * quantity / sold_quantity).round_dp(2);
+
+        amount += line_amount;
+        lines.push((product_id, quantity, unit_price));
     }
 
-    // 9. Crear registro de pago
-    let payment = sale_payments::ActiveModel {
-        sale_id: Set(sale_id.clone()),
-        payment_method_id: Set(request.payment_method_id),
-        amount: Set(request.total),
+    // 8. Forma de reembolso; el efectivo sale del turno de caja abierto del usuario.
+    // Lo cobrado a crédito no se reembolsa por otra forma de pago: se abona a la cuenta
+    // del cliente para que no se lleve efectivo por mercancía que aún debe.
+    let mut credit_paid = Decimal::ZERO;
+    let mut other_paid = Decimal::ZERO;
+    for (payment, method) in SalePayments::find()
+        .find_also_related(PaymentMethods)
+        .filter(sale_payments::Column::SaleId.eq(&sale.id))
+        .all(&txn)
+        .await
+        .map_err(|_| DB_ERROR)?
+    {
+        if method.is_some_and(|method| method.tender == TENDER_CREDIT) {
+            credit_paid += payment.amount;
+        } else {
+            other_paid += payment.amount;
+        }
+    }
+
+    let other_refunded: Decimal = Refunds::find()
+        .find_also_related(PaymentMethods)
+        .filter(refunds::Column::SaleId.eq(&sale.id))
+        .all(&txn)
+        .await
+        .map_err(|_| DB_ERROR)?
+        .into_iter()
+        .filter(|(_, method)| {
+            method
+                .as_ref()
+                .is_none_or(|method| method.tender != TENDER_CREDIT)
+        })
+        .map(|(refund, _)| refund.amount)
+        .sum();
+    let other_available = (other_paid - other_refunded).max(Decimal::ZERO);
+
+    // Sin forma indicada, una venta a crédito se reembolsa a la cuenta del cliente
+    let payment_method_id = match request.payment_method_id {
+        Some(payment_method_id) => Some(payment_method_id),
+        None if credit_paid > Decimal::ZERO && amount > other_available => Some(
+            PaymentMethods::find()
+                .filter(payment_methods::Column::Tender.eq(TENDER_CREDIT))
+                .one(&txn)
+                .await
+                .map_err(|_| DB_ERROR)?
+                .ok_or("No está configurado el método de pago a crédito")?
+                .id,
+        ),
+        None => None,
+    };
+    let (refund_method, cash_session) =
+        resolve_refund_method(&txn, payment_method_id, &session.user_id).await?;
+
+    if credit_paid > Decimal::ZERO
+        && refund_method.tender != TENDER_CREDIT
+        && amount > other_available
+    {
+        return Err(format!(
+            "La venta se pagó a crédito: solo se pueden reembolsar {} con otra forma de pago; el resto se abona a la cuenta del cliente",
+            other_available
+        ));
+    }
+
+    // 9. Crear registro de devolución
+    let refund = refunds::ActiveModel {
+        sale_id: Set(sale.id.clone()),
+        amount: Set(amount),
+        reason: Set(reason.to_string()),
+        payment_method_id: Set(Some(refund_method.id)),
+        cash_session_id: Set(cash_session.map(|cash_session| cash_session.id)),
+        created_by: Set(session.user_id.clone()),
+        updated_by: Set(session.user_id.clone()),
         ..Default::default()
     };
 
-    payment
+    let inserted_refund = refund
         .insert(&txn)
         .await
-        .map_err(|e| format!("Error al registrar pago: {:?}", e))?;
+        .map_err(|e| format!("Error al registrar devolución: {:?}", e))?;
 
-    // 10. Commit transacción
+    // 10. Crear detalles de devolución y regresar stock
+    for (product_id, quantity, unit_price) in lines {
+        let detail = refund_details::ActiveModel {
+            refund_id: Set(inserted_refund.id),
+            product_id: Set(product_id),
+            quantity: Set(quantity),
+            unit_price: Set(unit_price),
+            ..Default::default()
+        };
+
+        detail
+            .insert(&txn)
+            .await
+            .map_err(|e| format!("Error al registrar detalle de devolución: {:?}", e))?;
+
+        Products::update_many()
+            .col_expr(
+                products::Column::Stock,
+                Expr::col(products::Column::Stock).add(quantity),
+            )
+            .col_expr(
+                products::Column::UpdatedBy,
+                Expr::value(session.user_id.clone()),
+            )
+            .filter(products::Column::Id.eq(product_id))
+            .exec(&txn)
+            .await
+            .map_err(|e| format!("Error al actualizar inventario: {:?}", e))?;
+    }
+
+    // Un reembolso a crédito se abona a la cuenta del cliente
+    if refund_method.tender == TENDER_CREDIT {
+        credit_refund(&txn, &sale, amount, reason, &session.user_id).await?;
+    }
+
+    // Un reembolso en tarjeta de regalo queda como saldo a favor en un vale
+    let gift_card_code = if refund_method.tender == TENDER_GIFT_CARD {
+        let card = refund_to_gift_card(
+            &txn,
+            request.gift_card_code.as_deref(),
+            amount,
+            &sale.id,
+            inserted_refund.id,
+            &session.user_id,
+        )
+        .await?;
+        Some(card.code)
+    } else {
+        None
+    };
+
+    // Retirar los puntos ganados en proporción; un reembolso en puntos los devuelve
+    refund_sale_points(
+        &txn,
+        &sale,
+        amount,
+        refund_method.tender == TENDER_POINTS,
+        reason,
+        &session.user_id,
+    )
+    .await?;
+
+    // 11. Commit transacción
     txn.commit()
         .await
         .map_err(|_| "Error al confirmar la transacción")?;
 
-    // 11. Retornar respuesta
-    Ok(CreateSaleResponse {
-        sale_id: inserted_sale.id,
-        subtotal: inserted_sale.subtotal,
-        total: inserted_sale.total,
-        created_at: inserted_sale.created_at.to_string(),
+    Ok(CreateRefundResponse {
+        refund_id: inserted_refund.id,
+        sale_id: inserted_refund.sale_id,
+        amount: inserted_refund.amount,
+        gift_card_code,
+        created_at: inserted_refund.created_at.to_string(),
     })
 }
+
+/// Cancela una venta completa y regresa su inventario
+#[tauri::command]
+pub async fn cancel_sale(
+    state: tauri::State<'_, AppState>,
+    request: CancelSaleRequest,
+) -> Result<Sale, String> {
+    // 1. Validar permiso
+    let session = require_permission(&state, "sales.cancel")?;
+    let db = &state.database;
+
+    let reason = request.reason.trim();
+    if reason.is_empty() {
+        return Err("Debe indicar el motivo de la cancelación".to_string());
+    }
+
+    // 2. Iniciar transacción
+    let txn = db
+        .begin()
+        .await
+        .map_err(|_| "Error al iniciar transacción")?;
+
+    // 3. Bloquear la venta para evitar cancelaciones o devoluciones simultáneas
+    let sale = Sales::find_by_id(request.sale_id.clone())
+        .lock_exclusive()
+        .one(&txn)
+        .await
+        .map_err(|_| DB_ERROR)?
+        .ok_or("Venta no encontrada")?;
+
+    if !sale.status {
+        return Err("La venta ya se encuentra cancelada".to_string());
+    }
+
+    // Los apartados pendientes se cancelan con penalización y reembolso de lo abonado
+    if sale.layaway_status.as_deref() == Some(LAYAWAY_PENDING) {
+        return Err(
+            "Los apartados pendientes se cancelan desde la sección de apartados".to_string(),
+        );
+    }
+
+    // 4. No se permite cancelar ventas con devoluciones registradas
+    let refunds_count = Refunds::find()
+        .filter(refunds::Column::SaleId.eq(&sale.id))
+        .count(&txn)
+        .await
+        .map_err(|_| DB_ERROR)?;
+
+    if refunds_count > 0 {
+        return Err("No se puede cancelar una venta que tiene devoluciones".to_string());
+    }
+
+    // 5. Regresar stock de cada línea de la venta y liberar el cupón
+    restore_sale_inventory(&txn, &sale, &session.user_id).await?;
+
+    // Revertir en la cuenta del cliente lo que se pagó a crédito
+    if let Some(customer_id) = sale.customer_id {
+        let credit_paid: Decimal = SalePayments::find()
+            .inner_join(PaymentMethods)
+            .filter(sale_payments::Column::SaleId.eq(&sale.id))
+            .filter(payment_methods::Column::Tender.eq(TENDER_CREDIT))
+            .all(&txn)
+            .await
+            .map_err(|_| DB_ERROR)?
+            .iter()
+            .map(|payment| payment.amount)
+            .sum();
+
+        if credit_paid > Decimal::ZERO {
+            post_credit_entry(
+                &txn,
+                customer_id,
+                ENTRY_CANCELLATION,
+                -credit_paid,
+                Some(&sale.id),
+                None,
+                None,
+                Some(reason.to_string()),
+                &session.user_id,
+            )
+            .await?;
+        }
+    }
+
+    // Reembolsar con la misma forma de pago lo cobrado en efectivo, tarjeta o
+    // transferencia. Se registra en el turno abierto de quien cancela; el turno que
+    // cobró la venta conserva sus pagos. Crédito, puntos y tarjetas de regalo se
+    // revierten en su propio saldo.
+    let mut paid_by_method: HashMap<i32, Decimal> = HashMap::new();
+    for (payment, method) in SalePayments::find()
+        .find_also_related(PaymentMethods)
+        .filter(sale_payments::Column::SaleId.eq(&sale.id))
+        .all(&txn)
+        .await
+        .map_err(|_| DB_ERROR)?
+    {
+        let reversed_elsewhere = method.is_some_and(|method| {
+            [TENDER_CREDIT, TENDER_POINTS, TENDER_GIFT_CARD].contains(&method.tender.as_str())
+        });
+        if !reversed_elsewhere {
+            *paid_by_method
+                .entry(payment.payment_method_id)
+                .or_insert(Decimal::ZERO) += payment.amount;
+        }
+    }
+
+    for (payment_method_id, amount) in paid_by_method {
+        if amount <= Decimal::ZERO {
+            continue;
+        }
+        let (refund_method, cash_session) =
+            resolve_refund_method(&txn, Some(payment_method_id), &session.user_id).await?;
+
+        refunds::ActiveModel {
+            sale_id: Set(sale.id.clone()),
+            amount: Set(amount),
+            reason: Set(format!("Cancelación de venta: {}", reason)),
+            payment_method_id: Set(Some(refund_method.id)),
+            cash_session_id: Set(cash_session.map(|cash_session| cash_session.id)),
+            created_by: Set(session.user_id.clone()),
+            updated_by: Set(session.user_id.clone()),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await
+        .map_err(|e| format!("Error al registrar el reembolso: {:?}", e))?;
+    }
+
+    // Retirar los puntos ganados y devolver los canjeados
+    reverse_sale_points(&txn, &sale, reason, &session.user_id).await?;
+
+    // Devolver el saldo consumido de tarjetas de regalo y anular las vendidas
+    reverse_sale_gift_cards(&txn, &sale, reason, &session.user_id).await?;
+
+    // 6. Marcar la venta como cancelada
+    let mut sale_active: sales::ActiveModel = sale.into();
+    sale_active.status = Set(false);
+    sale_active.cancelled_by = Set(Some(session.user_id.clone()));
+    sale_active.cancelled_at = Set(Some(Utc::now().fixed_offset()));
+    sale_active.cancellation_reason = Set(Some(reason.to_string()));
+    sale_active.updated_by = Set(session.user_id.clone());
+
+    let cancelled_sale = sale_active
+        .update(&txn)
+        .await
+        .map_err(|e| format!("Error al cancelar venta: {:?}", e))?;
+
+    // 7. Commit transacción
+    txn.commit()
+        .await
+        .map_err(|_| "Error al confirmar la transacción")?;
+
+    Ok(Sale::from(cancelled_sale))
+}
@@ -1,4 +1,6 @@
+pub mod checkout;
 pub mod handlers;
+pub mod pricing;
 pub mod structs;
 
 pub use handlers as SalesHandler;
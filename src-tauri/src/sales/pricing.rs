@@ -0,0 +1,264 @@
+use sea_orm::prelude::Decimal;
+
+use super::structs::{DiscountRequest, DiscountType};
+use crate::entities::product_taxes;
+use crate::products::structs::{TAX_IEPS, TAX_IVA};
+
+/// Impuesto calculado sobre una línea de venta
+#[derive(Debug, Clone)]
+pub struct LineTax {
+    pub tax_type: String, // iva, ieps
+    pub rate: Decimal,
+    pub is_included: bool,
+    pub is_exempt: bool,
+    pub base: Decimal,
+    pub amount: Decimal,
+}
+
+/// Importes calculados para una línea de venta
+#[derive(Debug, Clone)]
+pub struct LineTotals {
+    pub gross: Decimal, // Precio unitario por cantidad, antes de descuentos
+    pub subtotal: Decimal,
+    pub tax_amount: Decimal,
+    pub total: Decimal,
+    pub taxes: Vec<LineTax>, // Desglose por impuesto; vacío si el producto no causa impuestos
+}
+
+impl LineTotals {
+    /// Tasa combinada efectivamente cobrada (impuestos entre subtotal), con la escala de
+    /// la columna `tax_rate` de los detalles
+    pub fn effective_rate(&self) -> Decimal {
+        if self.subtotal <= Decimal::ZERO {
+            return Decimal::ZERO;
+        }
+        (self.tax_amount / self.subtotal).round_dp(4)
+    }
+}
+
+/// Importe bruto de una línea redondeado a centavos
+pub fn line_gross(unit_price: Decimal, quantity: Decimal) -> Decimal {
+    (unit_price * quantity).round_dp(2)
+}
+
+/// Calcula subtotal, impuestos y total de una línea redondeando a centavos.
+/// El descuento se resta antes de calcular los impuestos. El IEPS se calcula sobre
+/// el subtotal y el IVA sobre el subtotal más el IEPS. Si los impuestos están
+/// incluidos en el precio, el importe con descuento es el total y el subtotal se
+/// obtiene descontándolos, de modo que el precio de anaquel no cambia por redondeo.
+pub fn compute_line(
+    unit_price: Decimal,
+    quantity: Decimal,
+    taxes: &[product_taxes::Model],
+    discount: Decimal,
+) -> LineTotals {
+    let gross = line_gross(unit_price, quantity);
+    let amount = gross - discount;
+
+    // El IEPS va primero porque forma parte de la base del IVA
+    let mut ordered: Vec<&product_taxes::Model> = taxes.iter().collect();
+    ordered.sort_by_key(|tax| tax.tax_type != TAX_IEPS);
+
+    let included = ordered.iter().any(|tax| tax.is_included);
+    let mut subtotal = if included {
+        let factor = ordered
+            .iter()
+            .filter(|tax| !tax.is_exempt)
+            .fold(Decimal::ONE, |factor, tax| {
+                factor * (Decimal::ONE + tax.rate)
+            });
+        (amount / factor).round_dp(2)
+    } else {
+        amount
+    };
+
+    let mut breakdown = tax_breakdown(&ordered, subtotal);
+    let tax_amount: Decimal = breakdown.iter().map(|tax| tax.amount).sum();
+
+    // La diferencia de redondeo del precio con impuestos se absorbe en el subtotal
+    if included && subtotal + tax_amount != amount {
+        let adjustment = amount - tax_amount - subtotal;
+        subtotal += adjustment;
+        for tax in &mut breakdown {
+            tax.base += adjustment;
+        }
+    }
+
+    LineTotals {
+        gross,
+        subtotal,
+        tax_amount,
+        total: subtotal + tax_amount,
+        taxes: breakdown,
+    }
+}
+
+/// Calcula cada impuesto sobre el subtotal; el IVA toma como base el subtotal más
+/// el IEPS. Un impuesto exento se registra con importe cero, a diferencia de la tasa 0%.
+fn tax_breakdown(ordered: &[&product_taxes::Model], subtotal: Decimal) -> Vec<LineTax> {
+    let mut ieps_amount = Decimal::ZERO;
+    let mut breakdown = Vec::with_capacity(ordered.len());
+
+    for tax in ordered {
+        let base = if tax.tax_type == TAX_IVA {
+            subtotal + ieps_amount
+        } else {
+            subtotal
+        };
+        let amount = if tax.is_exempt {
+            Decimal::ZERO
+        } else {
+            (base * tax.rate).round_dp(2)
+        };
+        if tax.tax_type == TAX_IEPS {
+            ieps_amount += amount;
+        }
+
+        breakdown.push(LineTax {
+            tax_type: tax.tax_type.clone(),
+            rate: tax.rate,
+            is_included: tax.is_included,
+            is_exempt: tax.is_exempt,
+            base,
+            amount,
+        });
+    }
+
+    breakdown
+}
+
+/// Convierte un descuento en porcentaje o monto fijo a su importe sobre la base indicada
+pub fn discount_amount(base: Decimal, discount: &DiscountRequest) -> Result<Decimal, String> {
+    if discount.value < Decimal::ZERO {
+        return Err("El descuento no puede ser negativo".to_string());
+    }
+
+    match discount.discount_type {
+        DiscountType::Percent => {
+            if discount.value > Decimal::ONE_HUNDRED {
+                return Err("El descuento no puede ser mayor al 100%".to_string());
+            }
+            Ok((base * discount.value / Decimal::ONE_HUNDRED).round_dp(2))
+        }
+        DiscountType::Amount => {
+            if discount.value > base {
+                return Err(format!(
+                    "El descuento de {} excede el importe de {}",
+                    discount.value, base
+                ));
+            }
+            Ok(discount.value.round_dp(2))
+        }
+    }
+}
+
+/// Reparte un importe entre varias bases de forma proporcional.
+/// La diferencia de redondeo se asigna a la última base con importe.
+pub fn prorate(amount: Decimal, bases: &[Decimal]) -> Vec<Decimal> {
+    let total: Decimal = bases.iter().sum();
+    if total <= Decimal::ZERO || amount == Decimal::ZERO {
+        return vec![Decimal::ZERO; bases.len()];
+    }
+
+    let mut shares: Vec<Decimal> = bases
+        .iter()
+        .map(|base| (amount * base / total).round_dp(2))
+        .collect();
+
+    let assigned: Decimal = shares.iter().sum();
+    if let Some(last) = bases.iter().rposition(|base| *base > Decimal::ZERO) {
+        shares[last] += amount - assigned;
+    }
+
+    shares
+}
+
+/// Indica si un descuento supera el porcentaje máximo sobre el importe bruto.
+/// Se tolera un centavo por el redondeo del prorrateo del descuento al ticket.
+pub fn exceeds_discount_limit(discount: Decimal, gross: Decimal, max_percent: Decimal) -> bool {
+    let allowed = (gross * max_percent / Decimal::ONE_HUNDRED).round_dp(2);
+    discount > allowed + Decimal::new(1, 2)
+}
+
+/// Verifica que el importe enviado por el cliente coincida con el calculado.
+/// Se tolera un centavo por línea por diferencias de redondeo del frontend.
+pub fn amounts_match(client: Decimal, computed: Decimal, lines: usize) -> bool {
+    let tolerance = Decimal::new(1, 2) * Decimal::from(lines.max(1) as u64);
+    (client.round_dp(2) - computed).abs() <= tolerance
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tax(tax_type: &str, rate: Decimal, is_included: bool) -> product_taxes::Model {
+        product_taxes::Model {
+            id: 0,
+            product_id: 1,
+            tax_type: tax_type.to_string(),
+            rate,
+            is_included,
+            is_exempt: false,
+        }
+    }
+
+    #[test]
+    fn included_taxes_back_out_ieps_before_iva() {
+        // El IVA se recibe primero para comprobar que el IEPS se calcula antes
+        let taxes = [
+            tax(TAX_IVA, Decimal::new(16, 2), true),
+            tax(TAX_IEPS, Decimal::new(8, 2), true),
+        ];
+        let line = compute_line(Decimal::from(100), Decimal::ONE, &taxes, Decimal::ZERO);
+
+        assert_eq!(line.total, Decimal::from(100));
+        assert_eq!(line.subtotal, Decimal::new(7982, 2));
+        assert_eq!(line.taxes[0].tax_type, TAX_IEPS);
+        assert_eq!(line.taxes[0].amount, Decimal::new(639, 2));
+        assert_eq!(line.taxes[1].tax_type, TAX_IVA);
+        assert_eq!(line.taxes[1].base, Decimal::new(8621, 2));
+        assert_eq!(line.taxes[1].amount, Decimal::new(1379, 2));
+        assert_eq!(line.tax_amount, Decimal::new(2018, 2));
+        assert_eq!(line.effective_rate(), Decimal::new(2528, 4));
+    }
+
+    #[test]
+    fn included_taxes_absorb_rounding_in_subtotal() {
+        let taxes = [tax(TAX_IVA, Decimal::new(16, 2), true)];
+        let line = compute_line(Decimal::new(555, 2), Decimal::ONE, &taxes, Decimal::ZERO);
+
+        assert_eq!(line.total, Decimal::new(555, 2));
+        assert_eq!(line.subtotal, Decimal::new(479, 2));
+        assert_eq!(line.taxes[0].base, Decimal::new(479, 2));
+        assert_eq!(line.tax_amount, Decimal::new(76, 2));
+    }
+
+    #[test]
+    fn prorate_assigns_remainder_to_last_base() {
+        let bases = [
+            Decimal::new(3333, 2),
+            Decimal::new(3333, 2),
+            Decimal::new(3334, 2),
+        ];
+        let shares = prorate(Decimal::from(10), &bases);
+
+        assert_eq!(
+            shares,
+            [
+                Decimal::new(333, 2),
+                Decimal::new(333, 2),
+                Decimal::new(334, 2)
+            ]
+        );
+        assert_eq!(shares.iter().sum::<Decimal>(), Decimal::from(10));
+    }
+
+    #[test]
+    fn prorate_skips_zero_bases() {
+        let bases = [Decimal::from(50), Decimal::from(50), Decimal::ZERO];
+        let shares = prorate(Decimal::new(1, 2), &bases);
+
+        assert_eq!(shares[2], Decimal::ZERO);
+        assert_eq!(shares.iter().sum::<Decimal>(), Decimal::new(1, 2));
+    }
+}
@@ -1,4 +1,7 @@
+use super::pricing::LineTax;
 use crate::entities::sales::{self, ActiveModel};
+use crate::entities::{quote_detail_taxes, sale_detail_taxes};
+use crate::gift_cards::structs::GiftCard;
 use cuid2;
 use sea_orm::{prelude::Decimal, ActiveValue::Set};
 use serde::{Deserialize, Serialize};
@@ -14,6 +17,21 @@ pub struct Sale {
     pub updated_at: String,
     pub created_by: String,
     pub updated_by: String,
+    pub cancelled_by: Option<String>,
+    pub cancelled_at: Option<String>,
+    pub cancellation_reason: Option<String>,
+    pub change_amount: Decimal,
+    pub customer_id: Option<i32>,
+    pub discount_type: Option<String>,
+    pub discount_value: Decimal,
+    pub discount_amount: Decimal, // Descuento al ticket antes de impuestos
+    pub coupon_id: Option<i32>,
+    pub coupon_discount_amount: Decimal,
+    pub cash_session_id: Option<i32>,
+    pub layaway_status: Option<String>, // pending, completed, cancelled; None si no es apartado
+    pub layaway_due_date: Option<String>,
+    pub layaway_penalty_amount: Decimal,
+    pub completed_at: Option<String>, // Fecha en que la venta cuenta como ingreso
 }
 
 impl From<sales::Model> for Sale {
@@ -27,6 +45,21 @@ impl From<sales::Model> for Sale {
             updated_at: value.updated_at.to_string(),
             created_by: value.created_by,
             updated_by: value.updated_by,
+            cancelled_by: value.cancelled_by,
+            cancelled_at: value.cancelled_at.map(|date| date.to_string()),
+            cancellation_reason: value.cancellation_reason,
+            change_amount: value.change_amount,
+            customer_id: value.customer_id,
+            discount_type: value.discount_type,
+            discount_value: value.discount_value,
+            discount_amount: value.discount_amount,
+            coupon_id: value.coupon_id,
+            coupon_discount_amount: value.coupon_discount_amount,
+            cash_session_id: value.cash_session_id,
+            layaway_status: value.layaway_status,
+            layaway_due_date: value.layaway_due_date.map(|date| date.to_string()),
+            layaway_penalty_amount: value.layaway_penalty_amount,
+            completed_at: value.completed_at.map(|date| date.to_string()),
         }
     }
 }
@@ -92,6 +125,15 @@ pub struct SaleFilter {
     pub limit: u64,
     pub date_from: Option<String>,
     pub date_to: Option<String>,
+    pub customer_id: Option<i32>,
+    pub created_by: Option<String>, // Cajero que registró la venta
+    pub payment_method_id: Option<i32>,
+    pub min_total: Option<Decimal>,
+    pub max_total: Option<Decimal>,
+    pub id_prefix: Option<String>,  // Primeros caracteres del folio
+    pub product_id: Option<i32>,    // Ventas que contienen el producto
+    pub sort_by: Option<String>,    // date (default), total
+    pub sort_order: Option<String>, // asc, desc (default)
 }
 
 /// Respuesta paginada de ventas
@@ -102,20 +144,117 @@ pub struct SaleListResponse {
     pub total_items: u64,
 }
 
-// Request para item individual de venta
+/// Tipo de descuento: porcentaje o monto fijo
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DiscountType {
+    Percent,
+    Amount,
+}
+
+impl DiscountType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DiscountType::Percent => "percent",
+            DiscountType::Amount => "amount",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "percent" => Some(DiscountType::Percent),
+            "amount" => Some(DiscountType::Amount),
+            _ => None,
+        }
+    }
+}
+
+// Descuento aplicado a una línea o a todo el ticket, siempre antes de impuestos
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DiscountRequest {
+    pub discount_type: DiscountType,
+    pub value: Decimal, // Porcentaje (0-100) o monto sin impuestos
+}
+
+impl DiscountRequest {
+    /// Reconstruye un descuento guardado como tipo y valor
+    pub fn from_stored(discount_type: Option<&str>, value: Decimal) -> Option<Self> {
+        discount_type
+            .and_then(DiscountType::parse)
+            .map(|discount_type| Self {
+                discount_type,
+                value,
+            })
+    }
+}
+
+// Request para item individual de venta.
+// El precio y la tasa de impuesto se toman del catálogo de productos.
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct SaleItemRequest {
     pub product_id: i32,
-    pub quantity: i32,
-    pub unit_price: Decimal,
-    pub tax_rate: Decimal, // 0.16 para 16%
+    pub quantity: Decimal,
+    pub price_override: Option<Decimal>, // Requiere permiso sales.price_override
+    pub discount: Option<DiscountRequest>, // Requiere permiso sales.discount
+}
+
+// Forma de pago individual dentro de una venta
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SalePaymentRequest {
+    pub payment_method_id: i32,
+    pub amount: Decimal, // En efectivo puede exceder el total para calcular el cambio
+    pub gift_card_code: Option<String>, // Requerido al pagar con tarjeta de regalo
 }
 
-// Request para crear venta completa
+// Request para crear venta completa.
+// subtotal y total son los esperados por el cliente y se validan contra el cálculo del backend.
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct CreateSaleRequest {
     pub items: Vec<SaleItemRequest>,
-    pub payment_method_id: i32,
+    pub payments: Vec<SalePaymentRequest>,
+    pub subtotal: Decimal,
+    pub total: Decimal,
+    pub customer_id: Option<i32>, // Cliente opcional (venta al público en general si es None)
+    pub discount: Option<DiscountRequest>, // Descuento al ticket, se prorratea entre las líneas
+    pub coupon_code: Option<String>, // Cupón a canjear; se consume al registrar la venta
+    pub parked_sale_id: Option<i32>, // Ticket en espera que se cobra con esta venta
+    pub idempotency_key: Option<String>, // Generada por la caja; un reintento no duplica la venta
+}
+
+// Request para calcular los importes de una venta sin registrarla
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SalePreviewRequest {
+    pub items: Vec<SaleItemRequest>,
+    pub discount: Option<DiscountRequest>,
+    pub coupon_code: Option<String>,
+}
+
+// Línea calculada con promoción y descuentos aplicados
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SalePreviewLine {
+    pub product_id: i32,
+    pub product_name: String,
+    pub unit_of_measure: String,
+    pub quantity: Decimal,
+    pub unit_price: Decimal,
+    pub promotion_id: Option<i32>,
+    pub promotion_name: Option<String>,
+    pub promotion_discount_amount: Decimal,
+    pub discount_amount: Decimal,
+    pub ticket_discount_amount: Decimal,
+    pub coupon_discount_amount: Decimal,
+    pub subtotal: Decimal,
+    pub tax_amount: Decimal,
+    pub taxes: Vec<SaleLineTax>,
+    pub total: Decimal,
+}
+
+// Importes que el backend usará al registrar la venta
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SalePreviewResponse {
+    pub lines: Vec<SalePreviewLine>,
+    pub discount_amount: Decimal,
+    pub coupon_discount_amount: Decimal,
     pub subtotal: Decimal,
     pub total: Decimal,
 }
@@ -126,7 +265,13 @@ pub struct CreateSaleResponse {
     pub sale_id: String,
     pub subtotal: Decimal,
     pub total: Decimal,
+    pub amount_paid: Decimal,
+    pub change: Decimal,
+    pub balance_due: Decimal, // Saldo pendiente de un apartado; cero en ventas normales
+    pub points_earned: i32,   // Puntos acumulados por el cliente en la venta
+    pub gift_cards: Vec<GiftCard>, // Tarjetas de regalo emitidas en la venta
     pub created_at: String,
+    pub print_error: Option<String>, // Solo si falló la impresión automática del ticket
 }
 
 // Método de pago para frontend
@@ -135,6 +280,7 @@ pub struct PaymentMethodResponse {
     pub id: i32,
     pub name: String,
     pub sat_key: String,
+    pub tender: String, // cash, card, transfer, points, gift_card, credit u other
 }
 
 impl From<crate::entities::payment_methods::Model> for PaymentMethodResponse {
@@ -143,6 +289,164 @@ impl From<crate::entities::payment_methods::Model> for PaymentMethodResponse {
             id: model.id,
             name: model.name,
             sat_key: model.sat_key,
+            tender: model.tender,
         }
     }
 }
+
+// Request para línea individual de devolución
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RefundItemRequest {
+    pub product_id: i32,
+    pub quantity: Decimal,
+}
+
+// Request para registrar una devolución sobre una venta
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CreateRefundRequest {
+    pub sale_id: String,
+    pub items: Vec<RefundItemRequest>,
+    pub reason: String,
+    pub payment_method_id: Option<i32>, // Forma de reembolso; efectivo si es None
+    pub gift_card_code: Option<String>, // Tarjeta a abonar; sin código se emite un vale nuevo
+}
+
+// Respuesta de devolución creada
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CreateRefundResponse {
+    pub refund_id: i32,
+    pub sale_id: String,
+    pub amount: Decimal,
+    pub gift_card_code: Option<String>, // Vale o tarjeta donde quedó el saldo a favor
+    pub created_at: String,
+}
+
+// Request para cancelar una venta
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CancelSaleRequest {
+    pub sale_id: String,
+    pub reason: String,
+}
+
+// Impuesto trasladado en una línea de venta
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SaleLineTax {
+    pub tax_type: String, // iva, ieps
+    pub rate: Decimal,    // 0.16 = 16%
+    pub is_included: bool,
+    pub is_exempt: bool,
+    pub base: Decimal,
+    pub amount: Decimal,
+}
+
+impl From<LineTax> for SaleLineTax {
+    fn from(value: LineTax) -> Self {
+        Self {
+            tax_type: value.tax_type,
+            rate: value.rate,
+            is_included: value.is_included,
+            is_exempt: value.is_exempt,
+            base: value.base,
+            amount: value.amount,
+        }
+    }
+}
+
+impl From<sale_detail_taxes::Model> for SaleLineTax {
+    fn from(value: sale_detail_taxes::Model) -> Self {
+        Self {
+            tax_type: value.tax_type,
+            rate: value.rate,
+            is_included: value.is_included,
+            is_exempt: value.is_exempt,
+            base: value.base,
+            amount: value.amount,
+        }
+    }
+}
+
+impl From<quote_detail_taxes::Model> for SaleLineTax {
+    fn from(value: quote_detail_taxes::Model) -> Self {
+        Self {
+            tax_type: value.tax_type,
+            rate: value.rate,
+            is_included: value.is_included,
+            is_exempt: value.is_exempt,
+            base: value.base,
+            amount: value.amount,
+        }
+    }
+}
+
+// Línea de venta con datos del producto
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SaleLineDetail {
+    pub id: i32,
+    pub product_id: i32,
+    pub product_name: Option<String>,
+    pub product_code: Option<String>,
+    pub unit_of_measure: Option<String>,
+    pub quantity: Decimal,
+    pub unit_price: Decimal,
+    pub discount_type: Option<String>,
+    pub discount_value: Decimal,
+    pub discount_amount: Decimal,        // Descuento propio de la línea
+    pub ticket_discount_amount: Decimal, // Parte prorrateada del descuento al ticket
+    pub promotion_id: Option<i32>,
+    pub promotion_name: Option<String>,
+    pub promotion_discount_amount: Decimal,
+    pub coupon_discount_amount: Decimal, // Parte prorrateada del cupón
+    pub subtotal: Decimal,
+    pub tax_rate: Decimal, // Tasa efectiva de todos los impuestos; el desglose está en `taxes`
+    pub tax_amount: Decimal, // Suma de todos los impuestos
+    pub taxes: Vec<SaleLineTax>,
+    pub total: Decimal,
+}
+
+// Pago registrado con el nombre del método
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SalePaymentDetail {
+    pub id: i32,
+    pub payment_method_id: i32,
+    pub payment_method_name: Option<String>,
+    pub amount: Decimal,
+    pub created_at: String,
+}
+
+// Línea devuelta dentro de una devolución
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RefundLineDetail {
+    pub id: i32,
+    pub product_id: i32,
+    pub product_name: Option<String>,
+    pub quantity: Decimal,
+    pub unit_price: Decimal,
+}
+
+// Devolución registrada sobre la venta
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RefundDetail {
+    pub id: i32,
+    pub amount: Decimal,
+    pub reason: String,
+    pub payment_method_id: Option<i32>,
+    pub created_at: String,
+    pub created_by: String,
+    pub created_by_name: Option<String>,
+    pub lines: Vec<RefundLineDetail>,
+}
+
+// Detalle completo de una venta (ticket)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SaleDetailResponse {
+    pub sale: Sale,
+    pub customer_name: Option<String>,
+    pub coupon_code: Option<String>,
+    pub created_by_name: Option<String>,
+    pub updated_by_name: Option<String>,
+    pub cancelled_by_name: Option<String>,
+    pub lines: Vec<SaleLineDetail>,
+    pub payments: Vec<SalePaymentDetail>,
+    pub refunds: Vec<RefundDetail>,
+    pub gift_cards: Vec<GiftCard>, // Tarjetas de regalo vendidas
+}
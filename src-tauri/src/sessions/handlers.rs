@@ -129,6 +129,7 @@ pub async fn login(
         profile_name: profile.name,
         email: user.email,
         permissions,
+        max_discount_percent: profile.max_discount_percent,
     };
     let session_clone = session.clone();
 
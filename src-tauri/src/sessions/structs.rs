@@ -1,3 +1,4 @@
+use sea_orm::prelude::Decimal;
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -8,6 +9,7 @@ pub struct Session {
     pub profile_name: String,
     pub email: String,
     pub permissions: Vec<String>,
+    pub max_discount_percent: Option<Decimal>, // None = sin límite
 }
 
 impl Session {
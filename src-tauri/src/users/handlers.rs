@@ -1,5 +1,8 @@
 use bcrypt::{hash, DEFAULT_COST};
-use sea_orm::{ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, QuerySelect};
+use sea_orm::{
+    prelude::Decimal, ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder,
+    QuerySelect,
+};
 
 use super::structs::{NewUser, Profile, UpdateUser, User, UserFilter, UserListReturn};
 use crate::entities::{prelude::Users, profiles, profiles::Entity as Profiles, users};
@@ -269,3 +272,36 @@ pub async fn update_user(
 
     Ok(User::from_with_profile(updated, profile))
 }
+
+/// Configura el descuento máximo que puede otorgar un perfil.
+/// El cambio aplica a partir del siguiente inicio de sesión de sus usuarios.
+#[tauri::command]
+pub async fn update_profile_discount_limit(
+    state: tauri::State<'_, AppState>,
+    profile_id: i32,
+    max_discount_percent: Option<Decimal>,
+) -> Result<Profile, String> {
+    require_permission(&state, "profiles.manage")?;
+    let db = &state.database;
+
+    use sea_orm::{ActiveModelTrait, ActiveValue};
+
+    if let Some(percent) = max_discount_percent {
+        if percent < Decimal::ZERO || percent > Decimal::ONE_HUNDRED {
+            return Err("El descuento máximo debe estar entre 0 y 100".to_string());
+        }
+    }
+
+    let profile = Profiles::find_by_id(profile_id)
+        .one(db)
+        .await
+        .map_err(|_| DB_ERROR)?
+        .ok_or("Perfil no encontrado".to_string())?;
+
+    let mut active_model: profiles::ActiveModel = profile.into();
+    active_model.max_discount_percent = ActiveValue::Set(max_discount_percent);
+
+    let updated = active_model.update(db).await.map_err(|_| DB_ERROR)?;
+
+    Ok(Profile::from(updated))
+}
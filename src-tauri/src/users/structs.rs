@@ -1,4 +1,5 @@
 use crate::entities::{profiles, users};
+use sea_orm::prelude::Decimal;
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -75,6 +76,7 @@ pub struct Profile {
     pub name: String,
     pub description: String,
     pub is_active: bool,
+    pub max_discount_percent: Option<Decimal>, // None = sin límite
 }
 
 impl From<profiles::Model> for Profile {
@@ -84,6 +86,7 @@ impl From<profiles::Model> for Profile {
             name: profile.name,
             description: profile.description,
             is_active: profile.is_active,
+            max_discount_percent: profile.max_discount_percent,
         }
     }
 }
@@ -1,5 +1,11 @@
 import { invoke } from "@tauri-apps/api/core";
-import type { NewProduct, UpdateProduct, ProductFilter, ProductListResponse } from "@/types";
+import type {
+  NewProduct,
+  UpdateProduct,
+  ProductFilter,
+  ProductListResponse,
+  ProductLookup,
+} from "@/types";
 
 export const productActions = {
   getProducts: async (
@@ -11,6 +17,10 @@ export const productActions = {
     return await invoke<ProductListResponse>("get_products", { filters });
   },
 
+  getProductByCode: async (code: string): Promise<ProductLookup> => {
+    return await invoke<ProductLookup>("get_product_by_code", { code });
+  },
+
   createProduct: async (productData: NewProduct) => {
     return await invoke("create_product", {
       productData,
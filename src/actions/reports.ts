@@ -1,5 +1,6 @@
 import { invoke } from "@tauri-apps/api/core";
 import type { LucideIcon } from "lucide-preact";
+import type { TaxType } from "@/types";
 import {
   LayoutDashboard,
   TrendingUp,
@@ -33,7 +34,7 @@ export interface DashboardResult {
   dominant_payment_method: string | null;
   dominant_payment_amount: string;
   top_product: string | null;
-  top_product_quantity: number;
+  top_product_quantity: string;
 }
 
 // ============================================================================
@@ -75,9 +76,9 @@ export interface ProductReportItem {
   product_id: number;
   product_name: string;
   category_name: string | null;
-  quantity_sold: number;
-  quantity_refunded: number;
-  net_quantity: number;
+  quantity_sold: string;
+  quantity_refunded: string;
+  net_quantity: string;
   gross_revenue: string;
   net_revenue: string;
   share_percentage: string;
@@ -85,9 +86,9 @@ export interface ProductReportItem {
 
 export interface ProductReportResult {
   items: ProductReportItem[];
-  total_quantity_sold: number;
-  total_quantity_refunded: number;
-  total_net_quantity: number;
+  total_quantity_sold: string;
+  total_quantity_refunded: string;
+  total_net_quantity: string;
   total_gross_revenue: string;
   total_net_revenue: string;
 }
@@ -105,14 +106,14 @@ export interface CategoryReportItem {
   category_id: number | null;
   category_name: string;
   net_sales: string;
-  quantity_sold: number;
+  quantity_sold: string;
   share_percentage: string;
 }
 
 export interface CategoryReportResult {
   items: CategoryReportItem[];
   total_net_sales: string;
-  total_quantity_sold: number;
+  total_quantity_sold: string;
 }
 
 // ============================================================================
@@ -150,7 +151,7 @@ export interface RefundsReportParams {
 export interface TopRefundedProduct {
   product_id: number;
   product_name: string;
-  quantity_refunded: number;
+  quantity_refunded: string;
   amount_refunded: string;
 }
 
@@ -162,6 +163,88 @@ export interface RefundsReportResult {
   top_refunded_products: TopRefundedProduct[];
 }
 
+// ============================================================================
+// 7. REPORTE DE DESCUENTOS
+// ============================================================================
+
+export interface DiscountsReportParams {
+  date_from: string;
+  date_to: string;
+}
+
+export interface DiscountsByUserItem {
+  user_id: string;
+  user_name: string;
+  sales_count: number;
+  total_discount: string;
+}
+
+export interface DiscountedProductItem {
+  product_id: number;
+  product_name: string;
+  quantity: string;
+  total_discount: string;
+}
+
+export interface DiscountsReportResult {
+  line_discounts: string;
+  ticket_discounts: string;
+  total_discounts: string;
+  discounted_sales_count: number;
+  gross_amount: string;
+  discount_percentage: string;
+  by_user: DiscountsByUserItem[];
+  top_discounted_products: DiscountedProductItem[];
+}
+
+// ============================================================================
+// 8. REPORTE DE PROMOCIONES
+// ============================================================================
+
+export interface PromotionsReportParams {
+  date_from: string;
+  date_to: string;
+}
+
+export interface PromotionReportItem {
+  promotion_id: number;
+  promotion_name: string;
+  promotion_type: string;
+  sales_count: number;
+  units_sold: string;
+  discount_amount: string;
+  net_sales: string;
+}
+
+export interface PromotionsReportResult {
+  items: PromotionReportItem[];
+  total_discount: string;
+  total_net_sales: string;
+}
+
+// ============================================================================
+// 9. RESUMEN DE IMPUESTOS
+// ============================================================================
+
+export interface TaxSummaryParams {
+  date_from: string;
+  date_to: string;
+}
+
+export interface TaxSummaryItem {
+  tax_type: TaxType;
+  rate: string; // 0.16 = 16%
+  is_exempt: boolean; // Exento; se reporta aparte de la tasa 0%
+  sales_count: number;
+  base: string;
+  amount: string;
+}
+
+export interface TaxSummaryResult {
+  items: TaxSummaryItem[];
+  total_amount: string;
+}
+
 // ============================================================================
 // CATALOGO DE REPORTES DISPONIBLES
 // ============================================================================
@@ -266,6 +349,27 @@ export const reportsActions = {
     return await invoke<RefundsReportResult>("get_refunds_report", { params });
   },
 
+  /**
+   * Obtiene el reporte de descuentos otorgados
+   */
+  getDiscountsReport: async (params: DiscountsReportParams): Promise<DiscountsReportResult> => {
+    return await invoke<DiscountsReportResult>("get_discounts_report", { params });
+  },
+
+  /**
+   * Obtiene el reporte de impacto de promociones
+   */
+  getPromotionsReport: async (params: PromotionsReportParams): Promise<PromotionsReportResult> => {
+    return await invoke<PromotionsReportResult>("get_promotions_report", { params });
+  },
+
+  /**
+   * Obtiene el resumen de impuestos trasladados por impuesto y tasa
+   */
+  getTaxSummaryReport: async (params: TaxSummaryParams): Promise<TaxSummaryResult> => {
+    return await invoke<TaxSummaryResult>("get_tax_summary_report", { params });
+  },
+
   /**
    * Obtiene la lista de reportes disponibles
    */
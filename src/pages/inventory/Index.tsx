@@ -65,10 +65,11 @@ export default function Index() {
 
       // Filtro de estado de stock
       if (filters.stockStatus.length > 0) {
+        const stock = parseFloat(product.stock);
         const stockMatches = filters.stockStatus.some((status) => {
-          if (status === "optimal") return product.stock >= 10;
-          if (status === "low") return product.stock > 0 && product.stock < 10;
-          if (status === "out") return product.stock === 0;
+          if (status === "optimal") return stock >= 10;
+          if (status === "low") return stock > 0 && stock < 10;
+          if (status === "out") return stock === 0;
           return false;
         });
 
@@ -83,7 +84,7 @@ export default function Index() {
     const product = products.find((p) => p.id === id);
     if (!product || !session) return;
 
-    const newStock = Math.max(0, product.stock + delta);
+    const newStock = Math.max(0, parseFloat(product.stock) + delta);
     try {
       await productActions.updateProduct(id, {
         stock: newStock,
@@ -7,9 +7,10 @@ interface InventoryStatsProps {
 
 export default function InventoryStats({ products }: InventoryStatsProps) {
   const totalProducts = products.length;
-  const inventoryValue = products.reduce((acc, p) => acc + parseFloat(p.cost) * p.stock, 0);
-  const lowStock = products.filter((p) => p.stock > 0 && p.stock < 10).length;
-  const outOfStock = products.filter((p) => p.stock === 0).length;
+  const stocks = products.map((p) => parseFloat(p.stock));
+  const inventoryValue = products.reduce((acc, p, i) => acc + parseFloat(p.cost) * stocks[i], 0);
+  const lowStock = stocks.filter((stock) => stock > 0 && stock < 10).length;
+  const outOfStock = stocks.filter((stock) => stock === 0).length;
 
   return (
     <div className="p-6 grid grid-cols-4 gap-4">
@@ -129,7 +129,7 @@ export default function InventoryTable({
       header: "Estado",
       enableSorting: false,
       cell: (info) => {
-        const status = getStockStatus(info.row.original.stock);
+        const status = getStockStatus(parseFloat(info.row.original.stock));
         return (
           <span
             className={`inline-flex items-center px-2.5 py-0.5 rounded-full text-xs font-medium border ${status.color}`}
@@ -21,6 +21,8 @@ export default function Sales() {
   const [createdSaleId, setCreatedSaleId] = useState<string>("");
   const [isProcessing, setIsProcessing] = useState(false);
   const [error, setError] = useState<string | null>(null);
+  // Clave del cobro en curso; un reintento con la misma clave no duplica la venta
+  const [idempotencyKey, setIdempotencyKey] = useState(() => crypto.randomUUID());
 
   // Cargar payment methods
   const { data: paymentMethods } = useSWR<PaymentMethodResponse[]>(
@@ -113,12 +115,16 @@ export default function Sales() {
         items: cart.map((item) => ({
           product_id: item.id,
           quantity: item.quantity,
-          unit_price: item.price,
-          tax_rate: item.tax / 100, // Convertir 16 a 0.16
         })),
-        payment_method_id: paymentMethodId,
+        payments: [
+          {
+            payment_method_id: paymentMethodId,
+            amount: selectedPayment === "cash" ? parseFloat(cashReceived) || total : total,
+          },
+        ],
         subtotal: subtotal,
         total: total,
+        idempotency_key: idempotencyKey,
       };
 
       const response = await salesActions.createSale(request);
@@ -140,6 +146,7 @@ export default function Sales() {
     setCreatedSaleId("");
     setError(null);
     setIsProcessing(false);
+    setIdempotencyKey(crypto.randomUUID());
     setViewState("sales");
   };
 
@@ -27,7 +27,7 @@ export default function SalesView({ cart, onAddToCart, onStartPayment }: SalesVi
     price: parseFloat(p.price),
     cost: parseFloat(p.cost),
     tax: parseFloat(p.tax),
-    stock: p.stock,
+    stock: parseFloat(p.stock),
     category: p.category_name ?? "otros",
   }));
 
@@ -16,6 +16,7 @@ export interface Session {
   profile_name: string;
   email: string;
   permissions: string[];
+  max_discount_percent: string | null; // null = sin límite
 }
 
 /** Estado de autenticación */
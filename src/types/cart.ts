@@ -2,6 +2,10 @@
  * Tipos relacionados con el carrito de compras y ventas
  */
 
+import type { GiftCard } from "./giftCard";
+import type { LayawayStatus } from "./layaway";
+import type { TaxType, UnitOfMeasure } from "./product";
+
 /** Item del carrito */
 export interface CartItem {
   id: number;
@@ -32,32 +36,176 @@ export interface Sale {
   createdAt: Date;
 }
 
+/** Descuento por línea o al ticket, aplicado antes de impuestos */
+export interface DiscountRequest {
+  discount_type: "percent" | "amount";
+  value: number; // Porcentaje (0-100) o monto sin impuestos
+}
+
 /**
  * Request para crear venta
  */
 export interface SaleItemRequest {
   product_id: number;
-  quantity: number;
-  unit_price: number;
-  tax_rate: number; // Como decimal: 0.16 para 16%
+  quantity: number; // Fraccionaria solo si el producto lo permite (máximo 3 decimales)
+  price_override?: number; // Requiere permiso sales.price_override
+  discount?: DiscountRequest; // Requiere permiso sales.discount
+}
+
+export interface SalePaymentRequest {
+  payment_method_id: number;
+  amount: number; // En efectivo puede exceder el total (cambio)
+  gift_card_code?: string; // Requerido al pagar con tarjeta de regalo
 }
 
 export interface CreateSaleRequest {
   items: SaleItemRequest[];
-  payment_method_id: number;
+  payments: SalePaymentRequest[];
   subtotal: number;
   total: number;
+  customer_id?: number; // Cliente opcional
+  discount?: DiscountRequest; // Descuento al ticket
+  coupon_code?: string; // Cupón a canjear
+  parked_sale_id?: number; // Ticket en espera que se cobra con esta venta
+  idempotency_key?: string; // Generada por la caja; un reintento regresa la venta original
+}
+
+/** Request para calcular los importes de la venta con promociones y descuentos */
+export interface SalePreviewRequest {
+  items: SaleItemRequest[];
+  discount?: DiscountRequest;
+  coupon_code?: string;
+}
+
+/** Impuesto trasladado en una línea de venta */
+export interface SaleLineTax {
+  tax_type: TaxType;
+  rate: string; // 0.16 = 16%
+  is_included: boolean;
+  is_exempt: boolean;
+  base: string;
+  amount: string;
+}
+
+export interface SalePreviewLine {
+  product_id: number;
+  product_name: string;
+  unit_of_measure: UnitOfMeasure;
+  quantity: string; // Decimal; fraccionaria en productos a granel
+  unit_price: string;
+  promotion_id: number | null;
+  promotion_name: string | null;
+  promotion_discount_amount: string;
+  discount_amount: string;
+  ticket_discount_amount: string;
+  coupon_discount_amount: string;
+  subtotal: string;
+  tax_amount: string;
+  taxes: SaleLineTax[];
+  total: string;
+}
+
+export interface SalePreviewResponse {
+  lines: SalePreviewLine[];
+  discount_amount: string; // Descuento al ticket
+  coupon_discount_amount: string;
+  subtotal: string;
+  total: string;
 }
 
 export interface CreateSaleResponse {
   sale_id: string;
   subtotal: string; // Decimal de Rust viene como string
   total: string;
+  amount_paid: string;
+  change: string;
+  balance_due: string; // Saldo pendiente de un apartado; "0" en ventas normales
+  points_earned: number; // Puntos acumulados por el cliente en la venta
+  gift_cards: GiftCard[]; // Tarjetas de regalo emitidas en la venta
+  created_at: string;
+  print_error: string | null; // Solo si falló la impresión automática
+}
+
+/** Venta registrada tal como la regresa el backend */
+export interface SaleRecord {
+  id: string;
+  subtotal: string;
+  total: string;
+  status: boolean; // false = cancelada
   created_at: string;
+  updated_at: string;
+  created_by: string;
+  updated_by: string;
+  cancelled_by: string | null;
+  cancelled_at: string | null;
+  cancellation_reason: string | null;
+  change_amount: string;
+  customer_id: number | null;
+  discount_type: string | null;
+  discount_value: string;
+  discount_amount: string;
+  coupon_id: number | null;
+  coupon_discount_amount: string;
+  cash_session_id: number | null;
+  layaway_status: LayawayStatus | null; // null si no es apartado
+  layaway_due_date: string | null;
+  layaway_penalty_amount: string;
+  completed_at: string | null; // Fecha en que la venta cuenta como ingreso
 }
 
+/** Request para dejar el carrito en espera */
+export interface ParkSaleRequest {
+  label: string;
+  items: SaleItemRequest[];
+  customer_id?: number;
+  discount?: DiscountRequest;
+  coupon_code?: string;
+}
+
+export interface ParkedSaleItem {
+  id: number;
+  product_id: number;
+  product_name: string | null;
+  product_code: string | null;
+  unit_price: string | null; // Precio vigente del catálogo
+  quantity: string;
+  price_override: string | null;
+  discount: DiscountRequest | null;
+}
+
+/** Ticket en espera, visible desde cualquier caja */
+export interface ParkedSale {
+  id: number;
+  label: string;
+  customer_id: number | null;
+  customer_name: string | null;
+  discount: DiscountRequest | null;
+  coupon_code: string | null;
+  created_by: string;
+  created_by_name: string | null;
+  created_at: string;
+  items: ParkedSaleItem[];
+}
+
+/** Ticket retomado; se cobra con create_sale enviando parked_sale_id */
+export interface ResumedParkedSale {
+  parked_sale: ParkedSale;
+  preview: SalePreviewResponse | null;
+  preview_error: string | null; // El ticket ya no se puede cobrar tal cual
+}
+
+export type PaymentTender =
+  | "cash"
+  | "card"
+  | "transfer"
+  | "points"
+  | "gift_card"
+  | "credit"
+  | "other";
+
 export interface PaymentMethodResponse {
   id: number;
   name: string;
-  sat_key: string;
+  sat_key: string; // Clave fiscal (FormaPago del CFDI)
+  tender: PaymentTender;
 }
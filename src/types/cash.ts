@@ -0,0 +1,131 @@
+/**
+ * Tipos relacionados con turnos de caja (corte de caja)
+ */
+
+/** Estado del turno */
+export type CashSessionStatus = "open" | "closed";
+
+/** Turno de caja con fondo inicial y resultado del corte */
+export interface CashSession {
+  id: number;
+  user_id: string;
+  user_name: string | null;
+  status: CashSessionStatus;
+  opening_float: string; // Decimal from DB comes as string
+  expected_cash: string | null; // Solo en turnos cerrados
+  counted_cash: string | null;
+  variance: string | null; // Contado - esperado; negativo = faltante
+  notes: string | null;
+  opened_at: string;
+  closed_at: string | null;
+  closed_by: string | null;
+}
+
+export interface OpenCashSessionRequest {
+  opening_float: number;
+}
+
+/** Cantidad contada de un billete o moneda */
+export interface DenominationCount {
+  denomination: number; // 1000, 500, 200, 100, 50, 20, 10, 5, 2, 1, 0.5, 0.2, 0.1
+  quantity: number;
+}
+
+export interface CloseCashSessionRequest {
+  cash_session_id: number;
+  counts: DenominationCount[];
+  notes?: string;
+}
+
+/** Tipo de movimiento de efectivo */
+export type CashMovementType = "in" | "out";
+
+/** Entrada o retiro de efectivo durante un turno */
+export interface CashMovement {
+  id: number;
+  cash_session_id: number;
+  movement_type: CashMovementType;
+  amount: string;
+  reason: string;
+  authorized_by: string;
+  authorized_by_name: string | null;
+  created_at: string;
+}
+
+export interface CreateCashMovementRequest {
+  cash_session_id?: number; // Sin valor = turno abierto del usuario actual
+  movement_type: CashMovementType;
+  amount: number;
+  reason: string;
+}
+
+/** Filtros para listar movimientos; sin permiso cash.view solo los de turnos propios */
+export interface CashMovementFilter {
+  cash_session_id?: number;
+  user_id?: string;
+  movement_type?: CashMovementType;
+  date_from?: string;
+  date_to?: string;
+  page: number;
+  limit: number;
+}
+
+export interface CashMovementListResponse {
+  movements: CashMovement[];
+  total_pages: number;
+  total_items: number;
+}
+
+/** Filtros para listar turnos; sin permiso cash.view solo se ven los propios */
+export interface CashSessionFilter {
+  user_id?: string;
+  status?: CashSessionStatus;
+  date_from?: string;
+  date_to?: string;
+  page: number;
+  limit: number;
+}
+
+export interface CashSessionListResponse {
+  sessions: CashSession[];
+  total_pages: number;
+  total_items: number;
+}
+
+export interface CashSessionPaymentTotal {
+  payment_method_id: number;
+  payment_method_name: string;
+  sales_count: number;
+  amount: string;
+}
+
+export interface CashCountLine {
+  denomination: string;
+  quantity: number;
+  amount: string;
+}
+
+/** Corte X (turno abierto) o Z (turno cerrado) */
+export interface CashSessionReport {
+  report_type: "X" | "Z";
+  session: CashSession;
+  sales_count: number;
+  sales_total: string;
+  cancelled_count: number;
+  cancelled_total: string;
+  refunds_count: number;
+  refunds_total: string;
+  payments: CashSessionPaymentTotal[];
+  cash_sales: string;
+  cash_refunds: string;
+  cash_in: string; // Entradas de efectivo (cambio, depósitos)
+  cash_out: string; // Retiros de efectivo (caja fuerte, pagos)
+  credit_payments: string; // Abonos a cuentas de crédito recibidos en el turno
+  cash_credit_payments: string;
+  movements: CashMovement[];
+  expected_cash: string; // Fondo + ventas, abonos y entradas en efectivo - devoluciones y retiros
+  counted_cash: string | null;
+  variance: string | null;
+  counts: CashCountLine[];
+  generated_at: string;
+}
@@ -7,6 +7,7 @@ export interface Category {
   id: number;
   name: string;
   is_active: boolean;
+  points_multiplier: string; // Multiplicador de puntos de lealtad
 }
 
 /** Respuesta de lista de categorías */
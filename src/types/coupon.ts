@@ -0,0 +1,62 @@
+/**
+ * Tipos relacionados con cupones
+ */
+
+/** Lote de cupones con el valor y condiciones de sus códigos */
+export interface CouponBatch {
+  id: number;
+  name: string;
+  discount_type: "percent" | "amount";
+  discount_value: string; // Decimal from DB comes as string
+  min_purchase: string; // Antes de impuestos
+  expires_on: string | null; // AAAA-MM-DD, válido hasta el final del día
+  usage_limit: number; // Usos permitidos por código
+  is_active: boolean;
+  codes_count: number;
+  redeemed_count: number;
+  created_at: string;
+  updated_at: string;
+}
+
+/** Código canjeable y las ventas que lo consumieron */
+export interface Coupon {
+  id: number;
+  batch_id: number;
+  code: string;
+  times_used: number;
+  sale_ids: string[];
+  created_at: string;
+}
+
+export interface CouponBatchDetail {
+  batch: CouponBatch;
+  coupons: Coupon[];
+}
+
+/** Filtros para listar lotes de cupones */
+export interface CouponBatchFilter {
+  status?: boolean;
+}
+
+/** Datos para generar un lote de cupones */
+export interface NewCouponBatch {
+  name: string;
+  discount_type: "percent" | "amount";
+  discount_value: number;
+  min_purchase?: number;
+  expires_on?: string;
+  usage_limit?: number; // Por defecto un solo uso
+  quantity: number; // Cantidad de códigos a generar
+  prefix?: string;
+}
+
+/** Resultado de validar un código en caja */
+export interface CouponValidation {
+  code: string;
+  batch_name: string;
+  discount_type: "percent" | "amount";
+  discount_value: string;
+  min_purchase: string;
+  expires_on: string | null;
+  remaining_uses: number;
+}
@@ -0,0 +1,94 @@
+/**
+ * Tipos relacionados con cuentas de crédito de clientes (fiado)
+ */
+
+/** Tipo de movimiento de la cuenta */
+export type CreditEntryType = "charge" | "payment" | "refund" | "cancellation";
+
+/** Movimiento de la cuenta; positivo = cargo, negativo = abono */
+export interface CreditEntry {
+  id: number;
+  customer_id: number;
+  entry_type: CreditEntryType;
+  amount: string;
+  balance_after: string;
+  sale_id: string | null;
+  payment_method_id: number | null;
+  payment_method_name: string | null;
+  notes: string | null;
+  created_by: string;
+  created_by_name: string | null;
+  created_at: string;
+}
+
+/** Antigüedad del saldo; los abonos se aplican a los cargos más antiguos */
+export interface CreditAging {
+  current: string; // 0 a 30 días
+  days_31_60: string;
+  days_61_90: string;
+  over_90: string;
+}
+
+/** Estado de la cuenta de crédito de un cliente */
+export interface CreditAccount {
+  customer_id: number;
+  customer_name: string;
+  credit_limit: string;
+  balance: string;
+  available_credit: string;
+  ledger_balance: string; // Suma de los movimientos; debe ser igual al saldo
+  is_reconciled: boolean;
+  aging: CreditAging;
+  last_payment_at: string | null;
+}
+
+export interface CreditPaymentRequest {
+  customer_id: number;
+  payment_method_id: number;
+  amount: string;
+  notes?: string;
+}
+
+export interface CreditPaymentResponse {
+  entry: CreditEntry;
+  account: CreditAccount;
+}
+
+export interface SetCreditLimitRequest {
+  customer_id: number;
+  credit_limit: string;
+}
+
+export interface CreditLedgerFilter {
+  customer_id: number;
+  entry_type?: CreditEntryType;
+  date_from?: string; // AAAA-MM-DD
+  date_to?: string;
+  page: number;
+  limit: number;
+}
+
+export interface CreditLedgerResponse {
+  entries: CreditEntry[];
+  total_pages: number;
+  total_items: number;
+}
+
+/** Parámetros del estado de cuenta; sin fechas incluye todos los movimientos */
+export interface CreditStatementParams {
+  customer_id: number;
+  date_from?: string; // AAAA-MM-DD
+  date_to?: string;
+}
+
+export interface CreditStatement {
+  account: CreditAccount;
+  date_from: string | null;
+  date_to: string | null;
+  opening_balance: string; // Saldo antes del periodo
+  charges_total: string;
+  credits_total: string; // Abonos, devoluciones y cancelaciones
+  closing_balance: string;
+  entries: CreditEntry[];
+  generated_at: string;
+}
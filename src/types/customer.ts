@@ -0,0 +1,59 @@
+/**
+ * Tipos relacionados con clientes
+ */
+
+/** Cliente con datos fiscales */
+export interface Customer {
+  id: number;
+  name: string;
+  rfc: string | null;
+  tax_regime: string | null; // RegimenFiscal del SAT, ej: "616"
+  cfdi_use: string | null; // UsoCFDI del SAT, ej: "G03"
+  postal_code: string | null;
+  email: string | null;
+  phone: string | null;
+  is_active: boolean;
+  credit_limit: string;
+  credit_balance: string; // Saldo adeudado en la cuenta de crédito
+  loyalty_points: number;
+  created_at: string;
+  updated_at: string;
+}
+
+/** Filtros para listar clientes */
+export interface CustomerFilter {
+  status?: boolean;
+  search?: string; // Nombre, RFC, correo o teléfono
+  page: number;
+  limit: number;
+}
+
+/** Respuesta de listado de clientes */
+export interface CustomerListResponse {
+  customers: Customer[];
+  total_pages: number;
+  total_items: number;
+}
+
+/** Datos para crear un nuevo cliente */
+export interface NewCustomer {
+  name: string;
+  rfc?: string;
+  tax_regime?: string;
+  cfdi_use?: string;
+  postal_code?: string;
+  email?: string;
+  phone?: string;
+}
+
+/** Datos para actualizar un cliente (una cadena vacía limpia el campo) */
+export interface UpdateCustomer {
+  name?: string;
+  rfc?: string;
+  tax_regime?: string;
+  cfdi_use?: string;
+  postal_code?: string;
+  email?: string;
+  phone?: string;
+  is_active?: boolean;
+}
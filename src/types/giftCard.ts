@@ -0,0 +1,86 @@
+/**
+ * Tipos relacionados con tarjetas de regalo y vales
+ */
+
+/** Tipo de movimiento de saldo */
+export type GiftCardEntryType = "issue" | "redeem" | "refund" | "reversal" | "void";
+
+/** Tarjeta de regalo o vale */
+export interface GiftCard {
+  id: number;
+  code: string;
+  initial_amount: string; // Decimal de Rust viene como string
+  balance: string;
+  is_active: boolean;
+  sale_id: string | null; // Venta en que se vendió la tarjeta
+  created_at: string;
+  updated_at: string;
+}
+
+/** Movimiento de saldo; positivo = carga, negativo = consumo */
+export interface GiftCardEntry {
+  id: number;
+  gift_card_id: number;
+  entry_type: GiftCardEntryType;
+  amount: string;
+  balance_after: string;
+  sale_id: string | null;
+  refund_id: number | null;
+  notes: string | null;
+  created_by: string;
+  created_by_name: string | null;
+  created_at: string;
+}
+
+export interface GiftCardDetail {
+  card: GiftCard;
+  entries: GiftCardEntry[];
+}
+
+export interface GiftCardFilter {
+  search?: string; // Código o parte del código
+  is_active?: boolean;
+  with_balance?: boolean; // Solo tarjetas con saldo disponible
+  page: number;
+  limit: number;
+}
+
+export interface GiftCardListResponse {
+  gift_cards: GiftCard[];
+  total_pages: number;
+  total_items: number;
+}
+
+/** Emisión de un vale fuera de una venta */
+export interface NewGiftCard {
+  amount: number;
+  code?: string; // Código impreso en una tarjeta física; se genera si no se indica
+  notes?: string;
+}
+
+export interface DeactivateGiftCardRequest {
+  gift_card_id: number;
+  reason: string;
+}
+
+/** Parámetros del reporte de saldos; sin fechas incluye todos los movimientos */
+export interface GiftCardLiabilityParams {
+  date_from?: string; // AAAA-MM-DD
+  date_to?: string;
+}
+
+export interface GiftCardMovementTotal {
+  entry_type: GiftCardEntryType;
+  count: number;
+  amount: string;
+}
+
+/** Pasivo por tarjetas de regalo: saldo pendiente de canjear */
+export interface GiftCardLiabilityReport {
+  outstanding_balance: string;
+  cards_with_balance: number;
+  ledger_balance: string; // Debe coincidir con outstanding_balance
+  is_reconciled: boolean;
+  movements: GiftCardMovementTotal[];
+  generated_at: string;
+}
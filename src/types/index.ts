@@ -12,6 +12,11 @@ export type {
   ProductListResponse,
   StockStatus,
   StockStatusType,
+  UnitOfMeasure,
+  ProductCodeType,
+  ProductLookup,
+  ProductTax,
+  TaxType,
 } from "./product";
 
 // Auth types
@@ -24,6 +29,59 @@ export type { PermissionCode } from "./permissions";
 // Category types
 export type { Category, CategoryListResponse, NewCategory, UpdateCategory } from "./category";
 
+// Customer types
+export type {
+  Customer,
+  CustomerFilter,
+  CustomerListResponse,
+  NewCustomer,
+  UpdateCustomer,
+} from "./customer";
+
+// Promotion types
+export type { Promotion, PromotionType, PromotionFilter, PromotionInput } from "./promotion";
+
+// Coupon types
+export type {
+  Coupon,
+  CouponBatch,
+  CouponBatchDetail,
+  CouponBatchFilter,
+  CouponValidation,
+  NewCouponBatch,
+} from "./coupon";
+
+// Quote types
+export type {
+  Quote,
+  QuoteStatus,
+  QuoteLineDetail,
+  QuoteDetailResponse,
+  CreateQuoteRequest,
+  ConvertQuoteRequest,
+  QuoteFilter,
+  QuoteListResponse,
+} from "./quote";
+
+// Cash session types
+export type {
+  CashSession,
+  CashSessionStatus,
+  OpenCashSessionRequest,
+  DenominationCount,
+  CloseCashSessionRequest,
+  CashSessionFilter,
+  CashSessionListResponse,
+  CashSessionPaymentTotal,
+  CashCountLine,
+  CashSessionReport,
+  CashMovement,
+  CashMovementType,
+  CreateCashMovementRequest,
+  CashMovementFilter,
+  CashMovementListResponse,
+} from "./cash";
+
 // Cart types
 export type {
   CartItem,
@@ -34,8 +92,68 @@ export type {
   CreateSaleRequest,
   CreateSaleResponse,
   PaymentMethodResponse,
+  ParkSaleRequest,
+  ParkedSale,
+  ParkedSaleItem,
+  ResumedParkedSale,
+  SaleRecord,
+  SaleLineTax,
 } from "./cart";
 
+// Layaway types
+export type {
+  Layaway,
+  LayawayStatus,
+  LayawayFilter,
+  LayawayListResponse,
+  LayawayPaymentRequest,
+  LayawayPaymentResponse,
+  CancelLayawayRequest,
+  CancelLayawayResponse,
+} from "./layaway";
+
+// Customer credit types
+export type {
+  CreditEntryType,
+  CreditEntry,
+  CreditAging,
+  CreditAccount,
+  CreditPaymentRequest,
+  CreditPaymentResponse,
+  SetCreditLimitRequest,
+  CreditLedgerFilter,
+  CreditLedgerResponse,
+  CreditStatementParams,
+  CreditStatement,
+} from "./credit";
+
+// Loyalty types
+export type {
+  LoyaltyEntryType,
+  CategoryMultiplier,
+  LoyaltyConfig,
+  UpdateLoyaltyConfigRequest,
+  LoyaltyEntry,
+  LoyaltyAccount,
+  LoyaltyEntryFilter,
+  LoyaltyEntryListResponse,
+} from "./loyalty";
+
+// Gift card types
+export type {
+  GiftCardEntryType,
+  GiftCard,
+  GiftCardEntry,
+  GiftCardDetail,
+  GiftCardFilter,
+  GiftCardListResponse,
+  NewGiftCard,
+  DeactivateGiftCardRequest,
+  GiftCardLiabilityParams,
+  GiftCardMovementTotal,
+  GiftCardLiabilityReport,
+} from "./giftCard";
+
 // Inventory types
 export type { InventoryFilters, PaginationOptions, SortOptions } from "./inventory";
 
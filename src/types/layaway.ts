@@ -0,0 +1,63 @@
+/**
+ * Tipos relacionados con apartados (ventas con anticipo y abonos)
+ */
+
+import type { SalePaymentRequest, SaleRecord } from "./cart";
+
+/** Estado del apartado */
+export type LayawayStatus = "pending" | "completed" | "cancelled";
+
+/** Apartado con lo abonado y su saldo */
+export interface Layaway {
+  sale: SaleRecord;
+  customer_name: string | null;
+  amount_paid: string;
+  balance_due: string;
+  is_overdue: boolean; // Pendiente con fecha límite vencida
+}
+
+export interface LayawayFilter {
+  status?: LayawayStatus;
+  customer_id?: number;
+  overdue?: boolean;
+  page: number;
+  limit: number;
+}
+
+export interface LayawayListResponse {
+  layaways: Layaway[];
+  total_pages: number;
+  total_items: number;
+}
+
+/** Abono a un apartado; en efectivo puede exceder el saldo */
+export interface LayawayPaymentRequest {
+  sale_id: string;
+  payments: SalePaymentRequest[];
+}
+
+export interface LayawayPaymentResponse {
+  sale_id: string;
+  amount_paid: string;
+  change: string;
+  total_paid: string;
+  balance_due: string;
+  completed: boolean; // El abono liquidó el apartado
+  points_earned: number; // Puntos acumulados al liquidar
+}
+
+/** Cancelación de un apartado pendiente; la penalización aplica si ya venció */
+export interface CancelLayawayRequest {
+  sale_id: string;
+  reason: string;
+  payment_method_id?: number; // Forma de reembolso; efectivo si no se indica
+  gift_card_code?: string; // Tarjeta a abonar; sin código se emite un vale nuevo
+}
+
+export interface CancelLayawayResponse {
+  sale: SaleRecord;
+  penalty_amount: string;
+  refund_amount: string;
+  refund_id: number | null;
+  gift_card_code: string | null; // Vale o tarjeta donde quedó el saldo a favor
+}
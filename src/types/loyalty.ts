@@ -0,0 +1,69 @@
+/**
+ * Tipos relacionados con el programa de puntos de lealtad
+ */
+
+/** Tipo de movimiento de puntos */
+export type LoyaltyEntryType = "earn" | "redeem" | "refund" | "reversal";
+
+/** Multiplicador de puntos de una categoría */
+export interface CategoryMultiplier {
+  category_id: number;
+  category_name: string;
+  multiplier: string;
+}
+
+/** Configuración del programa de puntos */
+export interface LoyaltyConfig {
+  earn_rate: string; // Puntos por peso pagado
+  point_value: string; // Pesos por punto al canjear
+  is_active: boolean;
+  category_multipliers: CategoryMultiplier[]; // Solo las distintas de 1
+  updated_by: string | null;
+  updated_at: string;
+}
+
+export interface UpdateLoyaltyConfigRequest {
+  earn_rate?: string;
+  point_value?: string;
+  is_active?: boolean;
+  category_multipliers?: { category_id: number; multiplier: string }[];
+}
+
+/** Movimiento de puntos; positivo = acumula, negativo = descuenta */
+export interface LoyaltyEntry {
+  id: number;
+  customer_id: number;
+  entry_type: LoyaltyEntryType;
+  points: number;
+  balance_after: number;
+  sale_id: string | null;
+  notes: string | null;
+  created_by: string;
+  created_by_name: string | null;
+  created_at: string;
+}
+
+/** Saldo de puntos de un cliente */
+export interface LoyaltyAccount {
+  customer_id: number;
+  customer_name: string;
+  points: number;
+  points_value: string; // Valor en pesos al canjear
+  ledger_points: number; // Suma de los movimientos; debe ser igual al saldo
+  is_reconciled: boolean;
+}
+
+export interface LoyaltyEntryFilter {
+  customer_id: number;
+  entry_type?: LoyaltyEntryType;
+  date_from?: string; // AAAA-MM-DD
+  date_to?: string;
+  page: number;
+  limit: number;
+}
+
+export interface LoyaltyEntryListResponse {
+  entries: LoyaltyEntry[];
+  total_pages: number;
+  total_items: number;
+}
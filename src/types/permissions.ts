@@ -8,6 +8,9 @@ export const PERMISSIONS = {
   SALES_VIEW: "sales.view",
   SALES_REFUND: "sales.refund",
   SALES_CANCEL: "sales.cancel",
+  SALES_PRICE_OVERRIDE: "sales.price_override",
+  SALES_DISCOUNT: "sales.discount",
+  SALES_LAYAWAY: "sales.layaway",
 
   // Productos
   PRODUCTS_VIEW: "products.view",
@@ -37,6 +40,37 @@ export const PERMISSIONS = {
   CUSTOMERS_CREATE: "customer.create",
   CUSTOMERS_UPDATE: "customer.update",
   CUSTOMERS_DELETE: "customer.delete",
+  CUSTOMERS_CREDIT: "customer.credit",
+  CUSTOMERS_CREDIT_LIMIT: "customer.credit_limit",
+
+  // Facturas
+  INVOICES_VIEW: "invoices.view",
+  INVOICES_CREATE: "invoices.create",
+
+  // Promociones
+  PROMOTIONS_VIEW: "promotions.view",
+  PROMOTIONS_MANAGE: "promotions.manage",
+
+  // Cupones
+  COUPONS_VIEW: "coupons.view",
+  COUPONS_MANAGE: "coupons.manage",
+
+  // Cotizaciones
+  QUOTES_VIEW: "quotes.view",
+  QUOTES_CREATE: "quotes.create",
+
+  // Caja
+  CASH_OPEN: "cash.open",
+  CASH_CLOSE: "cash.close",
+  CASH_VIEW: "cash.view",
+  CASH_MOVEMENTS: "cash.movements",
+
+  // Lealtad
+  LOYALTY_MANAGE: "loyalty.manage",
+
+  // Tarjetas de regalo
+  GIFT_CARDS_VIEW: "gift_cards.view",
+  GIFT_CARDS_MANAGE: "gift_cards.manage",
 
   // Perfiles
   PROFILES_VIEW: "profiles.view",
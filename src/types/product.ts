@@ -2,6 +2,20 @@
  * Tipos relacionados con productos
  */
 
+/** Unidad de medida: pieza, kilogramo, gramo, litro y metro */
+export type UnitOfMeasure = "piece" | "kg" | "g" | "l" | "m";
+
+/** Impuestos que puede causar un producto */
+export type TaxType = "iva" | "ieps";
+
+/** Impuesto de un producto; la tasa va en porcentaje, igual que `tax` */
+export interface ProductTax {
+  tax_type: TaxType;
+  rate: string | number;
+  is_included: boolean; // El precio ya incluye el impuesto
+  is_exempt: boolean; // Exento (distinto de tasa 0%)
+}
+
 /** Producto completo (coincide con backend) */
 export interface Product {
   id: number;
@@ -9,11 +23,17 @@ export interface Product {
   category_id: number | null;
   category_name: string | null;
   code: string;
-  stock: number;
+  stock: string; // Decimal; fraccionario en productos a granel
   is_active: boolean;
   price: string; // Decimal from DB comes as string
   cost: string; // Decimal from DB comes as string
   tax: string; // Decimal from DB comes as string
+  sat_product_key: string; // ClaveProdServ del SAT
+  sat_unit_key: string; // ClaveUnidad del SAT
+  is_gift_card: boolean; // Al venderse emite una tarjeta de regalo por pieza
+  unit_of_measure: UnitOfMeasure;
+  allow_fractions: boolean; // Se vende en cantidades fraccionarias (0.750 kg)
+  taxes: ProductTax[];
 }
 
 /** Filtros para obtener productos */
@@ -39,6 +59,12 @@ export interface NewProduct {
   price: number;
   cost: number;
   tax: number;
+  sat_product_key?: string;
+  sat_unit_key?: string;
+  is_gift_card?: boolean;
+  unit_of_measure?: UnitOfMeasure; // Por defecto piece
+  allow_fractions?: boolean;
+  taxes?: ProductTax[]; // Por defecto, IVA a la tasa de `tax`
   created_by: string;
 }
 
@@ -52,9 +78,27 @@ export interface UpdateProduct {
   price?: number;
   cost?: number;
   tax?: number;
+  sat_product_key?: string;
+  sat_unit_key?: string;
+  is_gift_card?: boolean;
+  unit_of_measure?: UnitOfMeasure;
+  allow_fractions?: boolean;
+  taxes?: ProductTax[]; // Reemplaza todos los impuestos del producto
   updated_by: string;
 }
 
+/** Origen del código leído: código del producto o etiqueta de báscula */
+export type ProductCodeType = "exact" | "scale_weight" | "scale_price";
+
+/** Producto encontrado por código de barras */
+export interface ProductLookup {
+  product: Product;
+  code_type: ProductCodeType;
+  plu: string | null; // PLU de la etiqueta de báscula
+  quantity: string | null; // Cantidad a agregar en la unidad del producto
+  embedded_price: string | null; // Importe impreso en la etiqueta, con impuestos
+}
+
 /** Estado del stock de un producto */
 export interface StockStatus {
   label: string;
@@ -0,0 +1,50 @@
+/**
+ * Tipos relacionados con promociones
+ */
+
+/** Tipo de promoción: NxM (ej. 3x2) o porcentaje de descuento */
+export type PromotionType = "nxm" | "percent";
+
+/** Promoción con su vigencia */
+export interface Promotion {
+  id: number;
+  name: string;
+  promotion_type: PromotionType;
+  buy_quantity: number | null; // N en NxM
+  pay_quantity: number | null; // M en NxM
+  discount_percent: string | null; // Decimal from DB comes as string
+  product_id: number | null;
+  category_id: number | null;
+  starts_on: string | null; // AAAA-MM-DD
+  ends_on: string | null;
+  weekdays: number[]; // 1 = lunes ... 7 = domingo; vacío = todos los días
+  starts_at: string | null; // HH:MM
+  ends_at: string | null;
+  is_active: boolean;
+  created_at: string;
+  updated_at: string;
+}
+
+/** Filtros para listar promociones */
+export interface PromotionFilter {
+  status?: boolean;
+  product_id?: number;
+  category_id?: number;
+}
+
+/** Datos para crear o reemplazar una promoción */
+export interface PromotionInput {
+  name: string;
+  promotion_type: PromotionType;
+  buy_quantity?: number;
+  pay_quantity?: number;
+  discount_percent?: number;
+  product_id?: number;
+  category_id?: number;
+  starts_on?: string;
+  ends_on?: string;
+  weekdays?: number[];
+  starts_at?: string;
+  ends_at?: string;
+  is_active?: boolean;
+}
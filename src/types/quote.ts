@@ -0,0 +1,89 @@
+/**
+ * Tipos relacionados con cotizaciones
+ */
+
+import type { DiscountRequest, SaleItemRequest, SaleLineTax, SalePaymentRequest } from "./cart";
+import type { UnitOfMeasure } from "./product";
+
+/** Estado de la cotización */
+export type QuoteStatus = "open" | "converted";
+
+/** Cotización con los importes vigentes al emitirla */
+export interface Quote {
+  id: number;
+  customer_id: number;
+  customer_name: string | null;
+  valid_until: string; // AAAA-MM-DD
+  expired: boolean;
+  notes: string | null;
+  discount_type: "percent" | "amount" | null;
+  discount_value: string; // Decimal from DB comes as string
+  discount_amount: string;
+  subtotal: string;
+  total: string;
+  status: QuoteStatus;
+  sale_id: string | null; // Venta generada al convertirla
+  created_at: string;
+  updated_at: string;
+  created_by: string;
+  updated_by: string;
+}
+
+export interface QuoteLineDetail {
+  id: number;
+  product_id: number;
+  product_name: string | null;
+  product_code: string | null;
+  unit_of_measure: UnitOfMeasure | null;
+  quantity: string;
+  unit_price: string;
+  price_override: string | null;
+  discount: DiscountRequest | null;
+  discount_amount: string;
+  ticket_discount_amount: string;
+  promotion_id: number | null;
+  promotion_discount_amount: string;
+  subtotal: string;
+  tax_rate: string;
+  tax_amount: string;
+  taxes: SaleLineTax[];
+  total: string;
+}
+
+export interface QuoteDetailResponse {
+  quote: Quote;
+  created_by_name: string | null;
+  lines: QuoteLineDetail[];
+}
+
+/** Request para emitir una cotización */
+export interface CreateQuoteRequest {
+  customer_id: number;
+  valid_until: string; // AAAA-MM-DD
+  notes?: string;
+  items: SaleItemRequest[];
+  discount?: DiscountRequest;
+}
+
+/** Request para convertir una cotización en venta con los precios vigentes */
+export interface ConvertQuoteRequest {
+  quote_id: number;
+  payments: SalePaymentRequest[];
+  subtotal: number;
+  total: number;
+  idempotency_key?: string; // Generada por la caja; un reintento regresa la venta original
+}
+
+/** Filtros para listar cotizaciones */
+export interface QuoteFilter {
+  customer_id?: number;
+  status?: QuoteStatus;
+  page: number;
+  limit: number;
+}
+
+export interface QuoteListResponse {
+  quotes: Quote[];
+  total_pages: number;
+  total_items: number;
+}
@@ -36,6 +36,7 @@ export interface Profile {
   name: string;
   description: string;
   is_active: boolean;
+  max_discount_percent: string | null; // null = sin límite
 }
 
 /** Datos para crear un nuevo usuario */